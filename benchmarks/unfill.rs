@@ -1,4 +1,4 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
 pub fn benchmark(c: &mut Criterion) {
     let words_per_line = [
@@ -17,6 +17,28 @@ pub fn benchmark(c: &mut Criterion) {
     assert_eq!(text.len(), 2650); // The size for reference.
 
     c.bench_function("unfill", |b| b.iter(|| textwrap::unfill(&text)));
+
+    // Large documents, wrapped and indented as a block quote. The
+    // refill benchmarks use the original width and a new width.
+    let mut group = c.benchmark_group("large");
+    for length in [10_000, 100_000, 1_000_000] {
+        let text = lipsum::lipsum_words_from_seed(length / 5, 42);
+        let options = textwrap::Options::new(72)
+            .initial_indent("> ")
+            .subsequent_indent("> ");
+        let filled = textwrap::fill(&text, &options);
+
+        group.bench_with_input(BenchmarkId::new("unfill", length), &filled, |b, filled| {
+            b.iter(|| textwrap::unfill(filled))
+        });
+        for width in [72, 60] {
+            let name = format!("refill_{width}");
+            group.bench_with_input(BenchmarkId::new(&name, length), &filled, |b, filled| {
+                b.iter(|| textwrap::refill(filled, width))
+            });
+        }
+    }
+    group.finish();
 }
 
 criterion_group!(benches, benchmark);
@@ -283,6 +283,7 @@ impl From<WasmPenalties> for Penalties {
             short_last_line_fraction: val.short_last_line_fraction,
             short_last_line_penalty: val.short_last_line_penalty,
             hyphen_penalty: val.hyphen_penalty,
+            ..Penalties::new()
         }
     }
 }
@@ -11,6 +11,9 @@ struct Penalties {
     short_last_line_fraction: usize,
     short_last_line_penalty: usize,
     hyphen_penalty: usize,
+    hard_hyphen_penalty: usize,
+    looseness: i32,
+    sentence_start_penalty: usize,
 }
 
 impl Into<wrap_algorithms::Penalties> for Penalties {
@@ -21,6 +24,9 @@ impl Into<wrap_algorithms::Penalties> for Penalties {
             short_last_line_fraction: std::cmp::max(1, self.short_last_line_fraction),
             short_last_line_penalty: self.short_last_line_penalty,
             hyphen_penalty: self.hyphen_penalty,
+            hard_hyphen_penalty: self.hard_hyphen_penalty,
+            looseness: self.looseness,
+            sentence_start_penalty: self.sentence_start_penalty,
         }
     }
 }
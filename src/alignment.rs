@@ -0,0 +1,123 @@
+//! Functionality for aligning wrapped lines inside a width.
+
+use crate::core::display_width;
+
+/// Horizontal alignment of a line, see [`pad_lines()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Alignment {
+    /// Put the line at the left and pad it on the right.
+    #[default]
+    Left,
+    /// Put the line in the middle. If the line cannot be centered
+    /// exactly, it is moved a column to the left.
+    Center,
+    /// Put the line at the right and pad it on the left.
+    Right,
+}
+
+/// Pad lines with `' '` so that they are `width` columns wide.
+///
+/// Each line is aligned on its own. The lines are measured with
+/// [`display_width()`], so wide characters such as CJK characters
+/// and emojis are aligned correctly. Lines which are wider than
+/// `width` are returned unchanged.
+///
+/// Use [`center_block()`] to center the lines as a whole.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{pad_lines, wrap, Alignment};
+///
+/// let lines = wrap("Memory safety without garbage collection.", 15);
+/// assert_eq!(pad_lines(&lines, 17, Alignment::Center),
+///            vec!["  Memory safety  ",
+///                 " without garbage ",
+///                 "   collection.   "]);
+/// assert_eq!(pad_lines(&["你好", "ab"], 6, Alignment::Right),
+///            vec!["  你好", "    ab"]);
+/// ```
+pub fn pad_lines<S: AsRef<str>>(lines: &[S], width: usize, alignment: Alignment) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            let line = line.as_ref();
+            let gap = width.saturating_sub(display_width(line));
+            let left = match alignment {
+                Alignment::Left => 0,
+                Alignment::Center => gap / 2,
+                Alignment::Right => gap,
+            };
+            pad(line, left, gap - left)
+        })
+        .collect()
+}
+
+/// Center a block of lines so that they are `width` columns wide.
+///
+/// Unlike [`pad_lines()`] with [`Alignment::Center`], the lines are
+/// moved as a block: every line gets the same left margin, which is
+/// computed from the widest line. This keeps the lines aligned with
+/// each other, e.g., for a centered paragraph or code block. The
+/// lines are padded on the right so that they are `width` columns
+/// wide. Lines are measured with [`display_width()`].
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{center_block, wrap};
+///
+/// let lines = wrap("Memory safety without garbage collection.", 15);
+/// assert_eq!(center_block(&lines, 17),
+///            vec![" Memory safety   ",
+///                 " without garbage ",
+///                 " collection.     "]);
+/// ```
+pub fn center_block<S: AsRef<str>>(lines: &[S], width: usize) -> Vec<String> {
+    let block_width = lines
+        .iter()
+        .map(|line| display_width(line.as_ref()))
+        .max()
+        .unwrap_or(0);
+    let left = width.saturating_sub(block_width) / 2;
+    lines
+        .iter()
+        .map(|line| {
+            let line = line.as_ref();
+            let right = width.saturating_sub(left + display_width(line));
+            pad(line, left, right)
+        })
+        .collect()
+}
+
+/// Add `left` and `right` spaces around `line`.
+fn pad(line: &str, left: usize, right: usize) -> String {
+    let mut result = String::with_capacity(left + line.len() + right);
+    result.extend(std::iter::repeat(' ').take(left));
+    result.push_str(line);
+    result.extend(std::iter::repeat(' ').take(right));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pad_lines_too_wide() {
+        assert_eq!(pad_lines(&["abcd"], 2, Alignment::Center), vec!["abcd"]);
+        assert_eq!(center_block(&["abcd", "a"], 2), vec!["abcd", "a "]);
+    }
+
+    #[test]
+    fn pad_lines_emoji() {
+        assert_eq!(pad_lines(&["😊"], 5, Alignment::Center), vec![" 😊  "]);
+        assert_eq!(pad_lines(&["😊"], 3, Alignment::Left), vec!["😊 "]);
+    }
+
+    #[test]
+    fn center_block_empty() {
+        assert!(center_block::<&str>(&[], 10).is_empty());
+        assert_eq!(center_block(&[""], 2), vec!["  "]);
+    }
+}
@@ -0,0 +1,204 @@
+//! Functions for debugging how text is wrapped.
+
+use std::borrow::Cow;
+
+use crate::core::{display_width, Fragment, FragmentBreak};
+use crate::wrap::{wrap_lines, LineParts, LineSink};
+use crate::Options;
+
+/// Return a ruler which is `width` columns wide.
+///
+/// Every fifth column is marked with `':'` and every tenth column
+/// with the last digit of its tens. Print the ruler above wrapped
+/// text to see which column each character is in.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::debug_ruler;
+///
+/// assert_eq!(debug_ruler(25), "....:....1....:....2....:");
+/// ```
+pub fn debug_ruler(width: usize) -> String {
+    (1..=width)
+        .map(|column| match column % 10 {
+            0 => char::from(b'0' + (column / 10 % 10) as u8),
+            5 => ':',
+            _ => '.',
+        })
+        .collect()
+}
+
+/// Wrap `text` and annotate each line with the reason it ended.
+///
+/// The result starts with a [`debug_ruler()`] for the width of
+/// `width_or_options`. Each wrapped line follows, padded to the
+/// width and followed by `|` and an annotation:
+///
+/// * `whitespace`: the line was broken at whitespace, which was
+///   removed.
+/// * `boundary`: the line was broken between two words without
+///   whitespace, or inside a word which was too long for the line.
+/// * `hyphen`: the line was broken after a hyphen in the text.
+/// * `penalty "-"`: the line was broken inside a word and the
+///   penalty was added to the line.
+/// * `line ending`: the line ended in the input text.
+/// * `end`: the end of the text.
+///
+/// The lines are the same as the ones returned by
+/// [`wrap()`](crate::wrap()) since the same code is used to wrap
+/// them. This makes the output useful when tuning the
+/// [`Penalties`](crate::wrap_algorithms::Penalties) and when
+/// reporting bugs.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{annotate_wrap, Options, WrapAlgorithm};
+///
+/// let options = Options::new(12).wrap_algorithm(WrapAlgorithm::FirstFit);
+/// assert_eq!(
+///     annotate_wrap("Memory-safety without garbage\ncollection.", &options),
+///     "....:....1..\n\
+///      Memory-     |hyphen\n\
+///      safety      |whitespace\n\
+///      without     |whitespace\n\
+///      garbage     |line ending\n\
+///      collection. |end"
+/// );
+/// ```
+pub fn annotate_wrap<'a, Opt>(text: &str, width_or_options: Opt) -> String
+where
+    Opt: Into<Options<'a>>,
+{
+    let options: Options = width_or_options.into();
+    // This cannot fail since `MinWidth::Error` is replaced.
+    let options = options.proceed_on_min_width_error().build().unwrap();
+    let mut sink = AnnotatedLines {
+        lines: Vec::new(),
+        annotations: Vec::new(),
+    };
+    // Wrapping text cannot overflow since the line widths are
+    // integers, and it cannot fail with `MinWidth::Error` since that
+    // is replaced, see `try_wrap`.
+    wrap_lines(text, &options, &mut sink).unwrap();
+    if let Some(annotation) = sink.annotations.last_mut() {
+        *annotation = Cow::from("end");
+    }
+
+    let mut result = debug_ruler(options.width);
+    for (line, annotation) in sink.lines.iter().zip(sink.annotations) {
+        result.push('\n');
+        result.push_str(line);
+        let padding = options.width.saturating_sub(display_width(line));
+        result.extend(std::iter::repeat(' ').take(padding));
+        result.push('|');
+        result.push_str(&annotation);
+    }
+    result
+}
+
+/// Collects the wrapped lines together with the reason they ended.
+struct AnnotatedLines<'a> {
+    lines: Vec<Cow<'a, str>>,
+    annotations: Vec<Cow<'static, str>>,
+}
+
+impl<'a> LineSink<'a> for AnnotatedLines<'a> {
+    fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn end_input_line(&mut self) {
+        if let Some(annotation) = self.annotations.last_mut() {
+            *annotation = Cow::from("line ending");
+        }
+    }
+
+    fn push_line(&mut self, line: Cow<'a, str>) {
+        self.lines.push(line);
+        self.annotations.push(Cow::from(""));
+    }
+
+    fn push_parts(&mut self, parts: LineParts<'a, '_>) {
+        let annotation = match parts.words.last() {
+            None => Cow::from(""),
+            Some(word) => match word.break_kind() {
+                FragmentBreak::Whitespace => Cow::from("whitespace"),
+                FragmentBreak::Boundary => Cow::from("boundary"),
+                FragmentBreak::HardHyphen => Cow::from("hyphen"),
+                FragmentBreak::InsertedHyphen => Cow::from(format!("penalty {:?}", word.penalty)),
+            },
+        };
+        self.lines.push(parts.into_line());
+        self.annotations.push(annotation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wrap, WordSeparator, WrapAlgorithm};
+
+    #[test]
+    fn debug_ruler_widths() {
+        assert_eq!(debug_ruler(0), "");
+        assert_eq!(debug_ruler(4), "....");
+        assert_eq!(debug_ruler(100).chars().last(), Some('0'));
+        assert_eq!(&debug_ruler(110)[105..], "....1");
+    }
+
+    #[test]
+    fn annotate_wrap_matches_wrap() {
+        let options = Options::new(10)
+            .initial_indent("> ")
+            .subsequent_indent("  ")
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .word_separator(WordSeparator::AsciiSpace);
+        let text = "Unnecessarily long words\n\nare hyphenated";
+        let annotated = annotate_wrap(text, &options);
+        let lines = annotated
+            .lines()
+            .skip(1)
+            .map(|line| line.split('|').next().unwrap().trim_end())
+            .collect::<Vec<_>>();
+        let wrapped = wrap(text, &options);
+        assert_eq!(
+            lines,
+            wrapped
+                .iter()
+                .map(|line| line.trim_end())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            annotated
+                .lines()
+                .map(|line| line.split('|').nth(1))
+                .collect::<Vec<_>>(),
+            vec![
+                None,
+                Some("boundary"),
+                Some("whitespace"),
+                Some("whitespace"),
+                Some("line ending"),
+                Some("line ending"),
+                Some("whitespace"),
+                Some("boundary"),
+                Some("end")
+            ]
+        );
+    }
+
+    #[test]
+    fn annotate_wrap_inserted_penalty() {
+        let options = Options::new(8)
+            .break_words(false)
+            .word_splitter(crate::WordSplitter::Custom(|word| vec![word.len() / 2]))
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .word_separator(WordSeparator::AsciiSpace);
+        assert_eq!(
+            annotate_wrap("abcdefghij", &options),
+            "....:...\nabcde-  |penalty \"-\"\nfghij   |end"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Functionality for wrapping text into a fixed-size block.
+
+use crate::core::{ch_width_with, display_width_with, skip_ansi_escape_sequence};
+use crate::{wrap, Options};
+
+/// Wrap text into a block of exactly `height` lines which are all
+/// exactly [`Options::width`] columns wide.
+///
+/// This is useful for drawing text into a fixed area of the screen,
+/// e.g., in a widget of a terminal user interface. The `text` is
+/// wrapped with [`wrap()`], after which short lines are padded with
+/// `' '` and missing lines are added at the bottom.
+///
+/// If the text needs more than `height` lines, the last line of the
+/// block is wrapped again with room reserved for `placeholder`, which
+/// is added to show that text has been dropped. The line is
+/// truncated if not even its first word leaves room for the
+/// placeholder. Lines which are wider than the width, such as
+/// long words when [`Options::break_words`] is `false`, are
+/// truncated in the same way. A double-width character which does not
+/// fit in the last column is replaced with `' '`.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::wrap_block;
+///
+/// assert_eq!(wrap_block("Hello World!", 8, 3, "..."),
+///            vec!["Hello   ",
+///                 "World!  ",
+///                 "        "]);
+///
+/// let text = "The quick brown fox jumps over the lazy dog.";
+/// assert_eq!(wrap_block(text, 10, 2, "..."),
+///            vec!["The quick ",
+///                 "brown...  "]);
+/// ```
+///
+/// Wide characters are measured with their displayed width:
+///
+/// ```
+/// use textwrap::{core::display_width, wrap_block};
+///
+/// # #[cfg(all(feature = "unicode-width", feature = "unicode-linebreak"))] {
+/// let lines = wrap_block("你好世界，你好世界", 7, 2, "…");
+/// assert_eq!(lines, vec!["你好世 ", "界，你…"]);
+/// assert!(lines.iter().all(|line| display_width(line) == 7));
+/// # }
+/// ```
+pub fn wrap_block<'a, Opt>(
+    text: &str,
+    width_or_options: Opt,
+    height: usize,
+    placeholder: &str,
+) -> Vec<String>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options: Options = width_or_options.into();
+    // This cannot fail since `MinWidth::Error` is replaced.
+    let options = options.proceed_on_min_width_error().build().unwrap();
+    let overrides = options.width_overrides;
+    let width = options.width;
+    let placeholder = truncate(placeholder, width, overrides);
+    let placeholder_width = display_width_with(placeholder, overrides);
+
+    let wrapped_lines = wrap(text, &options);
+    let mut lines = Vec::with_capacity(height);
+    for row in 0..height {
+        let line = wrapped_lines.get(row).map_or("", |line| line);
+        let is_cut = row + 1 == height && wrapped_lines.len() > height;
+        let mut block_line = if is_cut {
+            cut_line(line, row, &options, placeholder)
+        } else if display_width_with(line, overrides) > width {
+            let mut block_line = String::from(truncate(line, width - placeholder_width, overrides));
+            block_line.push_str(placeholder);
+            block_line
+        } else {
+            String::from(line)
+        };
+
+        let padding = width.saturating_sub(display_width_with(&block_line, overrides));
+        block_line.extend(std::iter::repeat(' ').take(padding));
+        lines.push(block_line);
+    }
+
+    lines
+}
+
+/// Shorten the wrapped `line` so that it ends with `placeholder`.
+///
+/// The line is wrapped again with `placeholder` reserved at the end,
+/// so that it is cut at a word boundary when possible.
+fn cut_line(line: &str, row: usize, options: &Options<'_>, placeholder: &str) -> String {
+    let overrides = options.width_overrides;
+    let indent = options.indent_for(row);
+    let content = line.strip_prefix(indent).unwrap_or(line);
+    let indent = &line[..line.len() - content.len()];
+    let indent_width = display_width_with(indent, overrides);
+    let available = options.width - display_width_with(placeholder, overrides);
+    let mut narrower = options.clone();
+    narrower.width = available.saturating_sub(indent_width);
+    narrower.last_line_reserve = 0;
+    narrower.initial_indent = "".into();
+    narrower.subsequent_indent = "".into();
+    narrower.indents = &[];
+    narrower.indent_width = None;
+    // The ranges refer to the whole text, not to this line.
+    narrower.protected_ranges = &[];
+    let mut block_line = match wrap(content, &narrower).first() {
+        Some(first)
+            if !first.is_empty()
+                && indent_width + display_width_with(first, overrides) <= available =>
+        {
+            format!("{}{}", indent, first)
+        }
+        _ => String::from(truncate(line, available, overrides)),
+    };
+    block_line.push_str(placeholder);
+    block_line
+}
+
+/// Return the longest prefix of `line` which is at most `width`
+/// columns wide.
+fn truncate<'a>(line: &'a str, width: usize, overrides: &[(char, usize)]) -> &'a str {
+    let mut char_indices = line.char_indices();
+    let mut column = 0;
+    while let Some((idx, ch)) = char_indices.next() {
+        if skip_ansi_escape_sequence(ch, &mut char_indices.by_ref().map(|(_, ch)| ch)) {
+            continue;
+        }
+        column += ch_width_with(ch, overrides);
+        if column > width {
+            return &line[..idx];
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::display_width;
+
+    #[test]
+    fn wrap_block_empty_text() {
+        assert_eq!(wrap_block("", 3, 2, "~"), vec!["   ", "   "]);
+        assert!(wrap_block("foo", 3, 0, "~").is_empty());
+    }
+
+    #[test]
+    fn wrap_block_long_words() {
+        let options = Options::new(5).break_words(false);
+        assert_eq!(
+            wrap_block("foo verylongword bar", &options, 3, "..."),
+            vec!["foo  ", "ve...", "bar  "]
+        );
+        assert_eq!(wrap_block("foo bar", 2, 1, "..."), vec![".."]);
+    }
+
+    #[test]
+    fn wrap_block_cuts_at_word_boundary() {
+        let options = Options::new(12).subsequent_indent("  ");
+        assert_eq!(
+            wrap_block("foo bar baz quux abcde def", &options, 2, "~"),
+            vec!["foo bar baz ", "  quux~     "]
+        );
+        // A long first word is truncated.
+        assert_eq!(
+            wrap_block("foo barbazquux abc", 8, 2, "..."),
+            vec!["foo     ", "barba..."]
+        );
+    }
+
+    #[test]
+    fn wrap_block_exact_width() {
+        let text = "Emojis 😊😊😊 and CJK 你好世界 text";
+        for width in 1..20 {
+            for line in wrap_block(text, width, 4, "…") {
+                assert_eq!(display_width(&line), width, "line: {:?}", line);
+            }
+        }
+    }
+}
@@ -0,0 +1,563 @@
+//! Analysis of the places where text can be broken into lines.
+
+use std::ops::Range;
+
+use crate::core::{display_width, ends_with_hyphen, Fragment, FragmentBreak, Word};
+use crate::word_separators::split_wide_spaces;
+use crate::word_splitters::{retain_long_fragments, split_word_at};
+use crate::{Options, WordSplitter};
+
+/// The kind of a [`BreakPoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakKind {
+    /// A line break in the input text. The text is always broken
+    /// here, see [`Options::line_ending`].
+    LineEnding,
+    /// A break at whitespace between two words. The whitespace is
+    /// removed when the text is broken here.
+    Whitespace,
+    /// A break between two words which are not separated by
+    /// whitespace. The [`WordSeparator`](crate::WordSeparator)
+    /// decides where these breaks are allowed, e.g.,
+    /// [`WordSeparator::UnicodeBreakProperties`](crate::WordSeparator::UnicodeBreakProperties)
+    /// allows breaks after `'/'` and between East-Asian characters.
+    WordBoundary,
+    /// A break inside a word, found by the
+    /// [`WordSplitter`](crate::WordSplitter).
+    Hyphenation,
+}
+
+/// A place where text can be broken into lines, as found by
+/// [`analyze()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakPoint {
+    /// Byte offset of the break. When the text is broken here, the
+    /// next line starts at this offset.
+    pub offset: usize,
+    /// The kind of break.
+    pub kind: BreakKind,
+    /// String inserted at the end of the line when the text is broken
+    /// here. This is [`Options::hyphen`] when a word is hyphenated
+    /// and `""` otherwise.
+    pub penalty: &'static str,
+    /// The cost of breaking here with
+    /// [`WrapAlgorithm::OptimalFit`]: the
+    /// [`Penalties::hyphen_penalty`] when a hyphen is inserted and
+    /// the [`Penalties::hard_hyphen_penalty`] after a hyphen in the
+    /// text. Costs which depend on the whole line, such as the gap
+    /// at the end of the line, are not included. The cost is zero
+    /// for other wrap algorithms.
+    ///
+    /// [`WrapAlgorithm::OptimalFit`]: crate::WrapAlgorithm::OptimalFit
+    /// [`Penalties::hyphen_penalty`]: crate::wrap_algorithms::Penalties::hyphen_penalty
+    /// [`Penalties::hard_hyphen_penalty`]: crate::wrap_algorithms::Penalties::hard_hyphen_penalty
+    pub cost: usize,
+}
+
+/// A candidate place for breaking a line, as passed to the
+/// [`Options::break_filter`] function.
+///
+/// The `offset` is relative to `line`, which is a single line of the
+/// input text, i.e., the text between two [`Options::line_ending`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakCandidate<'a> {
+    /// The line in which the break is found.
+    pub line: &'a str,
+    /// Byte offset of the break in `line`. When the text is broken
+    /// here, the next line starts at this offset.
+    pub offset: usize,
+    /// The kind of break. This is never [`BreakKind::LineEnding`]
+    /// since line endings cannot be vetoed.
+    pub kind: BreakKind,
+    /// String inserted at the end of the line when the text is broken
+    /// here.
+    pub penalty: &'static str,
+}
+
+impl<'a> BreakCandidate<'a> {
+    /// The text before the break, including trailing whitespace.
+    pub fn before(&self) -> &'a str {
+        &self.line[..self.offset]
+    }
+
+    /// The text after the break.
+    pub fn after(&self) -> &'a str {
+        &self.line[self.offset..]
+    }
+}
+
+/// Find all places where text can be broken into lines.
+///
+/// This runs the [`WordSeparator`](crate::WordSeparator) and
+/// [`WordSplitter`](crate::WordSplitter) from `width_or_options` on
+/// `text` and reports every break they allow, in order of
+/// increasing offset. The wrap algorithm is not run, so this tells
+/// you where [`wrap()`](crate::wrap()) _may_ break the text, not
+/// where it _will_ break it.
+///
+/// Breaks rejected by [`Options::break_filter`],
+/// [`Options::no_break_before`], or [`Options::no_break_after`] and
+/// breaks inside [`Options::protected_ranges`] are not reported.
+/// Breaks made when [`Options::break_words`] is `true` are not
+/// reported either since long words can be broken between any two
+/// characters.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{analyze, BreakKind, BreakPoint, Options};
+///
+/// let breaks = analyze("foo-bar baz\nqux", &Options::new(80));
+/// assert_eq!(breaks, vec![
+///     BreakPoint { offset: 4, kind: BreakKind::Hyphenation, penalty: "", cost: 0 },
+///     BreakPoint { offset: 8, kind: BreakKind::Whitespace, penalty: "", cost: 0 },
+///     BreakPoint { offset: 12, kind: BreakKind::LineEnding, penalty: "", cost: 0 },
+/// ]);
+/// ```
+pub fn analyze<'a, Opt>(text: &str, width_or_options: Opt) -> Vec<BreakPoint>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options = width_or_options.into();
+    let line_ending_str = options.line_ending.as_str();
+
+    let mut break_points = Vec::new();
+    let mut line_offset = 0;
+    for line in text.split(line_ending_str) {
+        if line_offset > 0 {
+            break_points.push(BreakPoint {
+                offset: line_offset,
+                kind: BreakKind::LineEnding,
+                penalty: "",
+                cost: 0,
+            });
+        }
+
+        let word_splitter = options.word_splitter.resolve(line);
+        let mut words = merge_words(line, line_offset, &options).peekable();
+        while let Some((start, word)) = words.next() {
+            for idx in split_points(line, line_offset, start, &word, &word_splitter, &options) {
+                let penalty = hyphenation_penalty(
+                    &word,
+                    idx,
+                    options.hyphen,
+                    word_splitter.has_unicode_hyphens(),
+                );
+                let fragment_break = match penalty {
+                    "" => FragmentBreak::HardHyphen,
+                    _ => FragmentBreak::InsertedHyphen,
+                };
+                break_points.push(BreakPoint {
+                    offset: line_offset + start + idx,
+                    kind: BreakKind::Hyphenation,
+                    penalty,
+                    cost: break_cost(fragment_break, &options),
+                });
+            }
+
+            if words.peek().is_some() {
+                break_points.push(BreakPoint {
+                    offset: line_offset + start + word.len() + word.whitespace.len(),
+                    kind: separator_kind(&word),
+                    penalty: "",
+                    cost: break_cost(word.break_kind(), &options),
+                });
+            }
+        }
+
+        line_offset += line.len() + line_ending_str.len();
+    }
+
+    break_points
+}
+
+/// Split `line` into words and further into the pieces allowed by
+/// the word splitter. Rejected breaks are removed, see
+/// [`is_allowed()`]. The `line_offset` is the offset of `line` in
+/// the text, it is used for [`Options::protected_ranges`].
+pub(crate) fn split_filtered_words<'a>(
+    line: &'a str,
+    line_offset: usize,
+    options: &'a Options<'_>,
+) -> impl Iterator<Item = Word<'a>> {
+    let word_splitter = options.word_splitter.resolve(line);
+    merge_words(line, line_offset, options).flat_map(move |(start, word)| {
+        let split_points = split_points(line, line_offset, start, &word, &word_splitter, options);
+        split_word_at(
+            word,
+            split_points,
+            options.hyphen,
+            word_splitter.has_unicode_hyphens(),
+        )
+    })
+}
+
+/// Find the words in `line`. Words separated by rejected breaks are
+/// merged, see [`is_allowed()`]. The words are returned
+/// together with their offset in `line`.
+fn merge_words<'a>(
+    line: &'a str,
+    line_offset: usize,
+    options: &'a Options<'_>,
+) -> impl Iterator<Item = (usize, Word<'a>)> {
+    let has_filter = has_break_filter(options);
+    let mut end = 0;
+    let mut words = options
+        .word_separator
+        .iter_words(line)
+        .flat_map(move |word| {
+            let start = end;
+            end += word.len() + word.whitespace.len();
+            split_wide_spaces(&line[start..end], word, options.wide_spaces)
+        })
+        .peekable();
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let start = offset;
+        let mut word = words.next()?;
+        offset += word.len() + word.whitespace.len();
+        if has_filter {
+            while let Some(&next) = words.peek() {
+                let candidate = BreakCandidate {
+                    line,
+                    offset,
+                    kind: separator_kind(&word),
+                    penalty: "",
+                };
+                if is_allowed(&candidate, options) && !is_protected(line_offset + offset, options) {
+                    break;
+                }
+                let end = offset + next.len();
+                word = Word {
+                    word: &line[start..end],
+                    width: display_width(&line[start..end]),
+                    whitespace: next.whitespace,
+                    tab_width: next.tab_width,
+                    penalty: next.penalty,
+                    payload: (),
+                };
+                offset = end + next.whitespace.len();
+                words.next();
+            }
+        }
+        Some((start, word))
+    })
+}
+
+/// Find the split points in `word`, which starts at `start` in
+/// `line`, using `word_splitter`. Split points which leave fragments
+/// shorter than [`Options::min_fragment_lengths`] or which are
+/// rejected by [`is_allowed()`] are removed.
+fn split_points(
+    line: &str,
+    line_offset: usize,
+    start: usize,
+    word: &Word<'_>,
+    word_splitter: &WordSplitter,
+    options: &Options<'_>,
+) -> Vec<usize> {
+    let mut split_points =
+        word_splitter.split_points_with(word, options.hyphenation_filter.unwrap_or(|_| true));
+    retain_long_fragments(word, &mut split_points, options.min_fragment_lengths);
+    if has_break_filter(options) {
+        split_points.retain(|&idx| {
+            let candidate = BreakCandidate {
+                line,
+                offset: start + idx,
+                kind: BreakKind::Hyphenation,
+                penalty: hyphenation_penalty(
+                    word,
+                    idx,
+                    options.hyphen,
+                    word_splitter.has_unicode_hyphens(),
+                ),
+            };
+            is_allowed(&candidate, options)
+        });
+    }
+    if !options.protected_ranges.is_empty() {
+        split_points.retain(|&idx| !is_protected(line_offset + start + idx, options));
+    }
+    split_points
+}
+
+/// Check if `options` can reject break points.
+fn has_break_filter(options: &Options<'_>) -> bool {
+    options.break_filter.is_some()
+        || !options.protected_ranges.is_empty()
+        || !options.no_break_before.is_empty()
+        || !options.no_break_after.is_empty()
+}
+
+/// Check if `candidate` is allowed by [`Options::break_filter`],
+/// [`Options::no_break_before`], and [`Options::no_break_after`].
+fn is_allowed(candidate: &BreakCandidate<'_>, options: &Options<'_>) -> bool {
+    let starts_forbidden = candidate
+        .after()
+        .starts_with(|ch| options.no_break_before.contains(ch));
+    let ends_forbidden = candidate
+        .before()
+        .trim_end()
+        .ends_with(|ch| options.no_break_after.contains(ch));
+    !starts_forbidden
+        && !ends_forbidden
+        && options
+            .break_filter
+            .map_or(true, |filter| filter(candidate))
+}
+
+/// Check if a break at `offset` in the text falls inside one of the
+/// [`Options::protected_ranges`].
+fn is_protected(offset: usize, options: &Options<'_>) -> bool {
+    options
+        .protected_ranges
+        .iter()
+        .any(|range| range.start < offset && offset < range.end)
+}
+
+/// Check if breaking the text in `span` apart can put a break inside
+/// one of the [`Options::protected_ranges`].
+pub(crate) fn is_protected_span(span: Range<usize>, options: &Options<'_>) -> bool {
+    options
+        .protected_ranges
+        .iter()
+        .any(|range| range.start.max(span.start) + 1 < range.end.min(span.end))
+}
+
+/// The kind of break found after `word`.
+fn separator_kind(word: &Word<'_>) -> BreakKind {
+    if word.whitespace.is_empty() {
+        BreakKind::WordBoundary
+    } else {
+        BreakKind::Whitespace
+    }
+}
+
+/// The cost of a break of the given kind, see [`BreakPoint::cost`].
+#[cfg_attr(not(feature = "smawk"), allow(unused_variables))]
+fn break_cost(fragment_break: FragmentBreak, options: &Options<'_>) -> usize {
+    match options.wrap_algorithm {
+        #[cfg(feature = "smawk")]
+        crate::WrapAlgorithm::OptimalFit(penalties) => match fragment_break {
+            FragmentBreak::InsertedHyphen => penalties.hyphen_penalty,
+            FragmentBreak::HardHyphen => penalties.hard_hyphen_penalty,
+            FragmentBreak::Whitespace | FragmentBreak::Boundary => 0,
+        },
+        _ => 0,
+    }
+}
+
+/// The penalty used when splitting `word` at `idx`: the `hyphen`,
+/// unless the word already has a hyphen there. Unicode hyphens and
+/// dashes only count with `unicode_hyphens`, which is set for the
+/// [`WordSplitter::UnicodeHyphenSplitter`].
+pub(crate) fn hyphenation_penalty(
+    word: &str,
+    idx: usize,
+    hyphen: &'static str,
+    unicode_hyphens: bool,
+) -> &'static str {
+    if ends_with_hyphen(&word[..idx], unicode_hyphens) || word[..idx].ends_with(hyphen) {
+        ""
+    } else {
+        hyphen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{WordSeparator, WordSplitter, WrapAlgorithm};
+
+    #[test]
+    fn analyze_empty() {
+        assert_eq!(analyze("", 10), vec![]);
+    }
+
+    #[test]
+    fn analyze_hyphenation_penalty() {
+        let options = Options::new(10)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .word_splitter(WordSplitter::Custom(|word| {
+                word.char_indices().skip(1).map(|(idx, _)| idx).collect()
+            }));
+        assert_eq!(
+            analyze("ab", options),
+            vec![BreakPoint {
+                offset: 1,
+                kind: BreakKind::Hyphenation,
+                penalty: "-",
+                cost: 0
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "smawk")]
+    fn analyze_optimal_fit_costs() {
+        use crate::wrap_algorithms::Penalties;
+
+        let penalties = Penalties::new().hard_hyphen_penalty(7);
+        let options = Options::new(10)
+            .wrap_algorithm(WrapAlgorithm::OptimalFit(penalties))
+            .word_separator(WordSeparator::AsciiSpace)
+            .word_splitter(WordSplitter::Custom(|word| match word {
+                "abcd" => vec![2],
+                "x-y" => vec![2],
+                _ => vec![],
+            }));
+        let costs = |options: &Options<'_>| {
+            analyze("abcd x-y", options)
+                .iter()
+                .map(|break_point| break_point.cost)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(costs(&options), vec![penalties.hyphen_penalty, 0, 7]);
+        let options = options.wrap_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(costs(&options), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn analyze_crlf() {
+        let options = Options::new(10).line_ending(crate::LineEnding::CRLF);
+        assert_eq!(
+            analyze("foo\r\nbar", options),
+            vec![BreakPoint {
+                offset: 5,
+                kind: BreakKind::LineEnding,
+                penalty: "",
+                cost: 0
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-linebreak")]
+    fn analyze_word_boundary() {
+        let options = Options::new(10).word_separator(WordSeparator::UnicodeBreakProperties);
+        assert_eq!(
+            analyze("a/b", options),
+            vec![BreakPoint {
+                offset: 2,
+                kind: BreakKind::WordBoundary,
+                penalty: "",
+                cost: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn break_filter_merges_words() {
+        let options =
+            Options::new(10).break_filter(|candidate| !candidate.before().ends_with("e.g. "));
+        assert_eq!(analyze("e.g. foo", &options), vec![]);
+        assert_eq!(
+            split_filtered_words("x e.g. foo", 0, &options).collect::<Vec<_>>(),
+            vec![Word::from("x "), Word::from("e.g. foo"),]
+        );
+    }
+
+    #[test]
+    fn break_filter_rejects_split_points() {
+        let options = Options::new(10).break_filter(|candidate| {
+            candidate.kind != BreakKind::Hyphenation
+                || !candidate
+                    .after()
+                    .starts_with(|ch: char| ch.is_ascii_digit())
+        });
+        assert_eq!(
+            analyze("ABC-1234 foo-bar", &options),
+            vec![
+                BreakPoint {
+                    offset: 9,
+                    kind: BreakKind::Whitespace,
+                    penalty: "",
+                    cost: 0
+                },
+                BreakPoint {
+                    offset: 13,
+                    kind: BreakKind::Hyphenation,
+                    penalty: "",
+                    cost: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn min_fragment_lengths_ignore_punctuation() {
+        let options = Options::new(10).min_fragment_lengths((2, 3));
+        assert_eq!(analyze("e-mail", &options), vec![]);
+        assert_eq!(analyze("re-do.", &options), vec![]);
+        assert_eq!(
+            analyze("re-use.", &options),
+            vec![BreakPoint {
+                offset: 3,
+                kind: BreakKind::Hyphenation,
+                penalty: "",
+                cost: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn no_break_around_punctuation() {
+        let options = Options::new(10)
+            .word_separator(WordSeparator::AsciiSpace)
+            .no_break_before(",)")
+            .no_break_after("(");
+        assert_eq!(
+            analyze("a , b ( c ) d", &options),
+            vec![
+                BreakPoint {
+                    offset: 4,
+                    kind: BreakKind::Whitespace,
+                    penalty: "",
+                    cost: 0
+                },
+                BreakPoint {
+                    offset: 6,
+                    kind: BreakKind::Whitespace,
+                    penalty: "",
+                    cost: 0
+                },
+                BreakPoint {
+                    offset: 12,
+                    kind: BreakKind::Whitespace,
+                    penalty: "",
+                    cost: 0
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn protected_ranges_in_second_line() {
+        let ranges = [0..3, 8..11];
+        let options = Options::new(10)
+            .word_separator(WordSeparator::AsciiSpace)
+            .protected_ranges(&ranges);
+        let offsets = |text| {
+            analyze(text, &options)
+                .iter()
+                .map(|break_point| break_point.offset)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(offsets("foo-bar\nx-y a b"), vec![4, 8, 12, 14]);
+        assert_eq!(offsets("foo-bar\nxx-yy a b"), vec![4, 8, 11, 14, 16]);
+    }
+
+    #[test]
+    fn analyze_leading_whitespace() {
+        let options = Options::new(10).word_separator(WordSeparator::AsciiSpace);
+        assert_eq!(
+            analyze("  foo", options),
+            vec![BreakPoint {
+                offset: 2,
+                kind: BreakKind::Whitespace,
+                penalty: "",
+                cost: 0
+            }]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Memoization of wrapped text.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::{fill, Options};
+
+/// Wraps text and remembers the results.
+///
+/// Interactive programs often redraw the screen many times a second
+/// with text which rarely changes. A `CachedWrapper` remembers the
+/// filled text for the most recently used inputs, which saves the
+/// work of wrapping the same text over and over again.
+///
+/// The cache holds at most `capacity` entries. When it is full, the
+/// least recently used entry is evicted. The cache is cleared when
+/// the options are changed with [`CachedWrapper::set_options()`].
+///
+/// **Note:** Only available when the `cache` Cargo feature is
+/// enabled.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{CachedWrapper, Options};
+///
+/// let mut wrapper = CachedWrapper::new(Options::new(15), 100);
+/// assert_eq!(wrapper.fill("Memoization of wrapped text"),
+///            "Memoization of\nwrapped text");
+/// assert_eq!(wrapper.wrap("Memoization of wrapped text"),
+///            vec!["Memoization of", "wrapped text"]);
+/// assert_eq!(wrapper.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct CachedWrapper<'a> {
+    options: Options<'a>,
+    capacity: usize,
+    entries: HashMap<Arc<str>, CacheEntry>,
+    /// The cached texts ordered by when they were last used. The
+    /// texts are shared with `entries` so each is stored only once.
+    recently_used: BTreeMap<u64, Arc<str>>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    filled: String,
+    last_used: u64,
+}
+
+impl<'a> CachedWrapper<'a> {
+    /// Create a new wrapper which caches up to `capacity` results.
+    ///
+    /// A `capacity` of zero is treated as one since the result of the
+    /// last call to [`CachedWrapper::fill()`] is always kept.
+    pub fn new<Opt>(width_or_options: Opt, capacity: usize) -> Self
+    where
+        Opt: Into<Options<'a>>,
+    {
+        CachedWrapper {
+            options: width_or_options.into(),
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recently_used: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// The options used for wrapping.
+    pub fn options(&self) -> &Options<'a> {
+        &self.options
+    }
+
+    /// Change the options used for wrapping.
+    ///
+    /// The cache is cleared unless the new options are equal to the
+    /// old options. Options with a `Custom` [`WrapAlgorithm`],
+    /// [`WordSeparator`], or [`WordSplitter`], or with a
+    /// [`break_filter`], a [`hyphenation_filter`], or a
+    /// [`break_words_if`] predicate, always clear the cache, see
+    /// [`Options::cache_key()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{CachedWrapper, Options};
+    ///
+    /// let mut wrapper = CachedWrapper::new(10, 100);
+    /// assert_eq!(wrapper.fill("foo bar baz"), "foo bar\nbaz");
+    ///
+    /// wrapper.set_options(Options::new(10));
+    /// assert_eq!(wrapper.len(), 1);
+    ///
+    /// wrapper.set_options(Options::new(20));
+    /// assert!(wrapper.is_empty());
+    /// assert_eq!(wrapper.fill("foo bar baz"), "foo bar baz");
+    /// ```
+    ///
+    /// [`WrapAlgorithm`]: crate::WrapAlgorithm
+    /// [`WordSeparator`]: crate::WordSeparator
+    /// [`WordSplitter`]: crate::WordSplitter
+    /// [`break_filter`]: Options::break_filter
+    /// [`hyphenation_filter`]: Options::hyphenation_filter
+    /// [`break_words_if`]: Options::break_words_if
+    pub fn set_options<Opt>(&mut self, width_or_options: Opt)
+    where
+        Opt: Into<Options<'a>>,
+    {
+        let options = width_or_options.into();
+        let unchanged = match (self.options.cache_key(), options.cache_key()) {
+            (Some(old), Some(new)) => old == new,
+            _ => false,
+        };
+        if !unchanged {
+            self.clear();
+        }
+        self.options = options;
+    }
+
+    /// Fill `text`, see [`fill()`].
+    ///
+    /// The result is taken from the cache if `text` has been filled
+    /// before.
+    pub fn fill(&mut self, text: &str) -> &str {
+        self.tick += 1;
+        match self.entries.get_mut(text) {
+            Some(entry) => {
+                let text = self.recently_used.remove(&entry.last_used).unwrap();
+                entry.last_used = self.tick;
+                self.recently_used.insert(self.tick, text);
+            }
+            None => {
+                if self.entries.len() >= self.capacity {
+                    self.evict_least_recently_used();
+                }
+                let filled = fill(text, &self.options);
+                let entry = CacheEntry {
+                    filled,
+                    last_used: self.tick,
+                };
+                let text: Arc<str> = Arc::from(text);
+                self.entries.insert(Arc::clone(&text), entry);
+                self.recently_used.insert(self.tick, text);
+            }
+        }
+
+        &self.entries[text].filled
+    }
+
+    /// Wrap `text`, see [`wrap()`](crate::wrap()).
+    ///
+    /// The lines borrow from the cached result of
+    /// [`CachedWrapper::fill()`].
+    pub fn wrap(&mut self, text: &str) -> Vec<&str> {
+        let line_ending = self.options.line_ending.as_str();
+        self.fill(text).split(line_ending).collect()
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all cached results.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recently_used.clear();
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        if let Some((_, text)) = self.recently_used.pop_first() {
+            self.entries.remove(&text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut wrapper = CachedWrapper::new(5, 2);
+        assert_eq!(wrapper.fill("aaa bbb"), "aaa\nbbb");
+        assert_eq!(wrapper.fill("ccc ddd"), "ccc\nddd");
+        assert_eq!(wrapper.fill("aaa bbb"), "aaa\nbbb");
+        assert_eq!(wrapper.fill("eee fff"), "eee\nfff");
+        assert_eq!(wrapper.len(), 2);
+        assert!(wrapper.entries.contains_key("aaa bbb"));
+        assert!(!wrapper.entries.contains_key("ccc ddd"));
+    }
+
+    #[test]
+    fn evicts_in_order_of_use() {
+        let mut wrapper = CachedWrapper::new(5, 3);
+        for text in ["a", "b", "c", "a", "b", "d", "e"] {
+            wrapper.fill(text);
+        }
+        let mut cached = wrapper.entries.keys().map(|t| &**t).collect::<Vec<_>>();
+        cached.sort();
+        assert_eq!(cached, vec!["b", "d", "e"]);
+        assert_eq!(
+            wrapper
+                .recently_used
+                .values()
+                .map(|t| &**t)
+                .collect::<Vec<_>>(),
+            vec!["b", "d", "e"]
+        );
+    }
+
+    #[test]
+    fn stores_each_text_once() {
+        let mut wrapper = CachedWrapper::new(5, 2);
+        wrapper.fill("aaa bbb");
+        wrapper.fill("aaa bbb");
+        let (text, _) = wrapper.entries.get_key_value("aaa bbb").unwrap();
+        let used = wrapper.recently_used.values().next().unwrap();
+        assert!(Arc::ptr_eq(text, used));
+    }
+
+    #[test]
+    fn zero_capacity_keeps_last_result() {
+        let mut wrapper = CachedWrapper::new(5, 0);
+        assert_eq!(wrapper.fill("aaa bbb"), "aaa\nbbb");
+        assert_eq!(wrapper.fill("ccc ddd"), "ccc\nddd");
+        assert_eq!(wrapper.len(), 1);
+    }
+
+    #[test]
+    fn custom_options_clear_cache() {
+        let options = Options::new(5).break_filter(|_| true);
+        let mut wrapper = CachedWrapper::new(options.clone(), 10);
+        wrapper.fill("aaa bbb");
+        wrapper.set_options(options);
+        assert!(wrapper.is_empty());
+    }
+
+    #[test]
+    fn wrap_uses_line_ending() {
+        let options = Options::new(5).line_ending(crate::LineEnding::CRLF);
+        let mut wrapper = CachedWrapper::new(options, 10);
+        assert_eq!(wrapper.fill("aaa bbb"), "aaa\r\nbbb");
+        assert_eq!(wrapper.wrap("aaa bbb"), vec!["aaa", "bbb"]);
+        assert_eq!(wrapper.len(), 1);
+    }
+}
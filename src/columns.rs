@@ -1,7 +1,9 @@
 //! Functionality for wrapping text into columns.
 
+use std::borrow::Cow;
+
 use crate::core::display_width;
-use crate::{wrap, Options};
+use crate::{try_wrap, wrap, Error, Options};
 
 /// Wrap text into columns with a given total width.
 ///
@@ -33,6 +35,10 @@ use crate::{wrap, Options};
 /// argument, but the width is overwritten to the computed
 /// `column_width`.
 ///
+/// The columns are aligned at the top and padded with `' '`. Please
+/// use [`wrap_columns_with()`] if you need control over the alignment
+/// and padding of the columns.
+///
 /// # Panics
 ///
 /// Panics if `columns` is zero.
@@ -71,9 +77,125 @@ pub fn wrap_columns<'a, Opt>(
 where
     Opt: Into<Options<'a>>,
 {
-    assert!(columns > 0);
+    let column = Column {
+        text,
+        header: None,
+        options: total_width_or_options.into(),
+        vertical_alignment: VerticalAlignment::Top,
+        padding: ' ',
+    };
+    wrap_columns_with(&column, columns, left_gap, middle_gap, right_gap)
+}
+
+/// Wrap text into columns with a given total width, returning an
+/// error instead of panicking.
+///
+/// This is like [`wrap_columns()`], but the error listed below is
+/// returned instead of causing a panic.
+///
+/// # Errors
+///
+/// Returns [`Error::ZeroColumns`] if `columns` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{try_wrap_columns, Error};
+///
+/// assert_eq!(try_wrap_columns("Foo", 0, 10, "", "", ""),
+///            Err(Error::ZeroColumns));
+/// ```
+pub fn try_wrap_columns<'a, Opt>(
+    text: &str,
+    columns: usize,
+    total_width_or_options: Opt,
+    left_gap: &str,
+    middle_gap: &str,
+    right_gap: &str,
+) -> Result<Vec<String>, Error>
+where
+    Opt: Into<Options<'a>>,
+{
+    try_wrap_columns_with(
+        &Column {
+            text,
+            header: None,
+            options: total_width_or_options.into(),
+            vertical_alignment: VerticalAlignment::Top,
+            padding: ' ',
+        },
+        columns,
+        left_gap,
+        middle_gap,
+        right_gap,
+    )
+}
+
+/// Wrap the text of a [`Column`] into a number of columns.
+///
+/// This is like [`wrap_columns()`], but the text, the total width and
+/// the wrapping options are taken from `column`. The
+/// [`Column::vertical_alignment`] and [`Column::padding`] settings
+/// apply to every column in the output: the alignment decides where
+/// the lines of a shorter final column go, and the padding character
+/// fills up narrow lines. If the column has a
+/// [`header`](Column::header), it is wrapped to the column width and
+/// shown above the first column.
+///
+/// # Panics
+///
+/// Panics if `columns` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap_columns_with, Column, VerticalAlignment};
+///
+/// let column = Column::new("one two three four five", 14)
+///     .vertical_alignment(VerticalAlignment::Bottom)
+///     .padding('.');
+/// assert_eq!(wrap_columns_with(&column, 2, "", " ", ""),
+///            vec!["one... .......",
+///                 "two... four...",
+///                 "three. five..."]);
+/// ```
+pub fn wrap_columns_with(
+    column: &Column<'_>,
+    columns: usize,
+    left_gap: &str,
+    middle_gap: &str,
+    right_gap: &str,
+) -> Vec<String> {
+    // This can only fail with zero columns since `MinWidth::Error`
+    // is replaced.
+    let column = Column {
+        options: column.options.clone().proceed_on_min_width_error(),
+        ..column.clone()
+    };
+    try_wrap_columns_with(&column, columns, left_gap, middle_gap, right_gap).unwrap()
+}
+
+/// Wrap the text of a [`Column`] into a number of columns, returning
+/// an error instead of panicking.
+///
+/// This is like [`wrap_columns_with()`], but the error listed below
+/// is returned instead of causing a panic.
+///
+/// # Errors
+///
+/// Returns [`Error::ZeroColumns`] if `columns` is zero.
+pub fn try_wrap_columns_with(
+    column: &Column<'_>,
+    columns: usize,
+    left_gap: &str,
+    middle_gap: &str,
+    right_gap: &str,
+) -> Result<Vec<String>, Error> {
+    if columns == 0 {
+        return Err(Error::ZeroColumns);
+    }
 
-    let mut options: Options = total_width_or_options.into();
+    let mut options = column.options.clone();
 
     let inner_width = options
         .width
@@ -83,28 +205,270 @@ where
 
     let column_width = std::cmp::max(inner_width / columns, 1);
     options.width = column_width;
-    let last_column_padding = " ".repeat(inner_width % column_width);
-    let wrapped_lines = wrap(text, options);
+    let header_lines = match column.header {
+        Some(header) => try_wrap(header, &options)?,
+        None => Vec::new(),
+    };
+    let wrapped_lines = try_wrap(column.text, &options)?;
     let lines_per_column =
         wrapped_lines.len() / columns + usize::from(wrapped_lines.len() % columns > 0);
-    let mut lines = Vec::new();
-    for line_no in 0..lines_per_column {
-        let mut line = String::from(left_gap);
-        for column_no in 0..columns {
-            match wrapped_lines.get(line_no + column_no * lines_per_column) {
-                Some(column_line) => {
-                    line.push_str(column_line);
-                    line.push_str(&" ".repeat(column_width - display_width(column_line)));
-                }
-                None => {
-                    line.push_str(&" ".repeat(column_width));
-                }
+    let mut chunks = wrapped_lines.chunks(std::cmp::max(lines_per_column, 1));
+    let column_lines = (0..columns)
+        .map(|_| chunks.next().unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    let layout = ColumnLayout {
+        width: column_width,
+        vertical_alignment: column.vertical_alignment,
+        padding: column.padding,
+    };
+    let mut layouts = vec![layout; columns];
+    // The last column absorbs the remaining width.
+    layouts[columns - 1].width += inner_width % column_width;
+
+    let mut header_columns = vec![&header_lines[..]];
+    header_columns.resize(columns, &[]);
+    let mut lines = join_columns(&header_columns, &layouts, left_gap, middle_gap, right_gap);
+    lines.extend(join_columns(
+        &column_lines,
+        &layouts,
+        left_gap,
+        middle_gap,
+        right_gap,
+    ));
+    Ok(lines)
+}
+
+/// Vertical alignment of a [`Column`] which is shorter than the
+/// other columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlignment {
+    /// Put the lines at the top of the column.
+    #[default]
+    Top,
+    /// Put the lines in the middle of the column. If the lines
+    /// cannot be centered exactly, they are moved up a line.
+    Middle,
+    /// Put the lines at the bottom of the column.
+    Bottom,
+}
+
+/// A column of text for [`wrap_columns_side_by_side()`].
+///
+/// Each column has its own text, an optional header, and its own
+/// [`Options`]. The width of the column is given by
+/// [`Options::width`].
+#[derive(Debug, Clone)]
+pub struct Column<'a> {
+    /// The text shown in the column.
+    pub text: &'a str,
+    /// Optional header shown above the text. See the
+    /// [`Column::header`] method.
+    pub header: Option<&'a str>,
+    /// Options used when wrapping the header and the text.
+    pub options: Options<'a>,
+    /// Vertical alignment of the header and the text. See the
+    /// [`Column::vertical_alignment`] method.
+    pub vertical_alignment: VerticalAlignment,
+    /// Character used to pad the lines to the column width. See the
+    /// [`Column::padding`] method.
+    pub padding: char,
+}
+
+impl<'a> Column<'a> {
+    /// Creates a new [`Column`] with the given text and options.
+    ///
+    /// The column has no header, it is aligned at the top, and it is
+    /// padded with `' '`.
+    pub fn new<Opt>(text: &'a str, width_or_options: Opt) -> Self
+    where
+        Opt: Into<Options<'a>>,
+    {
+        Column {
+            text,
+            header: None,
+            options: width_or_options.into(),
+            vertical_alignment: VerticalAlignment::Top,
+            padding: ' ',
+        }
+    }
+
+    /// Change [`self.header`]. The header is wrapped like the text
+    /// and is shown above it.
+    ///
+    /// [`self.header`]: #structfield.header
+    pub fn header(self, header: &'a str) -> Self {
+        Column {
+            header: Some(header),
+            ..self
+        }
+    }
+
+    /// Change [`self.vertical_alignment`]. This decides where the
+    /// lines go if the column is shorter than the other columns. The
+    /// header and the text are aligned separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap_columns_side_by_side, Column, VerticalAlignment};
+    ///
+    /// let columns = [
+    ///     Column::new("one two three", 5),
+    ///     Column::new("four", 4).vertical_alignment(VerticalAlignment::Middle),
+    ///     Column::new("five", 4).vertical_alignment(VerticalAlignment::Bottom),
+    /// ];
+    /// assert_eq!(wrap_columns_side_by_side(&columns, "|", "|", "|"),
+    ///            vec!["|one  |    |    |",
+    ///                 "|two  |four|    |",
+    ///                 "|three|    |five|"]);
+    /// ```
+    ///
+    /// [`self.vertical_alignment`]: #structfield.vertical_alignment
+    pub fn vertical_alignment(self, vertical_alignment: VerticalAlignment) -> Self {
+        Column {
+            vertical_alignment,
+            ..self
+        }
+    }
+
+    /// Change [`self.padding`]. This character fills up lines which
+    /// are narrower than the column, as well as empty lines added
+    /// to short columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap_columns_side_by_side, Column};
+    ///
+    /// let columns = [
+    ///     Column::new("Introduction", 15).padding('.'),
+    ///     Column::new("1", 3),
+    /// ];
+    /// assert_eq!(wrap_columns_side_by_side(&columns, "", " ", ""),
+    ///            vec!["Introduction... 1  "]);
+    /// ```
+    ///
+    /// [`self.padding`]: #structfield.padding
+    pub fn padding(self, padding: char) -> Self {
+        Column { padding, ..self }
+    }
+}
+
+/// Wrap several texts into columns shown side by side.
+///
+/// Unlike [`wrap_columns()`], which flows a single text through a
+/// number of equally wide columns, this function wraps each
+/// [`Column`] by itself using its own [`Options`]. This gives output
+/// similar to what you see in side-by-side diff viewers.
+///
+/// If any column has a header, the headers are shown on the first
+/// lines of output. Headers and texts are aligned with each other
+/// across the columns, shorter columns are padded with empty lines.
+///
+/// The `left_gap`, `middle_gap` and `right_gap` arguments specify the
+/// strings to insert before, between, and after the columns.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap_columns_side_by_side, Column};
+///
+/// let columns = [
+///     Column::new("The old and tired text.", 10).header("Before"),
+///     Column::new("The new text.", 8).header("After"),
+/// ];
+/// assert_eq!(wrap_columns_side_by_side(&columns, "", " | ", ""),
+///            vec!["Before     | After   ",
+///                 "The old    | The new ",
+///                 "and tired  | text.   ",
+///                 "text.      |         "]);
+/// ```
+pub fn wrap_columns_side_by_side(
+    columns: &[Column<'_>],
+    left_gap: &str,
+    middle_gap: &str,
+    right_gap: &str,
+) -> Vec<String> {
+    let layouts = columns
+        .iter()
+        .map(|column| ColumnLayout {
+            width: column.options.width,
+            vertical_alignment: column.vertical_alignment,
+            padding: column.padding,
+        })
+        .collect::<Vec<_>>();
+    let headers = columns
+        .iter()
+        .map(|column| match column.header {
+            Some(header) => wrap(header, &column.options),
+            None => Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    let texts = columns
+        .iter()
+        .map(|column| wrap(column.text, &column.options))
+        .collect::<Vec<_>>();
+
+    let mut lines = join_columns(&headers, &layouts, left_gap, middle_gap, right_gap);
+    lines.extend(join_columns(
+        &texts, &layouts, left_gap, middle_gap, right_gap,
+    ));
+    lines
+}
+
+/// Layout of a column when joining it with other columns.
+#[derive(Debug, Clone, Copy)]
+struct ColumnLayout {
+    width: usize,
+    vertical_alignment: VerticalAlignment,
+    padding: char,
+}
+
+/// Join lines from several columns into rows of output.
+///
+/// The lines in each column are padded to the column width. Columns
+/// with fewer lines than the longest column are padded with empty
+/// lines according to their vertical alignment.
+fn join_columns<'a, L: AsRef<[Cow<'a, str>]>>(
+    column_lines: &[L],
+    layouts: &[ColumnLayout],
+    left_gap: &str,
+    middle_gap: &str,
+    right_gap: &str,
+) -> Vec<String> {
+    let rows = column_lines
+        .iter()
+        .map(|lines| lines.as_ref().len())
+        .max()
+        .unwrap_or(0);
+    let offsets = column_lines
+        .iter()
+        .zip(layouts)
+        .map(|(lines, layout)| {
+            let gap = rows - lines.as_ref().len();
+            match layout.vertical_alignment {
+                VerticalAlignment::Top => 0,
+                VerticalAlignment::Middle => gap / 2,
+                VerticalAlignment::Bottom => gap,
             }
-            if column_no == columns - 1 {
-                line.push_str(&last_column_padding);
-            } else {
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::with_capacity(rows);
+    for row in 0..rows {
+        let mut line = String::from(left_gap);
+        for (column_no, (lines, layout)) in column_lines.iter().zip(layouts).enumerate() {
+            if column_no > 0 {
                 line.push_str(middle_gap);
             }
+            let column_line = row
+                .checked_sub(offsets[column_no])
+                .and_then(|idx| lines.as_ref().get(idx))
+                .map_or("", |line| line);
+            line.push_str(column_line);
+            let padding = layout.width.saturating_sub(display_width(column_line));
+            line.extend(std::iter::repeat(layout.padding).take(padding));
         }
         line.push_str(right_gap);
         lines.push(line);
@@ -190,4 +554,149 @@ mod tests {
     fn wrap_columns_panic_with_zero_columns() {
         wrap_columns("", 0, 10, "", "", "");
     }
+
+    #[test]
+    fn try_wrap_columns_zero_columns() {
+        assert_eq!(
+            try_wrap_columns("", 0, 10, "", "", ""),
+            Err(Error::ZeroColumns)
+        );
+    }
+
+    #[test]
+    fn wrap_columns_with_defaults_matches_wrap_columns() {
+        let text = "This is an example text, which is wrapped into three columns.";
+        assert_eq!(
+            wrap_columns_with(&Column::new(text, 50), 3, "| ", " | ", " |"),
+            wrap_columns(text, 3, 50, "| ", " | ", " |")
+        );
+    }
+
+    #[test]
+    fn wrap_columns_with_vertical_alignment() {
+        let column = Column::new("aa bb cc dd ee ff gg", 8);
+        assert_eq!(
+            wrap_columns_with(&column, 3, "", "|", ""),
+            vec!["aa|dd|gg", "bb|ee|  ", "cc|ff|  "]
+        );
+        assert_eq!(
+            wrap_columns_with(
+                &column.clone().vertical_alignment(VerticalAlignment::Middle),
+                3,
+                "",
+                "|",
+                ""
+            ),
+            vec!["aa|dd|  ", "bb|ee|gg", "cc|ff|  "]
+        );
+        assert_eq!(
+            wrap_columns_with(
+                &column.vertical_alignment(VerticalAlignment::Bottom),
+                3,
+                "",
+                "|",
+                ""
+            ),
+            vec!["aa|dd|  ", "bb|ee|  ", "cc|ff|gg"]
+        );
+    }
+
+    #[test]
+    fn wrap_columns_with_padding() {
+        let column = Column::new("a bb c", 7).padding('-');
+        assert_eq!(
+            wrap_columns_with(&column, 2, "", " ", ""),
+            vec!["a-- c--", "bb- ---"]
+        );
+    }
+
+    #[test]
+    fn wrap_columns_with_header() {
+        let column = Column::new("foo bar baz", 9).header("Head");
+        assert_eq!(
+            wrap_columns_with(&column, 2, "", " ", ""),
+            vec!["Head     ", "foo  baz ", "bar      "]
+        );
+    }
+
+    #[test]
+    fn try_wrap_columns_with_zero_columns() {
+        assert_eq!(
+            try_wrap_columns_with(&Column::new("foo", 10), 0, "", "", ""),
+            Err(Error::ZeroColumns)
+        );
+    }
+
+    #[test]
+    fn wrap_columns_side_by_side_no_columns() {
+        assert_eq!(
+            wrap_columns_side_by_side(&[], "|", "|", "|"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn wrap_columns_side_by_side_without_headers() {
+        let columns = [Column::new("foo bar", 3), Column::new("baz", 5)];
+        assert_eq!(
+            wrap_columns_side_by_side(&columns, "[", "|", "]"),
+            vec!["[foo|baz  ]", "[bar|     ]"]
+        );
+    }
+
+    #[test]
+    fn wrap_columns_side_by_side_some_headers() {
+        let columns = [
+            Column::new("foo", 4),
+            Column::new("bar", 6).header("long header"),
+        ];
+        assert_eq!(
+            wrap_columns_side_by_side(&columns, "", " ", ""),
+            vec![
+                "     long  ", //
+                "     header",
+                "foo  bar   "
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_columns_side_by_side_vertical_alignment() {
+        let columns = [
+            Column::new("a b c d", 1),
+            Column::new("x y", 1).vertical_alignment(VerticalAlignment::Middle),
+            Column::new("x", 1).vertical_alignment(VerticalAlignment::Middle),
+            Column::new("z", 1)
+                .header("h")
+                .vertical_alignment(VerticalAlignment::Bottom),
+        ];
+        assert_eq!(
+            wrap_columns_side_by_side(&columns, "", "", ""),
+            vec!["   h", "a   ", "bxx ", "cy  ", "d  z"]
+        );
+    }
+
+    #[test]
+    fn wrap_columns_side_by_side_padding() {
+        let columns = [
+            Column::new("foo", 5).padding('-'),
+            Column::new("bar baz", 3).padding('*'),
+        ];
+        assert_eq!(
+            wrap_columns_side_by_side(&columns, "", "", ""),
+            vec!["foo--bar", "-----baz"]
+        );
+    }
+
+    #[test]
+    fn wrap_columns_side_by_side_per_column_options() {
+        let columns = [
+            Column::new("foo bar", Options::new(6).initial_indent("- ")),
+            Column::new("foo bar", 7),
+        ];
+        assert_eq!(
+            wrap_columns_side_by_side(&columns, "", "|", ""),
+            vec!["- foo |foo bar", "bar   |       "]
+        );
+    }
 }
@@ -0,0 +1,202 @@
+//! Formatting of Git commit messages.
+
+use crate::{fill, Options};
+
+/// Format a Git commit message according to the 50/72 convention.
+///
+/// The message is processed as follows:
+///
+/// * The first line is the subject. It is never wrapped, only
+///   trailing whitespace is removed. The subject should be at most
+///   [`Options::COMMIT_SUBJECT_WIDTH`] columns wide, you can check
+///   this with [`core::display_width()`](crate::core::display_width).
+///
+/// * The body is separated from the subject by a single empty line.
+///   Paragraphs in the body are separated by empty lines and are
+///   refilled with [`Options::for_commit_message()`], which wraps
+///   them at 72 columns.
+///
+/// * Paragraphs with indented lines, such as code blocks, and
+///   paragraphs with list items (lines starting with `-`, `*`, `+`,
+///   or a number followed by `.` or `)`) are left intact.
+///
+/// * The last paragraph is left intact if it consists of trailers
+///   such as `Signed-off-by: Name <email>`. A trailer is a line with
+///   a token made of alphanumeric characters and `-`, followed by a
+///   colon. Indented lines continue the previous trailer.
+///
+/// A trailing newline in `text` is preserved.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::format_commit_message;
+///
+/// let message = "\
+/// Fix off-by-one error in the optimal-fit algorithm
+///
+/// The last line of a paragraph was sometimes considered too long, \
+/// which made the algorithm place a line break one word too early.
+///
+/// Signed-off-by: Jane Doe <jane@example.net>
+/// ";
+///
+/// assert_eq!(format_commit_message(message), "\
+/// Fix off-by-one error in the optimal-fit algorithm
+///
+/// The last line of a paragraph was sometimes considered too long, which
+/// made the algorithm place a line break one word too early.
+///
+/// Signed-off-by: Jane Doe <jane@example.net>
+/// ");
+/// ```
+pub fn format_commit_message(text: &str) -> String {
+    let mut lines = text.lines();
+    let subject = lines.next().unwrap_or("").trim_end();
+
+    let mut paragraphs = Vec::new();
+    let mut paragraph = Vec::new();
+    for line in lines {
+        let line = line.trim_end();
+        if line.is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(std::mem::take(&mut paragraph));
+            }
+        } else {
+            paragraph.push(line);
+        }
+    }
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph);
+    }
+
+    let options = Options::for_commit_message();
+    let mut result = String::with_capacity(text.len());
+    result.push_str(subject);
+    let last_idx = paragraphs.len().saturating_sub(1);
+    for (idx, paragraph) in paragraphs.iter().enumerate() {
+        result.push_str("\n\n");
+        let keep = paragraph
+            .iter()
+            .any(|line| is_indented(line) || is_list_item(line))
+            || (idx == last_idx && is_trailer_block(paragraph));
+        if keep {
+            result.push_str(&paragraph.join("\n"));
+        } else {
+            result.push_str(&fill(&paragraph.join(" "), &options));
+        }
+    }
+
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Check if `line` starts with whitespace.
+fn is_indented(line: &str) -> bool {
+    line.starts_with([' ', '\t'])
+}
+
+/// Check if `line` starts with a list marker such as `- ` or `1. `.
+pub(crate) fn is_list_item(line: &str) -> bool {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return true;
+    }
+    let without_digits = line.trim_start_matches(|ch: char| ch.is_ascii_digit());
+    without_digits.len() < line.len()
+        && (without_digits.starts_with(". ") || without_digits.starts_with(") "))
+}
+
+/// Check if `line` is a trailer such as `Signed-off-by: Name`.
+fn is_trailer(line: &str) -> bool {
+    match line.split_once(':') {
+        Some((token, value)) => {
+            !token.is_empty()
+                && token.chars().all(|ch| ch.is_alphanumeric() || ch == '-')
+                && (value.is_empty() || value.starts_with(' '))
+        }
+        None => false,
+    }
+}
+
+/// Check if all lines in `paragraph` are trailers or continuations
+/// of trailers.
+fn is_trailer_block(paragraph: &[&str]) -> bool {
+    paragraph.first().map_or(false, |line| is_trailer(line))
+        && paragraph
+            .iter()
+            .all(|line| is_trailer(line) || is_indented(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subject_only() {
+        assert_eq!(format_commit_message("Fix typo  \n"), "Fix typo\n");
+        assert_eq!(format_commit_message("Fix typo"), "Fix typo");
+        assert_eq!(format_commit_message(""), "");
+    }
+
+    #[test]
+    fn long_subject_is_not_wrapped() {
+        let subject = "Make the subject line much longer than the fifty columns allowed";
+        assert_eq!(format_commit_message(subject), subject);
+    }
+
+    #[test]
+    fn normalizes_empty_lines() {
+        assert_eq!(
+            format_commit_message("Subject\nBody\n\n\n\nMore body\n"),
+            "Subject\n\nBody\n\nMore body\n"
+        );
+    }
+
+    #[test]
+    fn refills_body() {
+        let body = "word ".repeat(20);
+        assert_eq!(
+            format_commit_message(&format!("Subject\n\n{body}\n")),
+            format!(
+                "Subject\n\n{}\n{}\n",
+                "word ".repeat(14).trim_end(),
+                "word ".repeat(6).trim_end()
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_indented_blocks_and_lists() {
+        let message = "Subject\n\
+                       \n\
+                       Example:\n    \
+                           fn main() {}\n\
+                       \n\
+                       - first item\n\
+                       - second item\n\
+                       \n\
+                       1. numbered\n\
+                       2) items\n";
+        assert_eq!(format_commit_message(message), message);
+    }
+
+    #[test]
+    fn keeps_trailers() {
+        let message = "Subject\n\
+                       \n\
+                       Reviewed-by: A <a@example.net>\n\
+                       Acked-by: B <b@example.net>\n  \
+                         with a continuation line\n";
+        assert_eq!(format_commit_message(message), message);
+    }
+
+    #[test]
+    fn trailers_only_in_last_paragraph() {
+        assert_eq!(
+            format_commit_message("Subject\n\nNote: this\nis wrapped\n\nDone.\n"),
+            "Subject\n\nNote: this is wrapped\n\nDone.\n"
+        );
+    }
+}
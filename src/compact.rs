@@ -0,0 +1,43 @@
+//! Merging of short last lines into the previous line.
+
+/// Describes when a short last line of a paragraph is merged into
+/// the previous line. Use this with
+/// [`Options::compact`](crate::Options::compact).
+///
+/// A last line which is narrower than `short_width` columns is merged
+/// into the previous line if the merged line is at most `overflow`
+/// columns wider than the line width. The default never merges
+/// lines.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap, Compact, Options, WrapAlgorithm};
+///
+/// let options = Options::new(15)
+///     .wrap_algorithm(WrapAlgorithm::FirstFit)
+///     .compact(Compact::new(4, 2));
+/// assert_eq!(wrap("Compact mode on.", &options),
+///            vec!["Compact mode on."]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Compact {
+    /// Last lines narrower than this many columns are merged.
+    pub short_width: usize,
+    /// The merged line may be this many columns wider than the line
+    /// width.
+    pub overflow: usize,
+}
+
+impl Compact {
+    /// Create a new `Compact` which merges last lines narrower than
+    /// `short_width` if the merged line overflows the line width by
+    /// at most `overflow` columns.
+    pub const fn new(short_width: usize, overflow: usize) -> Self {
+        Compact {
+            short_width,
+            overflow,
+        }
+    }
+}
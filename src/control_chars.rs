@@ -0,0 +1,127 @@
+//! Handling of control characters in the input text.
+
+use std::borrow::Cow;
+
+/// Describes what happens to control characters such as `'\0'`,
+/// `'\x0c'` (form feed), and `'\x0b'` (vertical tab) when wrapping
+/// text.
+///
+/// Control characters have no width, but printing them can corrupt
+/// the state of a terminal. Use [`ControlChars::Strip`] or
+/// [`ControlChars::Escape`] when wrapping text from untrusted
+/// sources.
+///
+/// The affected characters are the C0 control characters
+/// (`'\0'`–`'\x1f'`), DEL (`'\x7f'`), and the C1 control characters
+/// (`'\u{80}'`–`'\u{9f}'`). The exceptions are `'\t'`, `'\n'`,
+/// `'\r'` followed by `'\n'`, and `'\x1b'` (escape), which are
+/// always kept: line breaks are handled by
+/// [`Options::line_ending`](crate::Options::line_ending) and escape
+/// starts the ANSI escape sequences used for colored text.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap, ControlChars, Options};
+///
+/// let text = "Page one\x0cpage two";
+/// assert_eq!(wrap(text, Options::new(20).sanitize_control_chars(ControlChars::Strip)),
+///            vec!["Page onepage two"]);
+/// assert_eq!(wrap(text, Options::new(20).sanitize_control_chars(ControlChars::Escape)),
+///            vec!["Page one^Lpage two"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlChars {
+    /// Pass control characters through unchanged.
+    #[default]
+    Keep,
+    /// Remove control characters before wrapping.
+    Strip,
+    /// Replace control characters with visible escapes before
+    /// wrapping. This uses the caret notation known from `cat -v`:
+    /// `'\0'` becomes `^@`, `'\x0c'` becomes `^L`, DEL becomes `^?`,
+    /// and C1 control characters become `M-^@` to `M-^_`.
+    Escape,
+}
+
+impl ControlChars {
+    /// Apply this mode to `text`.
+    ///
+    /// The text is borrowed unchanged when it has no control
+    /// characters or when the mode is [`ControlChars::Keep`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::ControlChars;
+    ///
+    /// assert_eq!(ControlChars::Escape.sanitize("bell\x07"), "bell^G");
+    /// assert_eq!(ControlChars::Strip.sanitize("bell\x07"), "bell");
+    /// assert_eq!(ControlChars::Keep.sanitize("bell\x07"), "bell\x07");
+    /// ```
+    pub fn sanitize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if *self == ControlChars::Keep
+            || !text
+                .char_indices()
+                .any(|(idx, ch)| is_control(text, idx, ch))
+        {
+            return Cow::Borrowed(text);
+        }
+
+        let mut result = String::with_capacity(text.len());
+        for (idx, ch) in text.char_indices() {
+            if !is_control(text, idx, ch) {
+                result.push(ch);
+                continue;
+            }
+            if *self == ControlChars::Escape {
+                let code = ch as u32;
+                if code >= 0x80 {
+                    result.push_str("M-");
+                }
+                result.push('^');
+                result.push(match code & 0x7f {
+                    0x7f => '?',
+                    low => char::from(low as u8 + b'@'),
+                });
+            }
+        }
+        Cow::Owned(result)
+    }
+}
+
+/// Check if `ch`, found at `idx` in `text`, is a control character
+/// which should be sanitized.
+fn is_control(text: &str, idx: usize, ch: char) -> bool {
+    match ch {
+        '\t' | '\n' | '\x1b' => false,
+        '\r' => !text[idx + 1..].starts_with('\n'),
+        _ => ch.is_control(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_c0_del_and_c1() {
+        assert_eq!(ControlChars::Escape.sanitize("\0\x1f\x7f"), "^@^_^?");
+        assert_eq!(ControlChars::Escape.sanitize("\r\r\n"), "^M\r\n");
+        assert_eq!(ControlChars::Escape.sanitize("\u{85}\u{9b}"), "M-^EM-^[");
+    }
+
+    #[test]
+    fn keeps_tabs_newlines_and_ansi() {
+        let text = "\x1b[1mbold\x1b[0m\tand\r\nmore";
+        assert!(matches!(
+            ControlChars::Strip.sanitize(text),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            ControlChars::Escape.sanitize(text),
+            Cow::Borrowed(_)
+        ));
+    }
+}
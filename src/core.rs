@@ -26,6 +26,8 @@
 //!    [`wrap_optimal_fit`](crate::wrap_algorithms::wrap_optimal_fit)
 //!    and [`wrap_first_fit`](crate::wrap_algorithms::wrap_first_fit).
 //!    The former produces better line breaks, the latter is faster.
+//!    Use [`line_widths_for`] to compute the line widths the same way
+//!    as [`wrap()`](crate::wrap()).
 //!
 //! 5. Iterate through the slices returned by the wrapping functions
 //!    and construct your lines of output.
@@ -34,6 +36,8 @@
 //! the functionality here is not sufficient or if you have ideas for
 //! improving it. We would love to hear from you!
 
+use crate::Options;
+
 /// The CSI or “Control Sequence Introducer” introduces an ANSI escape
 /// sequence. This is typically used for colored text and will be
 /// ignored when computing the text width.
@@ -84,7 +88,7 @@ pub(crate) fn skip_ansi_escape_sequence<I: Iterator<Item = char>>(ch: char, char
 
 #[cfg(feature = "unicode-width")]
 #[inline]
-fn ch_width(ch: char) -> usize {
+pub(crate) fn ch_width(ch: char) -> usize {
     unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0)
 }
 
@@ -95,7 +99,7 @@ const DOUBLE_WIDTH_CUTOFF: char = '\u{1100}';
 
 #[cfg(not(feature = "unicode-width"))]
 #[inline]
-fn ch_width(ch: char) -> usize {
+pub(crate) fn ch_width(ch: char) -> usize {
     if ch < DOUBLE_WIDTH_CUTOFF {
         1
     } else {
@@ -103,6 +107,38 @@ fn ch_width(ch: char) -> usize {
     }
 }
 
+/// Compute the display width of a single `char`.
+///
+/// This uses the same rules as [`display_width`], which is the sum
+/// of the widths of the characters in the text. With the
+/// `unicode-width` Cargo feature, the width comes from the
+/// [unicode-width] crate and is zero for control characters. Without
+/// the feature, characters below U+1100 are 1 column wide and all
+/// other characters are 2 columns wide.
+///
+/// Use this when you need to align text with the lines produced by
+/// this crate. Note that [`display_width`] skips ANSI escape
+/// sequences, which a single `char` cannot show.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::char_width;
+///
+/// assert_eq!(char_width('a'), 1);
+/// assert_eq!(char_width('你'), 2);
+/// #[cfg(feature = "unicode-width")]
+/// assert_eq!(char_width('\u{301}'), 0); // Combining acute accent
+/// #[cfg(not(feature = "unicode-width"))]
+/// assert_eq!(char_width('\u{301}'), 1);
+/// ```
+///
+/// [unicode-width]: https://docs.rs/unicode-width/
+#[inline]
+pub fn char_width(ch: char) -> usize {
+    ch_width(ch)
+}
+
 /// Compute the display width of `text` while skipping over ANSI
 /// escape sequences.
 ///
@@ -197,6 +233,15 @@ fn ch_width(ch: char) -> usize {
 /// [CJK characters]: https://en.wikipedia.org/wiki/CJK_characters
 /// [emoji modifier sequences]: https://unicode.org/emoji/charts/full-emoji-modifiers.html
 pub fn display_width(text: &str) -> usize {
+    // This is called for every word, so we avoid decoding the chars
+    // when possible. Printable ASCII characters are one column wide.
+    if text.bytes().all(|b| (b' '..=b'~').contains(&b)) {
+        return text.len();
+    }
+    if !text.as_bytes().contains(&(CSI.0 as u8)) {
+        return text.chars().map(ch_width).sum();
+    }
+
     let mut chars = text.chars();
     let mut width = 0;
     while let Some(ch) = chars.next() {
@@ -208,6 +253,107 @@ pub fn display_width(text: &str) -> usize {
     width
 }
 
+/// Compute the display width of `text` when it starts at the first
+/// column of a terminal with tab stops every `tab_width` columns.
+///
+/// This is like [`display_width`], except that a `'\t'` advances to
+/// the next tab stop. A `tab_width` of zero makes tabs zero-width.
+/// This is used for measuring indentation, see
+/// [`Options::tab_width`].
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::display_width_with_tabs;
+///
+/// assert_eq!(display_width_with_tabs("\t", 8), 8);
+/// assert_eq!(display_width_with_tabs("-> \t", 8), 8);
+/// assert_eq!(display_width_with_tabs("\t\t* ", 4), 10);
+/// ```
+pub fn display_width_with_tabs(text: &str, tab_width: usize) -> usize {
+    measure_indent(text, tab_width, &[])
+}
+
+/// Like [`display_width_with_tabs`], but the width of the characters
+/// in `overrides` is taken from there.
+fn measure_indent(text: &str, tab_width: usize, overrides: &[(char, usize)]) -> usize {
+    let mut chars = text.chars();
+    let mut width = 0;
+    while let Some(ch) = chars.next() {
+        if skip_ansi_escape_sequence(ch, &mut chars) {
+            continue;
+        }
+        width = match ch {
+            '\t' if tab_width > 0 => (width / tab_width + 1) * tab_width,
+            '\t' => width,
+            _ => width + ch_width_with(ch, overrides),
+        };
+    }
+    width
+}
+
+/// The width of `ch`, unless it is found in `overrides`. See
+/// [`Options::width_overrides`].
+#[inline]
+pub(crate) fn ch_width_with(ch: char, overrides: &[(char, usize)]) -> usize {
+    match overrides.iter().find(|(c, _)| *c == ch) {
+        Some(&(_, width)) => width,
+        None => ch_width(ch),
+    }
+}
+
+/// Like [`display_width`], but the width of the characters in
+/// `overrides` is taken from there.
+pub(crate) fn display_width_with(text: &str, overrides: &[(char, usize)]) -> usize {
+    if overrides.is_empty() {
+        return display_width(text);
+    }
+    let mut chars = text.chars();
+    let mut width = 0;
+    while let Some(ch) = chars.next() {
+        if skip_ansi_escape_sequence(ch, &mut chars) {
+            continue;
+        }
+        width += ch_width_with(ch, overrides);
+    }
+    width
+}
+
+/// Find the byte index and column of each character in `text`.
+///
+/// The iterator yields `(byte_idx, column)` pairs where `column` is
+/// the display width of `&text[..byte_idx]`. Widths are computed
+/// like in [`display_width`], which means that ANSI escape sequences
+/// are skipped and yield no pairs. This lets you convert between
+/// byte offsets and columns consistently with how the text is
+/// wrapped, e.g., when placing a cursor in a text editor.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::display_width_indices;
+///
+/// assert_eq!(display_width_indices("a你b").collect::<Vec<_>>(),
+///            vec![(0, 0), (1, 1), (4, 3)]);
+/// assert_eq!(display_width_indices("\u{1b}[31mab").collect::<Vec<_>>(),
+///            vec![(5, 0), (6, 1)]);
+/// ```
+pub fn display_width_indices(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut char_indices = text.char_indices();
+    let mut column = 0;
+    std::iter::from_fn(move || {
+        while let Some((idx, ch)) = char_indices.next() {
+            if skip_ansi_escape_sequence(ch, &mut char_indices.by_ref().map(|(_, ch)| ch)) {
+                continue;
+            }
+            let start_column = column;
+            column += ch_width(ch);
+            return Some((idx, start_column));
+        }
+        None
+    })
+}
+
 /// A (text) fragment denotes the unit which we wrap into lines.
 ///
 /// Fragments represent an abstract _word_ plus the _whitespace_
@@ -229,25 +375,116 @@ pub trait Fragment: std::fmt::Debug {
     /// Displayed width of the penalty that must be inserted if the
     /// word falls at the end of a line.
     fn penalty_width(&self) -> f64;
+
+    /// How the line is broken if the fragment falls at the end of a
+    /// line.
+    ///
+    /// The optimal-fit algorithm uses this to choose between
+    /// [`Penalties::hyphen_penalty`] and
+    /// [`Penalties::hard_hyphen_penalty`]. The default implementation
+    /// returns [`FragmentBreak::InsertedHyphen`] if the fragment has
+    /// a penalty and [`FragmentBreak::Whitespace`] otherwise.
+    ///
+    /// [`Penalties::hyphen_penalty`]: crate::wrap_algorithms::Penalties::hyphen_penalty
+    /// [`Penalties::hard_hyphen_penalty`]: crate::wrap_algorithms::Penalties::hard_hyphen_penalty
+    fn break_kind(&self) -> FragmentBreak {
+        if self.penalty_width() > 0.0 {
+            FragmentBreak::InsertedHyphen
+        } else {
+            FragmentBreak::Whitespace
+        }
+    }
+
+    /// Does the fragment end a sentence?
+    ///
+    /// The optimal-fit algorithm uses this for
+    /// [`Penalties::sentence_start_penalty`]. The default
+    /// implementation returns `false`.
+    ///
+    /// [`Penalties::sentence_start_penalty`]: crate::wrap_algorithms::Penalties::sentence_start_penalty
+    fn ends_sentence(&self) -> bool {
+        false
+    }
+}
+
+/// Compute the width of a line consisting of `fragments`.
+///
+/// This is the width used by the wrapping algorithms: the widths of
+/// the fragments, the whitespace between them, and the penalty of the
+/// last fragment. The whitespace after the last fragment is not
+/// included since it is dropped at the end of a line.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::{total_width, Word};
+///
+/// let words = [Word::from("Hello, "), Word::from("World! ")];
+/// assert_eq!(total_width(&words), 13.0);
+///
+/// let mut hyphenated = Word::from("hyphen");
+/// hyphenated.penalty = "-";
+/// assert_eq!(total_width(&[Word::from("a "), hyphenated]), 9.0);
+/// assert_eq!(total_width::<Word>(&[]), 0.0);
+/// ```
+pub fn total_width<F: Fragment>(fragments: &[F]) -> f64 {
+    match fragments.split_last() {
+        Some((last, rest)) => {
+            rest.iter()
+                .map(|fragment| fragment.width() + fragment.whitespace_width())
+                .sum::<f64>()
+                + last.width()
+                + last.penalty_width()
+        }
+        None => 0.0,
+    }
 }
 
+/// The kind of line break after a [`Fragment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FragmentBreak {
+    /// The line is broken at whitespace, which is dropped.
+    Whitespace,
+    /// The line is broken between two fragments without whitespace,
+    /// e.g., between two East-Asian characters.
+    Boundary,
+    /// The line is broken after a hyphen which is part of the text,
+    /// such as the one in `"well-known"`.
+    HardHyphen,
+    /// The line is broken inside a word and the penalty, typically
+    /// `"-"`, is inserted.
+    InsertedHyphen,
+}
+
+/// The default [`Options::tab_width`].
+pub(crate) const DEFAULT_TAB_WIDTH: usize = 8;
+
 /// A piece of wrappable text, including any trailing whitespace.
 ///
 /// A `Word` is an example of a [`Fragment`], so it has a width,
 /// trailing whitespace, and potentially a penalty item.
+///
+/// A `Word` can carry a `payload` of type `T`, e.g., a style or a
+/// token kind. The payload is cloned to all the pieces when the word
+/// is split or broken, which means that it comes back out attached
+/// to the words on the wrapped lines. See [`Word::with_payload`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct Word<'a> {
+pub struct Word<'a, T = ()> {
     /// Word content.
     pub word: &'a str,
     /// Whitespace to insert if the word does not fall at the end of a line.
     pub whitespace: &'a str,
     /// Penalty string to insert if the word falls at the end of a line.
     pub penalty: &'a str,
+    /// User data attached to the word.
+    pub payload: T,
     // Cached width in columns.
     pub(crate) width: usize,
+    // Width of a tab in the whitespace, see `Options::tab_width`.
+    pub(crate) tab_width: usize,
 }
 
-impl std::ops::Deref for Word<'_> {
+impl<T> std::ops::Deref for Word<'_, T> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -262,14 +499,127 @@ impl<'a> Word<'a> {
     /// whitespace part of the word.
     pub fn from(word: &str) -> Word<'_> {
         let trimmed = word.trim_end_matches(' ');
+        Word::new(trimmed, &word[trimmed.len()..], "")
+    }
+
+    /// Construct a `Word` from its parts.
+    ///
+    /// The `whitespace` is inserted if the word does not fall at the
+    /// end of a line, the `penalty` is inserted if it does. The
+    /// whitespace should consist of `' '` only and the penalty should
+    /// be `""` or `"-"`, see the [`Fragment`] implementation.
+    ///
+    /// This is the way to construct a `Word` with a penalty outside of
+    /// this crate, e.g., when splitting words in a custom way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::core::{Fragment, Word};
+    ///
+    /// let word = Word::new("foo", "", "-");
+    /// assert_eq!(word.width(), 3.0);
+    /// assert_eq!(word.whitespace(), "");
+    /// assert_eq!(word.penalty(), "-");
+    /// assert_eq!(Word::new("foo", " ", ""), Word::from("foo "));
+    /// ```
+    pub fn new(word: &'a str, whitespace: &'a str, penalty: &'a str) -> Word<'a> {
+        Word {
+            word,
+            width: display_width(word),
+            whitespace,
+            tab_width: DEFAULT_TAB_WIDTH,
+            penalty,
+            payload: (),
+        }
+    }
+}
+
+impl<'a, T> Word<'a, T> {
+    /// Width of the whitespace in columns. A `'\t'` counts as
+    /// `self.tab_width` columns.
+    pub(crate) fn whitespace_columns(&self) -> usize {
+        if self.whitespace.bytes().all(|b| b == b' ') {
+            return self.whitespace.len();
+        }
+        self.whitespace
+            .chars()
+            .map(|ch| match ch {
+                '\t' => self.tab_width,
+                _ => ch_width(ch),
+            })
+            .sum()
+    }
+
+    /// Attach a payload to this word. Any existing payload is
+    /// replaced.
+    ///
+    /// The payload is kept when the word is split with
+    /// [`split_words`](crate::word_splitters::split_words) or broken
+    /// with [`break_words`], so you can find it again on the wrapped
+    /// lines without matching the words against their original
+    /// positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::core::{break_words, Word};
+    /// use textwrap::word_splitters::split_words_with_payload;
+    /// use textwrap::wrap_algorithms::wrap_first_fit;
+    /// use textwrap::WordSplitter;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// enum Style { Plain, Bold }
+    ///
+    /// let words = vec![
+    ///     Word::from("Very ").with_payload(Style::Plain),
+    ///     Word::from("bold-faced ").with_payload(Style::Bold),
+    ///     Word::from("text").with_payload(Style::Plain),
+    /// ];
+    /// let words = split_words_with_payload(words, &WordSplitter::HyphenSplitter);
+    /// let words = break_words(words, 10);
+    /// let lines = wrap_first_fit(&words, &[10.0]);
+    ///
+    /// let styles = lines
+    ///     .iter()
+    ///     .map(|line| line.iter().map(|w| (w.word, w.payload)).collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(styles, vec![
+    ///     vec![("Very", Style::Plain), ("bold-", Style::Bold)],
+    ///     vec![("faced", Style::Bold), ("text", Style::Plain)],
+    /// ]);
+    /// ```
+    pub fn with_payload<U>(self, payload: U) -> Word<'a, U> {
         Word {
-            word: trimmed,
-            width: display_width(trimmed),
-            whitespace: &word[trimmed.len()..],
-            penalty: "",
+            word: self.word,
+            whitespace: self.whitespace,
+            tab_width: self.tab_width,
+            penalty: self.penalty,
+            payload,
+            width: self.width,
         }
     }
 
+    /// Whitespace to insert if the word does not fall at the end of a
+    /// line. This is the same as the [`whitespace`] field.
+    ///
+    /// [`whitespace`]: Word::whitespace
+    #[inline]
+    pub fn whitespace(&self) -> &'a str {
+        self.whitespace
+    }
+
+    /// Penalty string to insert if the word falls at the end of a
+    /// line. This is the same as the [`penalty`] field.
+    ///
+    /// [`penalty`]: Word::penalty
+    #[inline]
+    pub fn penalty(&self) -> &'a str {
+        self.penalty
+    }
+}
+
+impl<'a, T: Clone> Word<'a, T> {
     /// Break this word into smaller words with a width of at most
     /// `line_width`. The whitespace and penalty from this `Word` is
     /// added to the last piece.
@@ -283,7 +633,18 @@ impl<'a> Word<'a> {
     ///     vec![Word::from("Hel"), Word::from("lo!  ")]
     /// );
     /// ```
-    pub fn break_apart<'b>(&'b self, line_width: usize) -> impl Iterator<Item = Word<'a>> + 'b {
+    pub fn break_apart<'b>(&'b self, line_width: usize) -> impl Iterator<Item = Word<'a, T>> + 'b {
+        self.break_apart_with(line_width, &[])
+    }
+
+    /// Like [`Word::break_apart`], but the width of the characters in
+    /// `overrides` is taken from there.
+    pub(crate) fn break_apart_with<'b>(
+        &'b self,
+        line_width: usize,
+        overrides: &'b [(char, usize)],
+    ) -> impl Iterator<Item = Word<'a, T>> + 'b {
+        let ch_width = move |ch| ch_width_with(ch, overrides);
         let mut char_indices = self.word.char_indices();
         let mut offset = 0;
         let mut width = 0;
@@ -299,9 +660,85 @@ impl<'a> Word<'a> {
                         word: &self.word[offset..idx],
                         width: width,
                         whitespace: "",
+                        tab_width: self.tab_width,
                         penalty: "",
+                        payload: self.payload.clone(),
+                    };
+                    offset = idx;
+                    width = ch_width(ch);
+                    return Some(word);
+                }
+
+                width += ch_width(ch);
+            }
+
+            if offset < self.word.len() {
+                let word = Word {
+                    word: &self.word[offset..],
+                    width: width,
+                    whitespace: self.whitespace,
+                    tab_width: self.tab_width,
+                    penalty: self.penalty,
+                    payload: self.payload.clone(),
+                };
+                offset = self.word.len();
+                return Some(word);
+            }
+
+            None
+        })
+    }
+
+    /// Break this word into smaller words which fit on lines of
+    /// `line_width` columns, with `penalty` added to all pieces but
+    /// the last.
+    ///
+    /// This is like [`Word::break_apart`], but the `penalty`, e.g.,
+    /// `"-"`, is shown at the end of each line where the word was
+    /// broken. The pieces are made narrow enough to leave room for
+    /// the penalty. The whitespace and penalty from this `Word` is
+    /// added to the last piece.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::core::Word;
+    ///
+    /// let pieces = Word::from("abcdefgh ").break_apart_with_penalty(4, "~").collect::<Vec<_>>();
+    /// assert_eq!(pieces, vec![Word::new("abc", "", "~"),
+    ///                         Word::new("def", "", "~"),
+    ///                         Word::new("gh", " ", "")]);
+    /// ```
+    pub fn break_apart_with_penalty<'b>(
+        &'b self,
+        line_width: usize,
+        penalty: &'a str,
+    ) -> impl Iterator<Item = Word<'a, T>> + 'b {
+        let penalty_width = display_width(penalty);
+        let mut char_indices = self.word.char_indices();
+        let mut offset = 0;
+        let mut width = 0;
+        // Width of the pieces returned so far.
+        let mut consumed = 0;
+
+        std::iter::from_fn(move || {
+            while let Some((idx, ch)) = char_indices.next() {
+                if skip_ansi_escape_sequence(ch, &mut char_indices.by_ref().map(|(_, ch)| ch)) {
+                    continue;
+                }
+
+                let rest_fits = self.width - consumed <= line_width;
+                if !rest_fits && width > 0 && width + ch_width(ch) + penalty_width > line_width {
+                    let word = Word {
+                        word: &self.word[offset..idx],
+                        width: width,
+                        whitespace: "",
+                        tab_width: self.tab_width,
+                        penalty: penalty,
+                        payload: self.payload.clone(),
                     };
                     offset = idx;
+                    consumed += width;
                     width = ch_width(ch);
                     return Some(word);
                 }
@@ -314,7 +751,9 @@ impl<'a> Word<'a> {
                     word: &self.word[offset..],
                     width: width,
                     whitespace: self.whitespace,
+                    tab_width: self.tab_width,
                     penalty: self.penalty,
+                    payload: self.payload.clone(),
                 };
                 offset = self.word.len();
                 return Some(word);
@@ -325,25 +764,201 @@ impl<'a> Word<'a> {
     }
 }
 
-impl Fragment for Word<'_> {
+impl<T: std::fmt::Debug> Fragment for Word<'_, T> {
     #[inline]
     fn width(&self) -> f64 {
         self.width as f64
     }
 
-    // We assume the whitespace consist of ' ' only. This allows us to
-    // compute the display width in constant time.
+    // The whitespace is usually `""` or `" "`, which we can measure
+    // in constant time. Tabs are found by
+    // `WordSeparator::AsciiWhitespace`, see `prepare_words`.
     #[inline]
     fn whitespace_width(&self) -> f64 {
-        self.whitespace.len() as f64
+        self.whitespace_columns() as f64
     }
 
-    // We assume the penalty is `""` or `"-"`. This allows us to
-    // compute the display width in constant time.
+    // The penalty is usually `""` or `"-"`, which we can measure in
+    // constant time. Other hyphens are measured with display_width.
     #[inline]
     fn penalty_width(&self) -> f64 {
-        self.penalty.len() as f64
+        match self.penalty.len() {
+            0 | 1 => self.penalty.len() as f64,
+            _ => display_width(self.penalty) as f64,
+        }
+    }
+
+    /// The break kind is determined from the word:
+    ///
+    /// ```
+    /// use textwrap::core::{Fragment, FragmentBreak, Word};
+    ///
+    /// assert_eq!(Word::from("foo ").break_kind(), FragmentBreak::Whitespace);
+    /// assert_eq!(Word::from("foo-").break_kind(), FragmentBreak::HardHyphen);
+    /// assert_eq!(Word::from("foo").break_kind(), FragmentBreak::Boundary);
+    ///
+    /// let mut word = Word::from("foo");
+    /// word.penalty = "-";
+    /// assert_eq!(word.break_kind(), FragmentBreak::InsertedHyphen);
+    /// ```
+    #[inline]
+    fn break_kind(&self) -> FragmentBreak {
+        if !self.penalty.is_empty() {
+            FragmentBreak::InsertedHyphen
+        } else if !self.whitespace.is_empty() {
+            FragmentBreak::Whitespace
+        } else if self.word.ends_with('-') {
+            FragmentBreak::HardHyphen
+        } else {
+            FragmentBreak::Boundary
+        }
+    }
+
+    /// A word ends a sentence if it ends with `'.'`, `'!'`, or `'?'`,
+    /// possibly followed by closing quotes or brackets, and if it is
+    /// followed by whitespace:
+    ///
+    /// ```
+    /// use textwrap::core::{Fragment, Word};
+    ///
+    /// assert!(Word::from("end. ").ends_sentence());
+    /// assert!(Word::from("(right?) ").ends_sentence());
+    /// assert!(!Word::from("end.").ends_sentence());
+    /// assert!(!Word::from("3.14 ").ends_sentence());
+    /// ```
+    #[inline]
+    fn ends_sentence(&self) -> bool {
+        !self.whitespace.is_empty() && is_sentence_end(self.word)
+    }
+}
+
+/// Check if `word` ends with sentence-ending punctuation, possibly
+/// followed by closing quotes or brackets.
+pub(crate) fn is_sentence_end(word: &str) -> bool {
+    let closing: &[char] = &['"', '\'', ')', ']', '}', '\u{bb}', '\u{2019}', '\u{201d}'];
+    word.trim_end_matches(closing).ends_with(['.', '!', '?'])
+}
+
+/// Check if `word` ends with a hyphen, after which no extra `'-'` is
+/// needed when the word is split. With `unicode_hyphens`, U+2010
+/// HYPHEN and en and em dashes count too, see
+/// [`WordSplitter::UnicodeHyphenSplitter`].
+///
+/// [`WordSplitter::UnicodeHyphenSplitter`]: crate::WordSplitter::UnicodeHyphenSplitter
+pub(crate) fn ends_with_hyphen(word: &str, unicode_hyphens: bool) -> bool {
+    if unicode_hyphens {
+        word.ends_with(['-', '\u{2010}', '\u{2013}', '\u{2014}'])
+    } else {
+        word.ends_with('-')
+    }
+}
+
+/// Compute the widths available for the first line and the
+/// subsequent lines after indentation.
+///
+/// This subtracts the width of [`Options::initial_indent`] and
+/// [`Options::subsequent_indent`] from [`Options::width`], with tabs
+/// measured using [`Options::tab_width`]. This is done exactly
+/// like [`wrap()`](crate::wrap()) does. Pass the result to one of
+/// the wrapping algorithms in [`wrap_algorithms`] if you want your
+/// own wrapping pipeline to match the high-level functions.
+///
+/// If [`Options::indents`] is set, the widths for the first and the
+/// last entry are returned.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::line_widths_for;
+/// use textwrap::Options;
+///
+/// let options = Options::new(20).initial_indent("* ").subsequent_indent("");
+/// assert_eq!(line_widths_for(&options), [18, 20]);
+///
+/// // The widths saturate at zero:
+/// assert_eq!(line_widths_for(&Options::new(1).initial_indent("-> ")), [0, 1]);
+/// ```
+///
+/// [`wrap_algorithms`]: crate::wrap_algorithms
+pub fn line_widths_for(options: &Options<'_>) -> [usize; 2] {
+    let [initial_indent_width, subsequent_indent_width] = indent_widths(options);
+    [
+        options.width.saturating_sub(initial_indent_width),
+        options.width.saturating_sub(subsequent_indent_width),
+    ]
+}
+
+/// Compute the widths of the indentation of the first line and of
+/// the remaining lines, taking [`Options::tab_width`] and
+/// [`Options::width_overrides`] into account. The
+/// [`Options::indent_width`] is used instead if it is set.
+pub(crate) fn indent_widths(options: &Options<'_>) -> [usize; 2] {
+    [
+        indent_width_for(options, 0),
+        indent_width_for(options, usize::MAX),
+    ]
+}
+
+/// Compute the width of the widest indentation. Unlike
+/// [`indent_widths`], this considers all the [`Options::indents`].
+pub(crate) fn max_indent_width(options: &Options<'_>) -> usize {
+    let lines = std::cmp::max(options.indents.len(), 2);
+    (0..lines)
+        .map(|line| indent_width_for(options, line))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Compute the width of the indentation of output line `line`, see
+/// [`indent_widths`].
+pub(crate) fn indent_width_for(options: &Options<'_>, line: usize) -> usize {
+    match options.indent_width {
+        Some(width) => width,
+        None => measure_indent(
+            options.indent_for(line),
+            options.tab_width,
+            options.width_overrides,
+        ),
+    }
+}
+
+/// Compute the line widths for a paragraph which starts on output
+/// line `first_line`. The last width is used for the remaining lines
+/// of the paragraph, see [`Options::indents`].
+pub(crate) fn line_widths_from(options: &Options<'_>, first_line: usize) -> Vec<usize> {
+    let last_line = std::cmp::max(options.indents.len().saturating_sub(1), first_line + 1);
+    (first_line..=last_line)
+        .map(|line| {
+            options
+                .width
+                .saturating_sub(indent_width_for(options, line))
+        })
+        .collect()
+}
+
+/// Break `word` into pieces which fit on lines of `line_width`
+/// columns, with a `"-"` penalty at each forced break.
+///
+/// Unlike [`break_words`], this inserts a visible hyphen where the
+/// word is broken, which shows that the word continues on the next
+/// line. This is useful for long identifiers or URLs which must be
+/// broken somewhere. Use [`Word::break_apart_with_penalty`] for
+/// another continuation marker.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::{break_word_with_hyphen, Word};
+///
+/// let pieces = break_word_with_hyphen(Word::from("VeryLongIdentifier"), 8);
+/// assert_eq!(pieces.iter().map(|w| format!("{}{}", w.word, w.penalty)).collect::<Vec<_>>(),
+///            vec!["VeryLon-", "gIdenti-", "fier"]);
+/// ```
+pub fn break_word_with_hyphen<T: Clone>(word: Word<'_, T>, line_width: usize) -> Vec<Word<'_, T>> {
+    if word.width <= line_width {
+        return vec![word];
     }
+    word.break_apart_with_penalty(line_width, "-").collect()
 }
 
 /// Forcibly break words wider than `line_width` into smaller words.
@@ -351,19 +966,102 @@ impl Fragment for Word<'_> {
 /// This simply calls [`Word::break_apart`] on words that are too
 /// wide. This means that no extra `'-'` is inserted, the word is
 /// simply broken into smaller pieces.
-pub fn break_words<'a, I>(words: I, line_width: usize) -> Vec<Word<'a>>
+pub fn break_words<'a, T, I>(words: I, line_width: usize) -> Vec<Word<'a, T>>
 where
-    I: IntoIterator<Item = Word<'a>>,
+    T: Clone,
+    I: IntoIterator<Item = Word<'a, T>>,
 {
-    let mut shortened_words = Vec::new();
-    for word in words {
-        if word.width > line_width {
-            shortened_words.extend(word.break_apart(line_width));
-        } else {
-            shortened_words.push(word);
+    break_words_with(words, line_width, &[])
+}
+
+/// Lazily break words wider than `line_width` into smaller words.
+///
+/// This is like [`break_words`], but the words are broken as the
+/// returned iterator is consumed. Only the pieces of a single word
+/// are kept in memory at a time, which means that this works with
+/// very long or even infinite streams of words.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::{break_words_iter, Word};
+///
+/// let words = std::iter::repeat(Word::from("Hello "));
+/// let broken = break_words_iter(words, 3).take(3).collect::<Vec<_>>();
+/// assert_eq!(broken, vec![Word::from("Hel"), Word::from("lo "), Word::from("Hel")]);
+/// ```
+pub fn break_words_iter<'a, T, I>(words: I, line_width: usize) -> impl Iterator<Item = Word<'a, T>>
+where
+    T: Clone,
+    I: IntoIterator<Item = Word<'a, T>>,
+{
+    break_words_iter_with(words, line_width, &[])
+}
+
+/// Like [`break_words`], but the width of the characters in
+/// `overrides` is taken from there.
+pub(crate) fn break_words_with<'a, T, I>(
+    words: I,
+    line_width: usize,
+    overrides: &[(char, usize)],
+) -> Vec<Word<'a, T>>
+where
+    T: Clone,
+    I: IntoIterator<Item = Word<'a, T>>,
+{
+    break_words_iter_with(words, line_width, overrides).collect()
+}
+
+/// Like [`break_words_iter`], but the width of the characters in
+/// `overrides` is taken from there.
+fn break_words_iter_with<'a, 'o, T, I>(
+    words: I,
+    line_width: usize,
+    overrides: &'o [(char, usize)],
+) -> BrokenWords<'a, 'o, T, I::IntoIter>
+where
+    T: Clone,
+    I: IntoIterator<Item = Word<'a, T>>,
+{
+    BrokenWords {
+        words: words.into_iter(),
+        line_width,
+        overrides,
+        pieces: Vec::new().into_iter(),
+    }
+}
+
+/// Iterator returned by [`break_words_iter_with`].
+struct BrokenWords<'a, 'o, T, I> {
+    words: I,
+    line_width: usize,
+    overrides: &'o [(char, usize)],
+    // Remaining pieces of the last word which was too wide.
+    pieces: std::vec::IntoIter<Word<'a, T>>,
+}
+
+impl<'a, T, I> Iterator for BrokenWords<'a, '_, T, I>
+where
+    T: Clone,
+    I: Iterator<Item = Word<'a, T>>,
+{
+    type Item = Word<'a, T>;
+
+    fn next(&mut self) -> Option<Word<'a, T>> {
+        if let Some(piece) = self.pieces.next() {
+            return Some(piece);
         }
+
+        let word = self.words.next()?;
+        if word.width <= self.line_width {
+            return Some(word);
+        }
+        self.pieces = word
+            .break_apart_with(self.line_width, self.overrides)
+            .collect::<Vec<_>>()
+            .into_iter();
+        self.pieces.next()
     }
-    shortened_words
 }
 
 #[cfg(test)]
@@ -373,6 +1071,76 @@ mod tests {
     #[cfg(feature = "unicode-width")]
     use unicode_width::UnicodeWidthChar;
 
+    #[test]
+    fn break_apart_keeps_payload() {
+        let word = Word::from("foobar ").with_payload(42);
+        let pieces = word.break_apart_with_penalty(3, "-").collect::<Vec<_>>();
+        assert_eq!(pieces.len(), 3);
+        assert!(pieces.iter().all(|piece| piece.payload == 42));
+        assert_eq!(break_word_with_hyphen(word, 10), vec![word]);
+    }
+
+    #[test]
+    fn break_words_iter_matches_break_words() {
+        let words = [
+            Word::from("foo "),
+            Word::from("foobarbaz "),
+            Word::from(""),
+            Word::from("bar"),
+        ];
+        for width in 0..12 {
+            assert_eq!(
+                break_words_iter(words, width).collect::<Vec<_>>(),
+                break_words(words, width)
+            );
+        }
+    }
+
+    #[test]
+    fn char_width_matches_display_width() {
+        for ch in [
+            'a', 'é', '\u{301}', '\u{1100}', '你', '😊', '\u{200d}', '\0',
+        ] {
+            assert_eq!(char_width(ch), display_width(ch.encode_utf8(&mut [0; 4])));
+        }
+    }
+
+    #[test]
+    fn word_new_matches_fields() {
+        let mut word = Word::from("foo");
+        word.penalty = "-";
+        assert_eq!(Word::new("foo", "", "-"), word);
+        assert_eq!(word.whitespace(), word.whitespace);
+        assert_eq!(word.penalty(), word.penalty);
+
+        let word = Word::new("Hello", "  ", "");
+        assert_eq!(word, Word::from("Hello  "));
+        assert_eq!(word.whitespace(), "  ");
+        assert_eq!(word.penalty(), "");
+    }
+
+    #[test]
+    fn break_apart_with_penalty_narrow_lines() {
+        let word = Word::from("abc");
+        assert_eq!(
+            word.break_apart_with_penalty(1, "-").collect::<Vec<_>>(),
+            vec![
+                Word::new("a", "", "-"),
+                Word::new("b", "", "-"),
+                Word::new("c", "", "")
+            ]
+        );
+        let word = Word::new("abcd", "  ", "-");
+        assert_eq!(
+            word.break_apart_with_penalty(3, "-").collect::<Vec<_>>(),
+            vec![Word::new("ab", "", "-"), Word::new("cd", "  ", "-")]
+        );
+        assert_eq!(
+            break_word_with_hyphen(Word::from("abc"), 3),
+            vec![Word::from("abc")]
+        );
+    }
+
     #[test]
     fn skip_ansi_escape_sequence_works() {
         let blue_text = "\u{1b}[34mHello\u{1b}[0m";
@@ -434,6 +1202,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn whitespace_width_follows_whitespace() {
+        let mut word = Word::from("foo");
+        assert_eq!(word.whitespace_width(), 0.0);
+        word.whitespace = "  ";
+        assert_eq!(word.whitespace_width(), 2.0);
+        word.whitespace = "\u{3000}";
+        assert_eq!(word.whitespace_width(), 2.0);
+        word.whitespace = " \t";
+        assert_eq!(word.whitespace_width(), 9.0);
+        word.tab_width = 4;
+        assert_eq!(word.whitespace_width(), 5.0);
+    }
+
+    #[test]
+    fn display_width_control_chars() {
+        // The fast path for ASCII must agree with `ch_width`.
+        let text = "a\tb\x7f";
+        assert_eq!(
+            display_width(text),
+            text.chars().map(ch_width).sum::<usize>()
+        );
+        assert_eq!(display_width("\x1bx"), 0);
+    }
+
     #[test]
     fn display_width_narrow_emojis() {
         #[cfg(feature = "unicode-width")]
@@ -458,4 +1251,25 @@ mod tests {
     fn display_width_emojis() {
         assert_eq!(display_width("😂😭🥺🤣✨😍🙏🥰😊🔥"), 20);
     }
+
+    #[test]
+    fn display_width_indices_works() {
+        assert_eq!(display_width_indices("").collect::<Vec<_>>(), vec![]);
+        assert_eq!(
+            display_width_indices("Café!").collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 2), (3, 3), (5, 4)]
+        );
+        assert_eq!(
+            display_width_indices("😂\u{1b}[0m😭").collect::<Vec<_>>(),
+            vec![(0, 0), (8, 2)]
+        );
+    }
+
+    #[test]
+    fn display_width_indices_agrees_with_display_width() {
+        let text = "\u{1b}[31mHello, 你好\u{1b}[0m world 😂";
+        for (idx, column) in display_width_indices(text) {
+            assert_eq!(display_width(&text[..idx]), column);
+        }
+    }
 }
@@ -0,0 +1,226 @@
+//! Functions for wrapping definition lists and help text.
+
+use crate::core::display_width;
+use crate::{wrap, Options};
+
+/// Wrap a list of terms and their descriptions into two columns.
+///
+/// Each term is printed in a column which is `term_width` columns
+/// wide, followed by the wrapped description. Continuation lines of
+/// the description are indented so that they line up under the
+/// description column. A term which does not fit in the term column
+/// with at least one column to spare is put on a line of its own and
+/// the description starts on the following line.
+///
+/// The descriptions are wrapped using [`wrap()`] and the given
+/// `total_width_or_options`, but the indentation is overwritten.
+/// This argument can simply be an integer if you want to use default
+/// settings when wrapping.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::wrap_definition_list;
+///
+/// let items = [
+///     ("-h, --help", "Print help information and exit"),
+///     ("--color <WHEN>", "Use colors in the output"),
+///     ("--really-long-option", "Terms which are too long get a line of their own"),
+/// ];
+/// assert_eq!(wrap_definition_list(&items, 16, 40),
+///            vec!["-h, --help      Print help information",
+///                 "                and exit",
+///                 "--color <WHEN>  Use colors in the output",
+///                 "--really-long-option",
+///                 "                Terms which are too long",
+///                 "                get a line of their own"]);
+/// ```
+pub fn wrap_definition_list<'a, Opt>(
+    items: &[(&str, &str)],
+    term_width: usize,
+    total_width_or_options: Opt,
+) -> Vec<String>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options = total_width_or_options.into();
+    let padding = " ".repeat(term_width);
+
+    let mut lines = Vec::new();
+    for (term, description) in items {
+        wrap_term(term, description, &padding, &options, &mut lines);
+    }
+    lines
+}
+
+/// Wrap the help text for a single command line option.
+///
+/// This formats one entry of the `--help` output of a command line
+/// program: the `option` is printed as-is and the `help` text is
+/// wrapped so that it starts in column `opt_col`. Continuation lines
+/// are aligned at `opt_col` as well. Like in
+/// [`wrap_definition_list()`], an option which does not fit before
+/// `opt_col` is put on a line of its own.
+///
+/// Include any leading indentation in `option`. Line breaks in `help`
+/// are kept and the following lines are aligned at `opt_col` too.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::wrap_help;
+///
+/// assert_eq!(wrap_help("  -v, --verbose", "Increase verbosity, can be repeated", 18, 44),
+///            vec!["  -v, --verbose   Increase verbosity, can be",
+///                 "                  repeated"]);
+/// ```
+pub fn wrap_help<'a, Opt>(
+    option: &str,
+    help: &str,
+    opt_col: usize,
+    width_or_options: Opt,
+) -> Vec<String>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options = width_or_options.into();
+    let padding = " ".repeat(opt_col);
+    let mut lines = Vec::new();
+    wrap_term(option, help, &padding, &options, &mut lines);
+    lines
+}
+
+/// Wrap a `key: value` pair with the value aligned after the colon.
+///
+/// The key is printed as-is, followed by a colon and a space. The
+/// value is wrapped with continuation lines indented so that they
+/// line up with the start of the value. This is the layout used for
+/// HTTP headers, YAML-like summaries, and log records.
+///
+/// Line breaks in `value` are kept and the following lines are
+/// aligned after the colon too. The indentation in
+/// `width_or_options` is replaced.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::wrap_kv;
+///
+/// assert_eq!(wrap_kv("Accept", "text/html, application/xhtml+xml, application/xml", 30),
+///            vec!["Accept: text/html,",
+///                 "        application/xhtml+xml,",
+///                 "        application/xml"]);
+/// ```
+pub fn wrap_kv<'a, Opt>(key: &str, value: &str, width_or_options: Opt) -> Vec<String>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options = width_or_options.into();
+    let term = format!("{}:", key);
+    let padding = " ".repeat(display_width(&term) + 1);
+    let mut lines = Vec::new();
+    wrap_term(&term, value, &padding, &options, &mut lines);
+    lines
+}
+
+/// Wrap a single term and description, aligning the description
+/// after `padding`.
+fn wrap_term(
+    term: &str,
+    description: &str,
+    padding: &str,
+    options: &Options<'_>,
+    lines: &mut Vec<String>,
+) {
+    if description.is_empty() {
+        lines.push(term.trim_end().to_string());
+        return;
+    }
+    let term_len = display_width(term);
+    let initial_indent = if term_len < padding.len() {
+        format!("{}{}", term, &padding[term_len..])
+    } else {
+        lines.push(term.to_string());
+        padding.to_string()
+    };
+    let options = options
+        .clone()
+        .initial_indent(&initial_indent)
+        .subsequent_indent(padding);
+    lines.extend(
+        wrap(description, &options)
+            .into_iter()
+            .map(|line| line.trim_end().to_string()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_description() {
+        assert_eq!(
+            wrap_definition_list(&[("--quiet", ""), ("--verbose", "")], 12, 40),
+            vec!["--quiet", "--verbose"]
+        );
+    }
+
+    #[test]
+    fn long_term_with_empty_description() {
+        assert_eq!(
+            wrap_definition_list(&[("--a-long-term", ""), ("-q", "quiet")], 6, 40),
+            vec!["--a-long-term", "-q    quiet"]
+        );
+    }
+
+    #[test]
+    fn term_exactly_as_wide_as_column() {
+        assert_eq!(
+            wrap_definition_list(&[("--term", "description")], 6, 40),
+            vec!["--term", "      description"]
+        );
+    }
+
+    #[test]
+    fn wide_characters_in_term() {
+        assert_eq!(
+            wrap_definition_list(&[("😊 smile", "happy")], 10, 40),
+            vec!["😊 smile  happy"]
+        );
+    }
+
+    #[test]
+    fn help_with_long_option() {
+        assert_eq!(
+            wrap_help("  --a-very-long-option", "Some help", 10, 40),
+            vec!["  --a-very-long-option", "          Some help"]
+        );
+    }
+
+    #[test]
+    fn help_with_line_breaks() {
+        assert_eq!(
+            wrap_help("  -q", "Be quiet\nRepeat to be quieter", 6, 40),
+            vec!["  -q  Be quiet", "      Repeat to be quieter"]
+        );
+    }
+
+    #[test]
+    fn empty_list() {
+        assert_eq!(wrap_definition_list(&[], 10, 40), Vec::<String>::new());
+    }
+
+    #[test]
+    fn wrap_kv_multiple_lines() {
+        assert_eq!(
+            wrap_kv("Note", "first line\nsecond line", 20),
+            vec!["Note: first line", "      second line"]
+        );
+    }
+
+    #[test]
+    fn wrap_kv_empty_value() {
+        assert_eq!(wrap_kv("Empty", "", 20), vec!["Empty:"]);
+    }
+}
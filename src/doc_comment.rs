@@ -0,0 +1,292 @@
+//! Refilling of Rust documentation comments.
+
+use crate::commit_message::is_list_item;
+use crate::core::display_width;
+use crate::{refill, Options, WordSplitter};
+
+/// Refill the prose in a block of Rust documentation comments.
+///
+/// The comments can be written as `///` (outer doc comments), `//!`
+/// (inner doc comments), or as `#[doc = "..."]` and `#![doc = "..."]`
+/// attributes. Each paragraph of consecutive comment lines is
+/// refilled so that the lines, including the indentation and the
+/// comment marker, are at most `width` columns wide. The Markdown in
+/// the comments is handled as follows:
+///
+/// * Fenced code blocks (starting with ` ``` ` or `~~~`) are kept
+///   unchanged. This includes doctests and their hidden `# ` lines.
+///
+/// * Headings, tables, indented code blocks, and link reference
+///   definitions such as `[foo]: https://example.net/` are kept
+///   unchanged.
+///
+/// * List items start a new paragraph and are refilled with a
+///   hanging indent, see [`refill()`].
+///
+/// Words are never broken or hyphenated, since they may be URLs or
+/// identifiers. Other lines, such as code between the comments, are
+/// kept unchanged. Escapes in `#[doc = "..."]` attributes are
+/// measured as they are written. The lines of the result are
+/// separated by `'\n'`, and a trailing newline in `comment_block` is
+/// preserved.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::refill_doc_comment;
+///
+/// let comment = "    /// Frobnicate the widget. This
+///     /// makes it ready for
+///     /// use.
+///     ///
+///     /// ```
+///     /// # let widget = 42;
+///     /// frobnicate(widget);
+///     /// ```
+/// ";
+/// assert_eq!(refill_doc_comment(comment, 60), "    /// Frobnicate the widget. This makes it ready for use.
+///     ///
+///     /// ```
+///     /// # let widget = 42;
+///     /// frobnicate(widget);
+///     /// ```
+/// ");
+/// ```
+pub fn refill_doc_comment(comment_block: &str, width: usize) -> String {
+    let mut result = String::with_capacity(comment_block.len());
+    let mut paragraph = Paragraph::default();
+    let mut fence = None;
+    let mut previous_lead = None;
+
+    for line in comment_block.lines() {
+        let doc_line = match DocLine::parse(line) {
+            Some(doc_line) => doc_line,
+            None => {
+                paragraph.push_to(&mut result, width);
+                fence = None;
+                previous_lead = None;
+                result.push_str(line);
+                result.push('\n');
+                continue;
+            }
+        };
+
+        if previous_lead != Some(doc_line.lead) {
+            paragraph.push_to(&mut result, width);
+            fence = None;
+            previous_lead = Some(doc_line.lead);
+        }
+
+        let content = doc_line.content;
+        let trimmed = content.trim_start();
+        let fence_marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        let verbatim = match (fence, fence_marker) {
+            (Some(open), Some(marker)) if open == marker => {
+                fence = None;
+                true
+            }
+            (Some(_), _) => true,
+            (None, Some(marker)) => {
+                fence = Some(marker);
+                true
+            }
+            (None, None) => {
+                trimmed.is_empty()
+                    || is_verbatim(content)
+                    || (paragraph.lines.is_empty() && is_indented_code(content))
+            }
+        };
+
+        if verbatim || is_list_item(trimmed) {
+            paragraph.push_to(&mut result, width);
+        }
+        if verbatim {
+            result.push_str(line);
+            result.push('\n');
+        } else {
+            paragraph.lines.push(doc_line);
+        }
+    }
+    paragraph.push_to(&mut result, width);
+
+    if !comment_block.ends_with('\n') {
+        result.pop();
+    }
+    result
+}
+
+/// A line of a documentation comment.
+#[derive(Debug, Clone, Copy)]
+struct DocLine<'a> {
+    /// Indentation and comment marker, e.g., `"    ///"` or
+    /// `"#[doc = \""`.
+    lead: &'a str,
+    /// The text of the comment without the leading space.
+    content: &'a str,
+    /// The end of the comment: `"\"]"` for attributes, otherwise
+    /// empty.
+    suffix: &'a str,
+}
+
+impl<'a> DocLine<'a> {
+    /// Parse `line`. Returns `None` if `line` is not a documentation
+    /// comment.
+    fn parse(line: &'a str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let (lead_len, rest, suffix) = if (trimmed.starts_with("///")
+            && !trimmed.starts_with("////"))
+            || trimmed.starts_with("//!")
+        {
+            (indent + 3, &trimmed[3..], "")
+        } else if trimmed.starts_with("#[doc") || trimmed.starts_with("#![doc") {
+            let (before, after) = trimmed.split_once('"')?;
+            let (name, equals) = before.split_once('=')?;
+            let name = name.trim_start_matches(['#', '!', '[']).trim();
+            if name != "doc" || !equals.trim().is_empty() {
+                return None;
+            }
+            let body = after.trim_end().strip_suffix("\"]")?;
+            (indent + before.len() + 1, body, "\"]")
+        } else {
+            return None;
+        };
+        Some(DocLine {
+            lead: &line[..lead_len],
+            content: rest.strip_prefix(' ').unwrap_or(rest),
+            suffix,
+        })
+    }
+}
+
+/// Check if the `content` of a comment line must be kept unchanged,
+/// regardless of the surrounding lines.
+fn is_verbatim(content: &str) -> bool {
+    let trimmed = content.trim_start();
+    trimmed.starts_with('#')
+        || trimmed.starts_with('|')
+        || (trimmed.starts_with('[') && trimmed.contains("]: "))
+}
+
+/// Check if the `content` of a comment line is part of an indented
+/// code block.
+fn is_indented_code(content: &str) -> bool {
+    content.starts_with("    ") || content.starts_with('\t')
+}
+
+/// Consecutive prose lines of a documentation comment.
+#[derive(Debug, Default)]
+struct Paragraph<'a> {
+    lines: Vec<DocLine<'a>>,
+}
+
+impl Paragraph<'_> {
+    /// Refill the paragraph and push the lines to `result`. The
+    /// paragraph is cleared afterwards.
+    fn push_to(&mut self, result: &mut String, width: usize) {
+        let (lead, suffix) = match self.lines.first() {
+            Some(line) => (line.lead, line.suffix),
+            None => return,
+        };
+        let text = self
+            .lines
+            .iter()
+            .map(|line| line.content)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let text_width = width.saturating_sub(display_width(lead) + 1 + display_width(suffix));
+        let options = Options::new(std::cmp::max(text_width, 1))
+            .break_words(false)
+            .word_splitter(WordSplitter::NoHyphenation);
+        for line in refill(&text, options).lines() {
+            result.push_str(lead);
+            result.push(' ');
+            result.push_str(line);
+            result.push_str(suffix);
+            result.push('\n');
+        }
+        self.lines.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refill_doc_comment_empty() {
+        assert_eq!(refill_doc_comment("", 80), "");
+        assert_eq!(refill_doc_comment("///", 80), "///");
+        assert_eq!(refill_doc_comment("///\n", 80), "///\n");
+    }
+
+    #[test]
+    fn refill_doc_comment_inner() {
+        assert_eq!(
+            refill_doc_comment("//! foo bar\n//! baz\n", 11),
+            "//! foo bar\n//! baz\n"
+        );
+        assert_eq!(
+            refill_doc_comment("//! foo\n//! bar baz\n", 80),
+            "//! foo bar baz\n"
+        );
+    }
+
+    #[test]
+    fn refill_doc_comment_attributes() {
+        let comment = "#[doc = \" foo\"]\n#[doc = \" bar baz\"]\n#![doc = \"\"]\n";
+        assert_eq!(
+            refill_doc_comment(comment, 20),
+            "#[doc = \" foo bar\"]\n#[doc = \" baz\"]\n#![doc = \"\"]\n"
+        );
+    }
+
+    #[test]
+    fn refill_doc_comment_keeps_markdown() {
+        let comment = "\
+/// # Heading
+/// | a | b |
+/// [link]: https://example.net/a/very/long/url
+///     indented code
+/// - foo
+///   bar
+/// - baz
+";
+        assert_eq!(
+            refill_doc_comment(comment, 20),
+            "\
+/// # Heading
+/// | a | b |
+/// [link]: https://example.net/a/very/long/url
+///     indented code
+/// - foo bar
+/// - baz
+"
+        );
+    }
+
+    #[test]
+    fn refill_doc_comment_code_between_comments() {
+        let comment = "/// foo\n/// bar\nfn f() {}\n/// baz\n";
+        assert_eq!(
+            refill_doc_comment(comment, 80),
+            "/// foo bar\nfn f() {}\n/// baz\n"
+        );
+        // Regular comments are not doc comments.
+        assert_eq!(
+            refill_doc_comment("//// foo\n//// bar", 80),
+            "//// foo\n//// bar"
+        );
+    }
+
+    #[test]
+    fn refill_doc_comment_tilde_fence() {
+        let comment = "/// ~~~\n/// ```\n/// a\n/// b\n/// ~~~\n/// c\n/// d\n";
+        assert_eq!(
+            refill_doc_comment(comment, 80),
+            "/// ~~~\n/// ```\n/// a\n/// b\n/// ~~~\n/// c d\n"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Errors returned by the fallible functions in this crate.
+
+/// Errors returned by functions such as [`try_wrap()`](crate::try_wrap())
+/// and [`try_wrap_columns()`](crate::try_wrap_columns()).
+///
+/// The non-fallible functions such as [`wrap()`](crate::wrap()) will
+/// panic in the situations described here. The `try_*` functions
+/// return an `Error` instead. This covers the errors listed here,
+/// not every conceivable panic, such as running out of memory.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The number of columns was zero.
+    ZeroColumns,
+    /// The cost computation of the optimal-fit algorithm overflowed.
+    /// See [`wrap_optimal_fit()`] for details.
+    ///
+    /// This cannot happen when wrapping text with integer line
+    /// widths, but a custom [`Fragment`](crate::core::Fragment) can
+    /// trigger it.
+    ///
+    /// [`wrap_optimal_fit()`]: crate::wrap_algorithms::wrap_optimal_fit
+    Overflow,
+    /// The width leaves too little room for the text after the
+    /// indentation. See [`MinWidth::Error`](crate::MinWidth::Error).
+    WidthTooSmall,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ZeroColumns => write!(f, "number of columns must be positive"),
+            Error::Overflow => write!(f, "wrap_optimal_fit cost computation overflowed"),
+            Error::WidthTooSmall => write!(f, "width leaves too little room for the text"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Error returned when parsing a [`WrapAlgorithm`],
+/// [`WordSeparator`], or [`WordSplitter`] from a string, or when
+/// loading [`FontMetrics`].
+///
+/// [`WrapAlgorithm`]: crate::WrapAlgorithm
+/// [`WordSeparator`]: crate::WordSeparator
+/// [`WordSplitter`]: crate::WordSplitter
+/// [`FontMetrics`]: crate::measure::FontMetrics
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The name is not recognized.
+    Unknown(String),
+    /// The name is recognized, but it requires a Cargo feature which
+    /// is not enabled.
+    Unsupported {
+        /// The name which was parsed.
+        name: &'static str,
+        /// The Cargo feature which must be enabled.
+        feature: &'static str,
+    },
+    /// A line of the input could not be parsed.
+    Malformed {
+        /// The line number, starting from 1.
+        line: usize,
+    },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Unknown(name) => write!(f, "unknown name {:?}", name),
+            ParseError::Unsupported { name, feature } => {
+                write!(f, "{:?} requires the {:?} Cargo feature", name, feature)
+            }
+            ParseError::Malformed { line } => write!(f, "malformed input on line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(feature = "smawk")]
+impl From<crate::wrap_algorithms::OverflowError> for Error {
+    fn from(_: crate::wrap_algorithms::OverflowError) -> Self {
+        Error::Overflow
+    }
+}
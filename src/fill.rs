@@ -1,11 +1,16 @@
 //! Functions for filling text.
 
-use crate::{wrap, wrap_algorithms, Options, WordSeparator};
+use std::borrow::Cow;
+
+use crate::word_separators::AsciiSpaceIter;
+use crate::wrap::{expands_tabs, fast_path_width, wrap_lines, LineParts, LineSink};
+use crate::{wrap_algorithms, ControlChars, Error, Normalization, Options};
 
 /// Fill a line of text at a given width.
 ///
 /// The result is a [`String`], complete with newlines between each
-/// line. Use [`wrap()`] if you need access to the individual lines.
+/// line. Use [`wrap()`](crate::wrap()) if you need access to the
+/// individual lines.
 ///
 /// The easiest way to use this function is to pass an integer for
 /// `width_or_options`:
@@ -33,36 +38,158 @@ use crate::{wrap, wrap_algorithms, Options, WordSeparator};
 ///     "- Memory safety\n  without\n  garbage\n  collection."
 /// );
 /// ```
+///
+/// Bytes which might not be valid UTF-8, e.g., from a log file, can
+/// be filled after replacing invalid sequences with
+/// [`String::from_utf8_lossy()`]. Valid input is not copied:
+///
+/// ```
+/// use textwrap::fill;
+///
+/// let bytes = b"Hello, \xF0\x90\x80World!";
+/// assert_eq!(fill(&String::from_utf8_lossy(bytes), 8),
+///            "Hello,\n\u{FFFD}World!");
+/// ```
 pub fn fill<'a, Opt>(text: &str, width_or_options: Opt) -> String
 where
     Opt: Into<Options<'a>>,
 {
-    let options = width_or_options.into();
+    // Filling text cannot overflow since the line widths are
+    // integers, and it cannot fail with `MinWidth::Error` since that
+    // is replaced, see `try_fill`.
+    let options = width_or_options.into().proceed_on_min_width_error();
+    try_fill(text, options).unwrap()
+}
 
-    if text.len() < options.width && !text.contains('\n') && options.initial_indent.is_empty() {
-        String::from(text.trim_end_matches(' '))
+/// Fill a line of text at a given width, returning an error instead
+/// of panicking.
+///
+/// This is like [`fill()`], but the errors listed below are returned
+/// instead of causing a panic. See [`try_wrap()`](crate::try_wrap())
+/// for details.
+/// Use [`try_fill_height()`](crate::try_fill_height()) if you only
+/// need the number of lines.
+///
+/// # Errors
+///
+/// Returns [`Error::Overflow`] if the optimal-fit algorithm
+/// overflows, and [`Error::WidthTooSmall`] if
+/// [`Options::min_width`] is [`MinWidth::Error`](crate::MinWidth::Error)
+/// and there is too little room for the text.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::try_fill;
+///
+/// assert_eq!(try_fill("Memory safety without garbage collection.", 15),
+///            Ok(String::from("Memory safety\nwithout garbage\ncollection.")));
+/// ```
+pub fn try_fill<'a, Opt>(text: &str, width_or_options: Opt) -> Result<String, Error>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options: Options = width_or_options.into();
+    let options = options.build()?;
+
+    if text.len() < fast_path_width(true, &options)
+        && !text.contains('\n')
+        && !expands_tabs(text, &options)
+        && options.indent_for(0).is_empty()
+        && options.drop_whitespace
+        && options.sanitize_control_chars == ControlChars::Keep
+        && options.normalization == Normalization::Keep
+        && options.width_overrides.is_empty()
+    {
+        Ok(String::from(text.trim_end_matches(' ')))
     } else {
         fill_slow_path(text, options)
     }
 }
 
+/// Fill a line of text at a given width and append it to an existing
+/// string.
+///
+/// This is like [`fill()`], but the result is appended to `buffer`.
+/// The lines are written directly into `buffer` without being
+/// collected first. This lets you reuse the allocation of `buffer`
+/// when you fill text repeatedly, e.g., in a render loop. Nothing is
+/// added between the existing content of `buffer` and the new text.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::fill_into;
+///
+/// let mut buffer = String::from("Note: ");
+/// fill_into("Memory safety without garbage collection.", 15, &mut buffer);
+/// assert_eq!(buffer, "Note: Memory safety\nwithout garbage\ncollection.");
+///
+/// buffer.clear();
+/// fill_into("Reused buffer", 15, &mut buffer);
+/// assert_eq!(buffer, "Reused buffer");
+/// ```
+pub fn fill_into<'a, Opt>(text: &str, width_or_options: Opt, buffer: &mut String)
+where
+    Opt: Into<Options<'a>>,
+{
+    // Filling text cannot overflow since the line widths are
+    // integers, and it cannot fail with `MinWidth::Error` since that
+    // is replaced, see `try_fill`.
+    let options = width_or_options.into().proceed_on_min_width_error();
+    try_fill_into(text, &options, buffer).unwrap()
+}
+
+fn try_fill_into(text: &str, options: &Options<'_>, buffer: &mut String) -> Result<(), Error> {
+    let mut sink = FilledLines {
+        buffer,
+        line_ending: options.line_ending.as_str(),
+        line_count: 0,
+    };
+    wrap_lines(text, options, &mut sink)
+}
+
+/// Appends lines to a string, separated by a line ending.
+struct FilledLines<'b> {
+    buffer: &'b mut String,
+    line_ending: &'static str,
+    line_count: usize,
+}
+
+impl<'a> LineSink<'a> for FilledLines<'_> {
+    fn line_count(&self) -> usize {
+        self.line_count
+    }
+
+    fn push_line(&mut self, line: Cow<'a, str>) {
+        if self.line_count > 0 {
+            self.buffer.push_str(self.line_ending);
+        }
+        self.buffer.push_str(&line);
+        self.line_count += 1;
+    }
+
+    fn push_parts(&mut self, parts: LineParts<'a, '_>) {
+        if self.line_count > 0 {
+            self.buffer.push_str(self.line_ending);
+        }
+        self.buffer.push_str(parts.indent);
+        self.buffer.push_str(&parts.text);
+        self.buffer.push_str(parts.penalty);
+        self.buffer.push_str(parts.padding);
+        self.line_count += 1;
+    }
+}
+
 /// Slow path for fill.
 ///
 /// This is taken when `text` is longer than `options.width`.
-pub(crate) fn fill_slow_path(text: &str, options: Options<'_>) -> String {
+pub(crate) fn fill_slow_path(text: &str, options: Options<'_>) -> Result<String, Error> {
     // This will avoid reallocation in simple cases (no
     // indentation, no hyphenation).
     let mut result = String::with_capacity(text.len());
-
-    let line_ending_str = options.line_ending.as_str();
-    for (i, line) in wrap(text, options).iter().enumerate() {
-        if i > 0 {
-            result.push_str(line_ending_str);
-        }
-        result.push_str(line);
-    }
-
-    result
+    try_fill_into(text, &options, &mut result)?;
+    Ok(result)
 }
 
 /// Fill `text` in-place without reallocating the input string.
@@ -111,6 +238,14 @@ pub(crate) fn fill_slow_path(text: &str, options: Options<'_>) -> String {
 /// indented. You can avoid this if you make sure that your input text
 /// has no double spaces.
 ///
+/// Control characters are left in place since
+/// [`Options::sanitize_control_chars`] would change the length of the
+/// text. Use [`ControlChars::sanitize`] first if the text can contain
+/// control characters.
+///
+/// [`Options::sanitize_control_chars`]: crate::Options::sanitize_control_chars
+/// [`ControlChars::sanitize`]: crate::ControlChars::sanitize
+///
 /// # Performance
 ///
 /// In benchmarks, `fill_inplace` is about twice as fast as
@@ -122,9 +257,7 @@ pub fn fill_inplace(text: &mut String, width: usize) {
 
     let mut offset = 0;
     for line in text.split('\n') {
-        let words = WordSeparator::AsciiSpace
-            .find_words(line)
-            .collect::<Vec<_>>();
+        let words = AsciiSpaceIter::new(line).collect::<Vec<_>>();
         let wrapped_words = wrap_algorithms::wrap_first_fit(&words, &[width as f64]);
 
         let mut line_offset = offset;
@@ -219,7 +352,7 @@ mod tests {
     #[test]
     fn break_words_empty_lines() {
         assert_eq!(
-            fill("foo\nbar", &Options::new(2).break_words(false)),
+            fill("foo\nbar", Options::new(2).break_words(false)),
             "foo\nbar"
         );
     }
@@ -280,6 +413,18 @@ mod tests {
         assert_eq!(text, "  foo bar\nbaz");
     }
 
+    #[test]
+    fn fill_into_matches_fill() {
+        let options = Options::new(10)
+            .initial_indent("* ")
+            .subsequent_indent("  ")
+            .line_ending(crate::LineEnding::CRLF);
+        let text = "Some text\n\nwith paragraphs which need wrapping";
+        let mut buffer = String::from(">");
+        fill_into(text, &options, &mut buffer);
+        assert_eq!(buffer, format!(">{}", fill(text, &options)));
+    }
+
     #[test]
     fn fill_inplace_trailing_whitespace() {
         let mut text = String::from("foo bar baz  ");
@@ -295,4 +440,15 @@ mod tests {
         fill_inplace(&mut text, 10);
         assert_eq!(text, "foo  bar   \nbaz");
     }
+
+    proptest::proptest! {
+        #[test]
+        fn fill_joins_wrapped_lines(
+            text in crate::proptest_support::text(),
+            options in crate::proptest_support::any_algorithm_options(),
+        ) {
+            let lines = crate::wrap(&text, &options);
+            proptest::prop_assert_eq!(fill(&text, &options), lines.join("\n"));
+        }
+    }
 }
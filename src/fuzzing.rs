@@ -5,12 +5,12 @@ use std::borrow::Cow;
 
 /// Exposed for fuzzing so we can check the slow path is correct.
 pub fn fill_slow_path<'a>(text: &str, options: Options<'_>) -> String {
-    crate::fill::fill_slow_path(text, options)
+    crate::fill::fill_slow_path(text, options).unwrap()
 }
 
 /// Exposed for fuzzing so we can check the slow path is correct.
 pub fn wrap_single_line<'a>(line: &'a str, options: &Options<'_>, lines: &mut Vec<Cow<'a, str>>) {
-    crate::wrap::wrap_single_line(line, options, lines);
+    crate::wrap::wrap_single_line(line, 0, true, options, lines).unwrap();
 }
 
 /// Exposed for fuzzing so we can check the slow path is correct.
@@ -19,5 +19,5 @@ pub fn wrap_single_line_slow_path<'a>(
     options: &Options<'_>,
     lines: &mut Vec<Cow<'a, str>>,
 ) {
-    crate::wrap::wrap_single_line_slow_path(line, options, lines)
+    crate::wrap::wrap_single_line_slow_path(line, 0, true, options, lines).unwrap()
 }
@@ -4,6 +4,11 @@
 //! The functions here can be used to uniformly indent or dedent
 //! (unindent) word wrapped lines of text.
 
+use std::borrow::Cow;
+
+use crate::core::display_width_with_tabs;
+use crate::{fill, Options};
+
 /// Indent each line by the given prefix.
 ///
 /// # Examples
@@ -49,29 +54,180 @@
 ///
 /// assert_eq!(indent(" \t  Foo   ", "->"), "-> \t  Foo   ");
 /// ```
-pub fn indent(s: &str, prefix: &str) -> String {
+///
+/// The text is borrowed when the prefix is empty:
+///
+/// ```
+/// use std::borrow::Cow;
+/// use textwrap::indent;
+///
+/// assert!(matches!(indent("foo\nbar\n", ""), Cow::Borrowed(_)));
+/// ```
+///
+/// Use [`indent_into()`] to append the indented text to an existing
+/// `String`.
+pub fn indent<'a>(s: &'a str, prefix: &str) -> Cow<'a, str> {
+    if prefix.is_empty() {
+        return Cow::Borrowed(s);
+    }
+
     // We know we'll need more than s.len() bytes for the output, but
     // without counting '\n' characters (which is somewhat slow), we
     // don't know exactly how much. However, we can preemptively do
     // the first doubling of the output size.
     let mut result = String::with_capacity(2 * s.len());
-    let trimmed_prefix = prefix.trim_end();
+    indent_into(s, prefix, &mut result);
+    Cow::Owned(result)
+}
+
+/// Indent each line by the given prefix and append the result to
+/// `buffer`.
+///
+/// This works like [`indent()`], but lets you reuse an allocation
+/// when indenting many pieces of text.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::indent_into;
+///
+/// let mut buffer = String::from("Items:\n");
+/// indent_into("foo\nbar\n", "- ", &mut buffer);
+/// indent_into("baz\n", "- ", &mut buffer);
+/// assert_eq!(buffer, "Items:\n- foo\n- bar\n- baz\n");
+/// ```
+pub fn indent_into(s: &str, prefix: &str, buffer: &mut String) {
+    indent_lines_into(s, prefix, prefix, buffer);
+}
+
+/// Indent text with a different prefix for the first line.
+///
+/// The first non-empty line is indented with `first_prefix` and all
+/// following lines, including the lines of later paragraphs, are
+/// indented with `rest_prefix`. This gives a hanging indentation
+/// which is useful when rendering items of a bulleted list where an
+/// item consists of several paragraphs.
+///
+/// Like with [`indent()`], trailing whitespace is stripped from the
+/// prefix on empty lines.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::indent_paragraphs;
+///
+/// let item = "First paragraph\nof the item.\n\nSecond paragraph.\n";
+/// assert_eq!(indent_paragraphs(item, "* ", "  "),
+///            "* First paragraph\n  of the item.\n\n  Second paragraph.\n");
+/// ```
+pub fn indent_paragraphs<'a>(s: &'a str, first_prefix: &str, rest_prefix: &str) -> Cow<'a, str> {
+    if first_prefix.is_empty() && rest_prefix.is_empty() {
+        return Cow::Borrowed(s);
+    }
+
+    let mut result = String::with_capacity(2 * s.len());
+    indent_lines_into(s, first_prefix, rest_prefix, &mut result);
+    Cow::Owned(result)
+}
+
+/// Wrap text and indent every line with a prefix.
+///
+/// The text is filled with [`fill()`] to the width left over after
+/// the prefix, and then indented with [`indent()`]. The lines,
+/// including the prefix, are thus no wider than the width. Any
+/// indentation in `width_or_options` is replaced by the prefix.
+///
+/// Like with [`indent()`], empty lines are indented with the prefix
+/// minus its trailing whitespace. Use [`wrap_and_indent_with()`] if
+/// you prefer to leave the empty lines empty.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::wrap_and_indent;
+///
+/// let text = "Memory safety without garbage collection.\n\nConcurrency without data races.\n";
+/// assert_eq!(wrap_and_indent(text, 20, "> "),
+///            "> Memory safety\n> without garbage\n> collection.\n>\n> Concurrency\n> without data\n> races.\n");
+/// ```
+pub fn wrap_and_indent<'a, Opt>(text: &str, width_or_options: Opt, prefix: &str) -> String
+where
+    Opt: Into<Options<'a>>,
+{
+    wrap_and_indent_with(text, width_or_options, prefix, true)
+}
+
+/// Wrap text and indent every line with a prefix, optionally leaving
+/// empty lines bare.
+///
+/// This works like [`wrap_and_indent()`] when `indent_empty_lines`
+/// is `true`. When it is `false`, empty lines are left empty instead
+/// of being indented with the prefix minus its trailing whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::wrap_and_indent_with;
+///
+/// let text = "Memory safety.\n\nNo data races.\n";
+/// assert_eq!(wrap_and_indent_with(text, 20, "> ", true),
+///            "> Memory safety.\n>\n> No data races.\n");
+/// assert_eq!(wrap_and_indent_with(text, 20, "> ", false),
+///            "> Memory safety.\n\n> No data races.\n");
+/// ```
+pub fn wrap_and_indent_with<'a, Opt>(
+    text: &str,
+    width_or_options: Opt,
+    prefix: &str,
+    indent_empty_lines: bool,
+) -> String
+where
+    Opt: Into<Options<'a>>,
+{
+    let options: Options<'_> = width_or_options.into();
+    let width = options
+        .width
+        .saturating_sub(display_width_with_tabs(prefix, options.tab_width));
+    let options = options
+        .width(width)
+        .initial_indent("")
+        .subsequent_indent("");
+    let filled = fill(text, options);
+    if indent_empty_lines {
+        return indent(&filled, prefix).into_owned();
+    }
+
+    let mut result = String::with_capacity(2 * filled.len());
+    for line in filled.split_inclusive('\n') {
+        if !line.trim().is_empty() {
+            result.push_str(prefix);
+        }
+        result.push_str(line);
+    }
+    result
+}
+
+/// Indent the first non-empty line with `first_prefix` and the other
+/// lines with `rest_prefix`.
+fn indent_lines_into(s: &str, first_prefix: &str, rest_prefix: &str, buffer: &mut String) {
+    let trimmed_prefix = rest_prefix.trim_end();
+    let mut prefix = first_prefix;
     for (idx, line) in s.split_terminator('\n').enumerate() {
         if idx > 0 {
-            result.push('\n');
+            buffer.push('\n');
         }
         if line.trim().is_empty() {
-            result.push_str(trimmed_prefix);
+            buffer.push_str(trimmed_prefix);
         } else {
-            result.push_str(prefix);
+            buffer.push_str(prefix);
+            prefix = rest_prefix;
         }
-        result.push_str(line);
+        buffer.push_str(line);
     }
     if s.ends_with('\n') {
         // split_terminator will have eaten the final '\n'.
-        result.push('\n');
+        buffer.push('\n');
     }
-    result
 }
 
 /// Removes common leading whitespace from each line.
@@ -92,7 +248,9 @@ pub fn indent(s: &str, prefix: &str) -> String {
 /// 3rd line
 /// ");
 /// ```
-pub fn dedent(s: &str) -> String {
+///
+/// The text is borrowed if there is nothing to remove.
+pub fn dedent(s: &str) -> Cow<'_, str> {
     let mut prefix = "";
     let mut lines = s.lines();
 
@@ -131,6 +289,14 @@ pub fn dedent(s: &str) -> String {
         }
     }
 
+    if prefix.is_empty()
+        && !s.contains('\r')
+        && s.lines()
+            .all(|line| line.is_empty() || line.chars().any(|c| !c.is_whitespace()))
+    {
+        return Cow::Borrowed(s);
+    }
+
     // We now go over the lines a second time to build the result.
     let mut result = String::new();
     for line in s.lines() {
@@ -146,7 +312,114 @@ pub fn dedent(s: &str) -> String {
         result.truncate(new_len);
     }
 
-    result
+    Cow::Owned(result)
+}
+
+/// Removes common leading whitespace from each line, comparing the
+/// indentation by its width.
+///
+/// Unlike [`dedent()`], which removes the longest prefix shared
+/// byte-for-byte by all non-empty lines, this function measures the
+/// indentation of each line in columns. A `'\t'` advances to the next
+/// multiple of `tab_width` and other whitespace counts as one column.
+/// The smallest indentation is then removed from all lines. This
+/// means that text indented with a mix of tabs and spaces is dedented
+/// as it looks in an editor:
+///
+/// ```
+/// use textwrap::dedent_with_tabs;
+///
+/// assert_eq!(dedent_with_tabs("\tfoo\n        bar\n", 8), "foo\nbar\n");
+/// assert_eq!(dedent_with_tabs("\t\tfoo\n    bar\n", 4), "\tfoo\nbar\n");
+/// ```
+///
+/// Whitespace after the removed indentation is kept as-is, so tabs
+/// further in keep their relative position. A tab which straddles the
+/// removed indentation is replaced by the spaces needed to fill the
+/// remaining columns.
+///
+/// Lines consisting only of whitespace become empty, just like with
+/// [`dedent()`]. Line endings are kept unchanged and the text is
+/// borrowed if there is nothing to remove:
+///
+/// ```
+/// use std::borrow::Cow;
+/// use textwrap::dedent_with_tabs;
+///
+/// assert!(matches!(dedent_with_tabs("foo\r\n  bar\r\n", 4), Cow::Borrowed(_)));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `tab_width` is zero.
+pub fn dedent_with_tabs(s: &str, tab_width: usize) -> Cow<'_, str> {
+    assert!(tab_width > 0, "tab_width must be positive");
+
+    let mut min_width = None;
+    let mut has_blank_lines = false;
+    for line in s.split_inclusive('\n') {
+        let content = strip_line_ending(line);
+        match indentation_width(content, tab_width) {
+            Some(width) => min_width = Some(min_width.map_or(width, |min: usize| min.min(width))),
+            None => has_blank_lines |= !content.is_empty(),
+        }
+    }
+
+    let min_width = min_width.unwrap_or(0);
+    if min_width == 0 && !has_blank_lines {
+        return Cow::Borrowed(s);
+    }
+
+    let mut result = String::with_capacity(s.len());
+    for line in s.split_inclusive('\n') {
+        let content = strip_line_ending(line);
+        let line_ending = &line[content.len()..];
+        if indentation_width(content, tab_width).is_some() {
+            let mut column = 0;
+            let mut chars = content.char_indices();
+            let mut tail = content;
+            while column < min_width {
+                let (idx, ch) = match chars.next() {
+                    Some(item) => item,
+                    None => break,
+                };
+                column = next_column(column, ch, tab_width);
+                tail = &content[idx + ch.len_utf8()..];
+            }
+            result.extend(std::iter::repeat(' ').take(column - min_width));
+            result.push_str(tail);
+        }
+        result.push_str(line_ending);
+    }
+    Cow::Owned(result)
+}
+
+/// Strip a trailing `"\n"` or `"\r\n"` from `line`.
+fn strip_line_ending(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// The column reached after `ch` when starting at `column`.
+fn next_column(column: usize, ch: char, tab_width: usize) -> usize {
+    if ch == '\t' {
+        (column / tab_width + 1) * tab_width
+    } else {
+        column + 1
+    }
+}
+
+/// The width of the leading whitespace in `line`, or `None` if the
+/// line consists only of whitespace.
+fn indentation_width(line: &str, tab_width: usize) -> Option<usize> {
+    let mut column = 0;
+    for ch in line.chars() {
+        if !ch.is_whitespace() {
+            return Some(column);
+        }
+        column = next_column(column, ch, tab_width);
+    }
+    None
 }
 
 #[cfg(test)]
@@ -192,6 +465,30 @@ mod tests {
         assert_eq!(indent(&text, "// "), expected);
     }
 
+    #[test]
+    fn indent_borrows_with_empty_prefix() {
+        assert!(matches!(indent("foo\n\nbar", ""), Cow::Borrowed(_)));
+        assert!(matches!(indent("foo", " "), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn indent_paragraphs_leading_empty_lines() {
+        assert_eq!(
+            indent_paragraphs("\n  \nfoo\nbar", "- ", "  "),
+            "\n  \n- foo\n  bar"
+        );
+        assert_eq!(indent_paragraphs("foo\n", "", "> "), "foo\n");
+        assert_eq!(indent_paragraphs("foo\nbar", "", "> "), "foo\n> bar");
+    }
+
+    #[test]
+    fn dedent_borrows_without_common_whitespace() {
+        assert!(matches!(dedent("foo\n\n  bar\n"), Cow::Borrowed(_)));
+        assert!(matches!(dedent("foo\n  \nbar"), Cow::Owned(_)));
+        assert!(matches!(dedent("foo\r\nbar"), Cow::Owned(_)));
+        assert_eq!(dedent("foo\r\nbar"), "foo\nbar");
+    }
+
     #[test]
     fn dedent_empty() {
         assert_eq!(dedent(""), "");
@@ -344,4 +641,72 @@ mod tests {
         ].join("\n");
         assert_eq!(dedent(&x), y);
     }
+
+    #[test]
+    fn dedent_with_tabs_straddling_tab() {
+        assert_eq!(dedent_with_tabs("\tfoo\n  bar\n", 4), "  foo\nbar\n");
+    }
+
+    #[test]
+    fn dedent_with_tabs_keeps_inner_tabs() {
+        assert_eq!(
+            dedent_with_tabs("    foo\n    \tbar\tbaz", 4),
+            "foo\n\tbar\tbaz"
+        );
+    }
+
+    #[test]
+    fn dedent_with_tabs_blank_lines() {
+        assert_eq!(
+            dedent_with_tabs("  foo\r\n \t \r\n\r\n  bar", 8),
+            "foo\r\n\r\n\r\nbar"
+        );
+        assert_eq!(dedent_with_tabs("foo\n  \n", 8), "foo\n\n");
+    }
+
+    #[test]
+    fn dedent_with_tabs_borrows() {
+        assert!(matches!(dedent_with_tabs("", 8), Cow::Borrowed("")));
+        assert!(matches!(
+            dedent_with_tabs("foo\n\n  bar", 8),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn wrap_and_indent_replaces_indentation() {
+        let options = Options::new(10)
+            .initial_indent("* ")
+            .subsequent_indent("  ");
+        assert_eq!(
+            wrap_and_indent("foo bar baz", &options, "// "),
+            "// foo bar\n// baz"
+        );
+    }
+
+    #[test]
+    fn wrap_and_indent_with_empty_lines() {
+        let text = "foo bar\n\nbaz\n";
+        assert_eq!(
+            wrap_and_indent_with(text, 7, "# ", true),
+            "# foo\n# bar\n#\n# baz\n"
+        );
+        assert_eq!(
+            wrap_and_indent_with(text, 7, "# ", false),
+            "# foo\n# bar\n\n# baz\n"
+        );
+        assert_eq!(
+            wrap_and_indent(text, 7, "# "),
+            wrap_and_indent_with(text, 7, "# ", true)
+        );
+    }
+
+    #[test]
+    fn wrap_and_indent_measures_tabs() {
+        let options = Options::new(12).tab_width(4);
+        assert_eq!(
+            wrap_and_indent("foo bar baz", &options, "\t"),
+            "\tfoo bar\n\tbaz"
+        );
+    }
 }
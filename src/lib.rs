@@ -179,12 +179,56 @@
 //!
 //! * `terminal_size`: enables automatic detection of the terminal
 //!   width via the [terminal_size] crate. See
-//!   [`Options::with_termwidth()`] for details.
+//!   [`Options::with_termwidth()`] and [`TerminalWrapper`] for
+//!   details.
 //!
 //! * `hyphenation`: enables language-sensitive hyphenation via the
 //!   [hyphenation] crate. See the [`word_splitters::WordSplitter`]
 //!   trait for details.
 //!
+//! * `whatlang`: together with `hyphenation`, this enables
+//!   [`WordSplitter::AutoHyphenation`], which detects the language of
+//!   each paragraph via the [whatlang] crate and picks a matching
+//!   hyphenation dictionary.
+//!
+//! * `serde`: implements `Serialize` and `Deserialize` from the
+//!   [serde] crate for [`Options`], [`WrapAlgorithm`],
+//!   [`WordSeparator`], [`WordSplitter`], [`WordOverflow`],
+//!   [`ControlChars`], [`Normalization`], [`SentenceSpacing`],
+//!   [`LineEnding`], and [`wrap_algorithms::Penalties`]. This lets
+//!   you store wrapping configuration in configuration files.
+//!
+//!   The `Custom` variants hold function pointers and
+//!   [`WordSplitter::Hyphenation`] and
+//!   [`WordSplitter::SharedHyphenation`] hold an entire dictionary,
+//!   so these variants cannot be serialized. Neither can
+//!   [`WordSplitter::AutoHyphenation`]. The indentation strings in
+//!   [`Options`] are borrowed from the input when possible and copied
+//!   otherwise. The ellipsis in [`WordOverflow::Truncate`] is always
+//!   borrowed, which means that the deserializer must support
+//!   borrowed strings when it is used.
+//!
+//! * `unicode-normalization`: enables [`Normalization::Nfc`] and
+//!   [`Normalization::MeasureNfc`] via the [unicode-normalization]
+//!   crate. These normalize decomposed characters, such as `'e'`
+//!   followed by a combining accent, to their precomposed form.
+//!
+//! * `cache`: enables [`CachedWrapper`], which remembers wrapped text
+//!   so that it can be redrawn quickly. This is useful for terminal
+//!   user interfaces which redraw the same text many times a second.
+//!
+//! * `test-util`: enables [`diff_wrapped()`] and the
+//!   [`assert_wrapped_eq!`] macro, which compare wrapped lines with
+//!   an expected block of text and point out the first difference.
+//!   Enable this in your `[dev-dependencies]` to test code which
+//!   wraps text.
+//!
+//! * `proptest`: enables the [`proptest_support`] module with
+//!   [proptest] strategies for text and [`Options`], and checks for
+//!   the invariants of wrapped text. Enable this in your
+//!   `[dev-dependencies]` to write property-based tests for code
+//!   which wraps text.
+//!
 //! [unicode-linebreak]: https://docs.rs/unicode-linebreak/
 //! [unicode-width]: https://docs.rs/unicode-width/
 //! [smawk]: https://docs.rs/smawk/
@@ -192,6 +236,10 @@
 //! [textwrap-macros]: https://docs.rs/textwrap-macros/
 //! [terminal_size]: https://docs.rs/terminal_size/
 //! [hyphenation]: https://docs.rs/hyphenation/
+//! [whatlang]: https://docs.rs/whatlang/
+//! [serde]: https://docs.rs/serde/
+//! [unicode-normalization]: https://docs.rs/unicode-normalization/
+//! [proptest]: https://docs.rs/proptest/
 
 #![doc(html_root_url = "https://docs.rs/textwrap/0.16.1")]
 #![forbid(unsafe_code)] // See https://github.com/mgeisler/textwrap/issues/210
@@ -207,29 +255,84 @@ mod readme_doctest {}
 pub mod core;
 #[cfg(fuzzing)]
 pub mod fuzzing;
+pub mod measure;
+#[cfg(any(test, feature = "proptest"))]
+pub mod proptest_support;
+pub mod vertical;
+pub mod word_separators;
 pub mod word_splitters;
 pub mod wrap_algorithms;
 
+mod alignment;
+mod annotate;
+mod block;
+mod break_points;
+#[cfg(feature = "cache")]
+mod cached_wrapper;
 mod columns;
+mod commit_message;
+mod compact;
+mod control_chars;
+mod definition_list;
+mod doc_comment;
+mod error;
 mod fill;
 mod indentation;
 mod line_ending;
+mod min_width;
+mod normalization;
 mod options;
+mod paginate;
 mod refill;
 #[cfg(feature = "terminal_size")]
 mod termwidth;
-mod word_separators;
+#[cfg(feature = "test-util")]
+mod test_util;
+mod trailing_comments;
+mod word_overflow;
 mod wrap;
+mod wrap_stats;
 
-pub use columns::wrap_columns;
-pub use fill::{fill, fill_inplace};
-pub use indentation::{dedent, indent};
-pub use line_ending::LineEnding;
-pub use options::Options;
-pub use refill::{refill, unfill};
+pub use alignment::{center_block, pad_lines, Alignment};
+pub use annotate::{annotate_wrap, debug_ruler};
+pub use block::wrap_block;
+pub use break_points::{analyze, BreakCandidate, BreakKind, BreakPoint};
+#[cfg(feature = "cache")]
+pub use cached_wrapper::CachedWrapper;
+pub use columns::{
+    try_wrap_columns, try_wrap_columns_with, wrap_columns, wrap_columns_side_by_side,
+    wrap_columns_with, Column, VerticalAlignment,
+};
+pub use commit_message::format_commit_message;
+pub use compact::Compact;
+pub use control_chars::ControlChars;
+pub use definition_list::{wrap_definition_list, wrap_help, wrap_kv};
+pub use doc_comment::refill_doc_comment;
+pub use error::{Error, ParseError};
+pub use fill::{fill, fill_inplace, fill_into, try_fill};
+pub use indentation::{
+    dedent, dedent_with_tabs, indent, indent_into, indent_paragraphs, wrap_and_indent,
+    wrap_and_indent_with,
+};
+pub use line_ending::{lines_with_endings, LineEnding};
+pub use min_width::MinWidth;
+pub use normalization::Normalization;
+pub use options::{Options, OptionsKey};
+pub use paginate::paginate;
+pub use refill::{
+    refill, refill_continued_lines, refill_with, unfill, unfill_with_hard_breaks, RefillKeep,
+    SentenceSpacing,
+};
+#[cfg(all(feature = "terminal_size", any(unix, windows)))]
+pub use termwidth::termwidth_of;
 #[cfg(feature = "terminal_size")]
-pub use termwidth::termwidth;
+pub use termwidth::{termwidth, termwidth_opt, termwidth_with_env, TerminalWrapper};
+#[cfg(feature = "test-util")]
+pub use test_util::diff_wrapped;
+pub use trailing_comments::{wrap_trailing_comments, TrailingComment};
+pub use word_overflow::WordOverflow;
 pub use word_separators::WordSeparator;
 pub use word_splitters::WordSplitter;
-pub use wrap::wrap;
+pub use wrap::{try_wrap, wrap, wrap_into, wrap_word_indices};
 pub use wrap_algorithms::WrapAlgorithm;
+pub use wrap_stats::{fill_height, try_fill_height, try_wrap_stats, wrap_stats, WrapStats};
@@ -1,10 +1,12 @@
 //! Line ending detection and conversion.
 
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 /// Supported line endings. Like in the Rust standard library, two line
 /// endings are supported: `\r\n` and `\n`
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineEnding {
     /// _Carriage return and line feed_ – a line ending sequence
     /// historically used in Windows. Corresponds to the sequence
@@ -24,6 +26,130 @@ impl LineEnding {
             Self::LF => "\n",
         }
     }
+
+    /// Detect the line ending used in `text`.
+    ///
+    /// Returns `None` if `text` has no line endings. Text with mixed
+    /// line endings is detected as [`LineEnding::LF`], just like in
+    /// [`unfill()`](crate::unfill()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::detect("foo\r\nbar\r\n"), Some(LineEnding::CRLF));
+    /// assert_eq!(LineEnding::detect("foo\r\nbar\n"), Some(LineEnding::LF));
+    /// assert_eq!(LineEnding::detect("foo"), None);
+    /// ```
+    pub fn detect(text: &str) -> Option<LineEnding> {
+        let mut detected = None;
+        for (idx, _) in text.match_indices('\n') {
+            if idx == 0 || text.as_bytes()[idx - 1] != b'\r' {
+                return Some(LineEnding::LF);
+            }
+            detected = Some(LineEnding::CRLF);
+        }
+        detected
+    }
+
+    /// Convert all line endings in `text` to this line ending.
+    ///
+    /// Both `"\r\n"` and `"\n"` are recognized as line endings. The
+    /// text is borrowed if it already uses this line ending
+    /// throughout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use textwrap::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::LF.normalize("foo\r\nbar\n"), "foo\nbar\n");
+    /// assert_eq!(LineEnding::CRLF.normalize("foo\r\nbar\n"), "foo\r\nbar\r\n");
+    /// assert!(matches!(LineEnding::LF.normalize("foo\nbar\n"), Cow::Borrowed(_)));
+    /// ```
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let line_ending_str = self.as_str();
+        let normalized = text.match_indices('\n').all(|(idx, _)| {
+            let crlf = idx > 0 && text.as_bytes()[idx - 1] == b'\r';
+            crlf == (*self == LineEnding::CRLF)
+        });
+        if normalized {
+            return Cow::Borrowed(text);
+        }
+
+        let mut result = String::with_capacity(text.len() + text.len() / 16);
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                result.push_str(line_ending_str);
+            }
+            result.push_str(line.strip_suffix('\r').unwrap_or(line));
+        }
+        // The last segment is not followed by a line ending, so any
+        // '\r' we stripped from it must be put back.
+        if text.ends_with('\r') {
+            result.push('\r');
+        }
+        Cow::Owned(result)
+    }
+}
+
+/// Iterate over the lines of `text` together with their line endings.
+///
+/// Both `"\r\n"` and `"\n"` are recognized as line endings. Unlike
+/// [`str::lines`], empty lines are included and a trailing line
+/// ending results in a final empty line without a line ending. This
+/// is exactly how [`wrap()`](crate::wrap()) and
+/// [`fill()`](crate::fill()) split their input before wrapping each
+/// line.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{lines_with_endings, LineEnding};
+///
+/// assert_eq!(
+///     lines_with_endings("foo\r\n\nbar\n").collect::<Vec<_>>(),
+///     vec![
+///         ("foo", Some(LineEnding::CRLF)),
+///         ("", Some(LineEnding::LF)),
+///         ("bar", Some(LineEnding::LF)),
+///         ("", None),
+///     ]
+/// );
+/// ```
+pub fn lines_with_endings(text: &str) -> impl Iterator<Item = (&str, Option<LineEnding>)> {
+    let mut segments = text.split('\n').peekable();
+    std::iter::from_fn(move || {
+        let segment = segments.next()?;
+        if segments.peek().is_none() {
+            return Some((segment, None));
+        }
+        Some(match segment.strip_suffix('\r') {
+            Some(line) => (line, Some(LineEnding::CRLF)),
+            None => (segment, Some(LineEnding::LF)),
+        })
+    })
+}
+
+/// Replace U+2028 LINE SEPARATOR with `'\n'` and U+2029 PARAGRAPH
+/// SEPARATOR with `"\n\n"`. See [`Options::unicode_separators`].
+///
+/// [`Options::unicode_separators`]: crate::Options::unicode_separators
+pub(crate) fn replace_unicode_separators(text: &str) -> Cow<'_, str> {
+    if !text.contains(['\u{2028}', '\u{2029}']) {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\u{2028}' => result.push('\n'),
+            '\u{2029}' => result.push_str("\n\n"),
+            _ => result.push(ch),
+        }
+    }
+    Cow::Owned(result)
 }
 
 /// An iterator over the lines of a string, as tuples of string slice
@@ -81,6 +207,37 @@ mod tests {
         assert_eq!(NonEmptyLines("\r\n\n\n\r\n").next(), None);
     }
 
+    #[test]
+    fn detect_leading_newline() {
+        assert_eq!(LineEnding::detect("\nfoo\r\n"), Some(LineEnding::LF));
+        assert_eq!(LineEnding::detect("\r\n"), Some(LineEnding::CRLF));
+        assert_eq!(LineEnding::detect("foo\r"), None);
+    }
+
+    #[test]
+    fn normalize_keeps_lone_carriage_returns() {
+        assert_eq!(LineEnding::LF.normalize("a\rb\r\nc\r"), "a\rb\nc\r");
+        assert_eq!(LineEnding::CRLF.normalize("a\rb\nc\r"), "a\rb\r\nc\r");
+    }
+
+    #[test]
+    fn lines_with_endings_edge_cases() {
+        assert_eq!(lines_with_endings("").collect::<Vec<_>>(), vec![("", None)]);
+        assert_eq!(
+            lines_with_endings("\r\r\n\r").collect::<Vec<_>>(),
+            vec![("\r", Some(LineEnding::CRLF)), ("\r", None)]
+        );
+    }
+
+    #[test]
+    fn replace_unicode_separators_borrows() {
+        assert!(matches!(
+            replace_unicode_separators("a\nb"),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(replace_unicode_separators("a\u{2028}\u{2029}b"), "a\n\n\nb");
+    }
+
     #[test]
     fn non_empty_lines_no_input() {
         assert_eq!(NonEmptyLines("").next(), None);
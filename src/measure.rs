@@ -0,0 +1,614 @@
+//! Wrapping text with custom width measurements.
+//!
+//! The high-level functions such as [`wrap()`](crate::wrap()) measure
+//! text in columns, which is right for terminals and other monospace
+//! output. When you draw text with a proportional font, such as on a
+//! HTML canvas in a WebAssembly program, you instead need to measure
+//! the text in pixels or points.
+//!
+//! The [`ClosureWidth`] adapter lets you do this with a closure which
+//! measures strings. It runs the same word separation and splitting
+//! as the high-level functions, measures the resulting words with
+//! your closure, and wraps them with the wrap algorithm from your
+//! [`Options`].
+//!
+//! Use [`OptionsF64`] with [`ClosureWidth::wrap_with()`] when the
+//! line width and the indentation are fractional too.
+//!
+//! If you have no canvas to measure text with, [`FontMetrics`] can
+//! hold a table of advance widths, e.g., loaded from a BDF bitmap
+//! font, and measure text with it.
+
+use std::collections::HashMap;
+
+use crate::break_points::split_filtered_words;
+use crate::core::{display_width, Fragment, Word};
+use crate::wrap::split_lines;
+use crate::{Error, Options, ParseError};
+
+/// A [`Word`] measured by a [`ClosureWidth`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasuredWord<'a> {
+    /// The measured word.
+    pub word: Word<'a>,
+    /// Width of `word.word`.
+    pub width: f64,
+    /// Width of `word.whitespace`.
+    pub whitespace_width: f64,
+    /// Width of `word.penalty`.
+    pub penalty_width: f64,
+}
+
+impl Fragment for MeasuredWord<'_> {
+    #[inline]
+    fn width(&self) -> f64 {
+        self.width
+    }
+
+    #[inline]
+    fn whitespace_width(&self) -> f64 {
+        self.whitespace_width
+    }
+
+    #[inline]
+    fn penalty_width(&self) -> f64 {
+        self.penalty_width
+    }
+
+    #[inline]
+    fn ends_sentence(&self) -> bool {
+        self.word.ends_sentence()
+    }
+}
+
+/// Options for wrapping text measured with `f64` widths.
+///
+/// This is the counterpart of [`Options`] for text drawn with a
+/// proportional font: the line width and the width of the
+/// indentation are given in the same unit as the measured words,
+/// e.g., pixels or points. The word separation, word splitting, and
+/// wrap algorithm are taken from [`OptionsF64::options`]. Its
+/// `width` and indentation are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::measure::{ClosureWidth, OptionsF64};
+///
+/// let measure = ClosureWidth::new(|text: &str| 1.5 * text.len() as f64);
+/// let options = OptionsF64::new(10.5).initial_indent(3.0);
+/// let lines = measure.wrap_with("foo bar baz", &options).unwrap();
+/// let lines = lines
+///     .iter()
+///     .map(|line| line.iter().map(|word| word.word.word).collect::<Vec<_>>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(lines, vec![vec!["foo"], vec!["bar", "baz"]]);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct OptionsF64<'a> {
+    /// The width in which to wrap the text.
+    pub width: f64,
+    /// The width of the indentation used on the first line.
+    pub initial_indent: f64,
+    /// The width of the indentation used on all other lines.
+    pub subsequent_indent: f64,
+    /// How to find, split, and wrap the words.
+    pub options: Options<'a>,
+}
+
+impl<'a> OptionsF64<'a> {
+    /// Create new options for wrapping text in `width`. The text is
+    /// not indented and the other settings are taken from
+    /// [`Options::new()`].
+    pub fn new(width: f64) -> Self {
+        OptionsF64 {
+            width,
+            initial_indent: 0.0,
+            subsequent_indent: 0.0,
+            options: Options::new(0),
+        }
+    }
+
+    /// Change [`self.initial_indent`].
+    ///
+    /// [`self.initial_indent`]: #structfield.initial_indent
+    pub fn initial_indent(self, initial_indent: f64) -> Self {
+        OptionsF64 {
+            initial_indent,
+            ..self
+        }
+    }
+
+    /// Change [`self.subsequent_indent`].
+    ///
+    /// [`self.subsequent_indent`]: #structfield.subsequent_indent
+    pub fn subsequent_indent(self, subsequent_indent: f64) -> Self {
+        OptionsF64 {
+            subsequent_indent,
+            ..self
+        }
+    }
+
+    /// Change [`self.options`].
+    ///
+    /// [`self.options`]: #structfield.options
+    pub fn options(self, options: Options<'a>) -> Self {
+        OptionsF64 { options, ..self }
+    }
+}
+
+impl From<f64> for OptionsF64<'_> {
+    fn from(width: f64) -> Self {
+        OptionsF64::new(width)
+    }
+}
+
+/// Measures and wraps text using a closure which computes the width
+/// of a string.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::measure::ClosureWidth;
+/// use textwrap::{Options, WrapAlgorithm};
+///
+/// // A font where 'i' is narrow and 'm' is wide.
+/// let measure = ClosureWidth::new(|text: &str| {
+///     text.chars()
+///         .map(|ch| match ch {
+///             'i' => 0.5,
+///             'm' => 2.0,
+///             _ => 1.0,
+///         })
+///         .sum()
+/// });
+///
+/// let options = Options::new(80).wrap_algorithm(WrapAlgorithm::FirstFit);
+/// let lines = measure.wrap("mmm iii iii mmm", 8.0, &options).unwrap();
+/// let lines = lines
+///     .iter()
+///     .map(|line| line.iter().map(|word| word.word.word).collect::<Vec<_>>().join(" "))
+///     .collect::<Vec<_>>();
+/// assert_eq!(lines, vec!["mmm", "iii iii", "mmm"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ClosureWidth<F> {
+    measure: F,
+}
+
+impl<F> ClosureWidth<F>
+where
+    F: Fn(&str) -> f64,
+{
+    /// Create a new adapter which measures text with `measure`.
+    pub const fn new(measure: F) -> Self {
+        ClosureWidth { measure }
+    }
+
+    /// Measure the width of `text`.
+    pub fn width(&self, text: &str) -> f64 {
+        (self.measure)(text)
+    }
+
+    /// Measure `word`, its whitespace, and its penalty.
+    pub fn measure<'a>(&self, word: Word<'a>) -> MeasuredWord<'a> {
+        MeasuredWord {
+            word,
+            width: self.width(word.word),
+            whitespace_width: self.width(word.whitespace),
+            penalty_width: self.width(word.penalty),
+        }
+    }
+
+    /// Break `word` into smaller words which are at most
+    /// `line_width` wide.
+    ///
+    /// Like [`Word::break_apart()`], this breaks the word between
+    /// characters and inserts no hyphens. All parts except the last
+    /// get a width of at least `line_width`. This ensures that they
+    /// take up a line of their own, which keeps the wrapping stable
+    /// when the line width changes slightly.
+    pub fn break_apart<'a>(&self, word: Word<'a>, line_width: f64) -> Vec<MeasuredWord<'a>> {
+        let measured = self.measure(word);
+        if measured.width <= line_width {
+            return vec![measured];
+        }
+
+        let mut start = 0;
+        let mut words = Vec::new();
+        for (idx, ch) in word.word.char_indices() {
+            let with_char = &word.word[start..idx + ch.len_utf8()];
+            if idx > start && self.width(with_char) > line_width {
+                let without_char = &word.word[start..idx];
+                words.push(MeasuredWord {
+                    word: Word {
+                        word: without_char,
+                        whitespace: "",
+                        tab_width: word.tab_width,
+                        penalty: "",
+                        payload: (),
+                        width: display_width(without_char),
+                    },
+                    width: line_width.max(self.width(without_char)),
+                    whitespace_width: 0.0,
+                    penalty_width: 0.0,
+                });
+                start = idx;
+            }
+        }
+
+        let rest = &word.word[start..];
+        words.push(MeasuredWord {
+            word: Word {
+                word: rest,
+                whitespace: word.whitespace,
+                tab_width: word.tab_width,
+                penalty: word.penalty,
+                payload: (),
+                width: display_width(rest),
+            },
+            width: self.width(rest),
+            ..measured
+        });
+        words
+    }
+
+    /// Wrap `text` into lines which are at most `line_width` wide.
+    ///
+    /// The words are found, split, and broken according to
+    /// `options`, just like [`wrap()`](crate::wrap()) does it. The
+    /// `options.width` and the indentation are ignored: you place the
+    /// lines yourself when drawing them. Use
+    /// [`ClosureWidth::wrap_with()`] to leave room for indentation.
+    /// A [`WrapAlgorithm::Custom`] function is called as described in
+    /// [`WrapAlgorithm::wrap_fragments()`].
+    ///
+    /// Each line of `text` is wrapped into one or more output lines.
+    /// An output line is the list of words on it. Use the
+    /// `whitespace` of all but the last word and the `penalty` of the
+    /// last word when drawing the line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overflow`] if the optimal-fit algorithm
+    /// overflows. This can happen with very large widths, see
+    /// [`wrap_optimal_fit()`](crate::wrap_algorithms::wrap_optimal_fit)
+    /// for details.
+    ///
+    /// [`WrapAlgorithm::Custom`]: crate::WrapAlgorithm::Custom
+    /// [`WrapAlgorithm::wrap_fragments()`]: crate::WrapAlgorithm::wrap_fragments
+    pub fn wrap<'a>(
+        &self,
+        text: &'a str,
+        line_width: f64,
+        options: &'a Options<'_>,
+    ) -> Result<Vec<Vec<MeasuredWord<'a>>>, Error> {
+        self.wrap_lines(text, [line_width, line_width], options)
+    }
+
+    /// Wrap `text` according to `options`, measuring everything in
+    /// `f64` widths.
+    ///
+    /// This works like [`ClosureWidth::wrap()`], but the first line
+    /// is shortened by the initial indentation and the other lines
+    /// by the subsequent indentation. The indentation itself is not
+    /// part of the returned lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overflow`] if the optimal-fit algorithm
+    /// overflows, see [`ClosureWidth::wrap()`].
+    pub fn wrap_with<'a>(
+        &self,
+        text: &'a str,
+        options: &'a OptionsF64<'_>,
+    ) -> Result<Vec<Vec<MeasuredWord<'a>>>, Error> {
+        let line_widths = [
+            options.width - options.initial_indent,
+            options.width - options.subsequent_indent,
+        ];
+        self.wrap_lines(text, line_widths, &options.options)
+    }
+
+    fn wrap_lines<'a>(
+        &self,
+        text: &'a str,
+        mut line_widths: [f64; 2],
+        options: &'a Options<'_>,
+    ) -> Result<Vec<Vec<MeasuredWord<'a>>>, Error> {
+        let line_width = line_widths[1];
+        let mut lines = Vec::new();
+        for (line_offset, line) in split_lines(text) {
+            if !lines.is_empty() {
+                line_widths[0] = line_width;
+            }
+            let words = split_filtered_words(line, line_offset, options)
+                .flat_map(|word| {
+                    let measured = self.measure(word);
+                    let too_wide = measured.width > line_width;
+                    if too_wide
+                        && options.break_words
+                        && options.break_words_if.map_or(true, |f| f(&word))
+                    {
+                        self.break_apart(word, line_width)
+                    } else {
+                        vec![measured]
+                    }
+                })
+                .collect::<Vec<_>>();
+            if words.is_empty() {
+                lines.push(Vec::new());
+                continue;
+            }
+
+            let wrapped_words = options
+                .wrap_algorithm
+                .try_wrap_fragments(&words, &line_widths)?;
+            lines.extend(wrapped_words.into_iter().map(|line| line.to_vec()));
+        }
+        Ok(lines)
+    }
+}
+
+/// A table of advance widths for the characters of a font.
+///
+/// The advance width of a character is the distance the pen moves
+/// after drawing it. Characters which are not in the table use a
+/// default advance. Kerning and ligatures are not supported, so the
+/// width of a string is simply the sum of the advance widths of its
+/// characters. This is exact for bitmap fonts and a good
+/// approximation for many proportional fonts.
+///
+/// Use [`FontMetrics::closure_width()`] to wrap text with the
+/// metrics.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use textwrap::measure::FontMetrics;
+/// use textwrap::{Options, WrapAlgorithm};
+///
+/// let advances = HashMap::from([('i', 3.0), ('l', 3.0), ('m', 11.0)]);
+/// let metrics = FontMetrics::from_map(advances, 7.0);
+/// assert_eq!(metrics.width("mill"), 20.0);
+///
+/// let options = Options::new(80).wrap_algorithm(WrapAlgorithm::FirstFit);
+/// let measure = metrics.closure_width();
+/// let lines = measure.wrap("ill mill hill", 40.0, &options).unwrap();
+/// assert_eq!(lines.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FontMetrics {
+    advances: HashMap<char, f64>,
+    default_advance: f64,
+}
+
+impl FontMetrics {
+    /// Create empty metrics where every character has the
+    /// `default_advance` width.
+    pub fn new(default_advance: f64) -> Self {
+        FontMetrics {
+            advances: HashMap::new(),
+            default_advance,
+        }
+    }
+
+    /// Create metrics from a table of `advances`. Characters which
+    /// are not in the table have the `default_advance` width.
+    pub fn from_map(advances: HashMap<char, f64>, default_advance: f64) -> Self {
+        FontMetrics {
+            advances,
+            default_advance,
+        }
+    }
+
+    /// Load metrics from the source of a font in the [Glyph Bitmap
+    /// Distribution Format][bdf] (BDF).
+    ///
+    /// The `ENCODING` and `DWIDTH` of every glyph are used. Glyphs
+    /// with a negative encoding are skipped. The default advance is
+    /// the global `DWIDTH` of the font, or the width of the
+    /// `FONTBOUNDINGBOX` if there is no global `DWIDTH`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::Malformed`] with the line number if an
+    /// `ENCODING`, `DWIDTH`, or `FONTBOUNDINGBOX` line has no valid
+    /// number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::measure::FontMetrics;
+    ///
+    /// let bdf = "\
+    /// STARTFONT 2.1
+    /// FONTBOUNDINGBOX 6 12 0 -2
+    /// CHARS 2
+    /// STARTCHAR i
+    /// ENCODING 105
+    /// DWIDTH 3 0
+    /// ENDCHAR
+    /// STARTCHAR W
+    /// ENCODING 87
+    /// DWIDTH 8 0
+    /// ENDCHAR
+    /// ENDFONT
+    /// ";
+    /// let metrics = FontMetrics::from_bdf(bdf).unwrap();
+    /// assert_eq!(metrics.width("Wii"), 14.0);
+    /// assert_eq!(metrics.advance('x'), 6.0);
+    /// ```
+    ///
+    /// [bdf]: https://en.wikipedia.org/wiki/Glyph_Bitmap_Distribution_Format
+    pub fn from_bdf(source: &str) -> Result<Self, ParseError> {
+        let mut metrics = FontMetrics::new(0.0);
+        let mut bounding_box_width = None;
+        let mut has_default = false;
+        let mut encoding = None;
+        let mut in_char = false;
+        for (idx, line) in source.lines().enumerate() {
+            let mut fields = line.split_whitespace();
+            let keyword = fields.next();
+            let mut number = || -> Result<f64, ParseError> {
+                fields
+                    .next()
+                    .and_then(|field| field.parse().ok())
+                    .ok_or(ParseError::Malformed { line: idx + 1 })
+            };
+            match keyword {
+                Some("STARTCHAR") => {
+                    in_char = true;
+                    encoding = None;
+                }
+                Some("ENDCHAR") => in_char = false,
+                Some("ENCODING") => {
+                    let value = number()?;
+                    encoding = if value >= 0.0 {
+                        char::from_u32(value as u32)
+                    } else {
+                        None
+                    };
+                }
+                Some("DWIDTH") if in_char => {
+                    let advance = number()?;
+                    if let Some(ch) = encoding {
+                        metrics.advances.insert(ch, advance);
+                    }
+                }
+                Some("DWIDTH") => {
+                    metrics.default_advance = number()?;
+                    has_default = true;
+                }
+                Some("FONTBOUNDINGBOX") => bounding_box_width = Some(number()?),
+                _ => {}
+            }
+        }
+        if !has_default {
+            metrics.default_advance = bounding_box_width.unwrap_or(0.0);
+        }
+        Ok(metrics)
+    }
+
+    /// Set the advance width of `ch`.
+    pub fn set_advance(&mut self, ch: char, advance: f64) {
+        self.advances.insert(ch, advance);
+    }
+
+    /// The advance width of `ch`.
+    pub fn advance(&self, ch: char) -> f64 {
+        self.advances
+            .get(&ch)
+            .copied()
+            .unwrap_or(self.default_advance)
+    }
+
+    /// The width of `text`, which is the sum of the advance widths
+    /// of its characters.
+    pub fn width(&self, text: &str) -> f64 {
+        text.chars().map(|ch| self.advance(ch)).sum()
+    }
+
+    /// A [`ClosureWidth`] which measures text with these metrics.
+    pub fn closure_width(&self) -> ClosureWidth<impl Fn(&str) -> f64 + '_> {
+        ClosureWidth::new(move |text: &str| self.width(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WrapAlgorithm;
+
+    fn char_width(text: &str) -> f64 {
+        text.chars().count() as f64
+    }
+
+    fn words<'a>(line: &[MeasuredWord<'a>]) -> Vec<&'a str> {
+        line.iter().map(|word| word.word.word).collect()
+    }
+
+    #[test]
+    fn break_apart_fills_lines() {
+        let measure = ClosureWidth::new(char_width);
+        let parts = measure.break_apart(Word::from("abcdefg "), 3.0);
+        assert_eq!(
+            parts.iter().map(|part| part.word.word).collect::<Vec<_>>(),
+            vec!["abc", "def", "g"]
+        );
+        assert_eq!(parts[0].width, 3.0);
+        assert_eq!(parts[2].whitespace_width, 1.0);
+    }
+
+    #[test]
+    fn break_apart_keeps_wide_chars() {
+        let measure = ClosureWidth::new(|text: &str| 10.0 * char_width(text));
+        let parts = measure.break_apart(Word::from("ab"), 5.0);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].width, 10.0);
+    }
+
+    #[test]
+    fn font_metrics_from_bdf() {
+        let bdf = "STARTFONT 2.1\nDWIDTH 5 0\nFONTBOUNDINGBOX 9 9 0 0\n\
+                   STARTCHAR a\nENCODING 97\nDWIDTH 4 0\nENDCHAR\n\
+                   STARTCHAR unencoded\nENCODING -1\nDWIDTH 7 0\nENDCHAR\n";
+        let metrics = FontMetrics::from_bdf(bdf).unwrap();
+        assert_eq!(metrics.advance('a'), 4.0);
+        assert_eq!(metrics.advance('b'), 5.0);
+        assert_eq!(
+            FontMetrics::from_bdf("STARTCHAR a\nENCODING x\n"),
+            Err(ParseError::Malformed { line: 2 })
+        );
+        assert_eq!(FontMetrics::from_bdf(""), Ok(FontMetrics::new(0.0)));
+    }
+
+    #[test]
+    fn wrap_matches_columns() {
+        let options = Options::new(80)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .break_words(false);
+        let text = "foo bar-baz\n\nquux";
+        let measure = ClosureWidth::new(char_width);
+        let lines = measure.wrap(text, 6.0, &options).unwrap();
+        assert_eq!(
+            lines.iter().map(|line| words(line)).collect::<Vec<_>>(),
+            vec![vec!["foo"], vec!["bar-"], vec!["baz"], vec![], vec!["quux"]]
+        );
+        assert_eq!(lines[1][0].penalty_width, 0.0);
+    }
+
+    #[test]
+    fn wrap_with_indents() {
+        let options = OptionsF64::new(7.5)
+            .initial_indent(2.0)
+            .subsequent_indent(0.5)
+            .options(Options::new(0).wrap_algorithm(WrapAlgorithm::FirstFit));
+        let measure = ClosureWidth::new(char_width);
+        let lines = measure
+            .wrap_with(
+                "foo bar baz
+foo bar",
+                &options,
+            )
+            .unwrap();
+        assert_eq!(
+            lines.iter().map(|line| words(line)).collect::<Vec<_>>(),
+            vec![vec!["foo"], vec!["bar", "baz"], vec!["foo", "bar"]]
+        );
+    }
+
+    #[test]
+    fn wrap_custom_algorithm() {
+        let options = Options::new(80)
+            .wrap_algorithm(WrapAlgorithm::Custom(|words, _| words.chunks(1).collect()));
+        let measure = ClosureWidth::new(char_width);
+        let lines = measure.wrap("foo bar", 80.0, &options).unwrap();
+        assert_eq!(
+            lines.iter().map(|line| words(line)).collect::<Vec<_>>(),
+            vec![vec!["foo"], vec!["bar"]]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Handling of widths which leave too little room for the text.
+
+/// Describes what happens when the width leaves too little room for
+/// the text after the indentation has been subtracted.
+///
+/// A width of zero, or a width smaller than the indentation, gives
+/// lines with nothing but indentation or a single character per
+/// line. This is rarely what you want, e.g., when a window is
+/// resized to almost nothing. Use this with
+/// [`Options::min_width`](crate::Options::min_width) to either widen
+/// the text or report an error instead.
+///
+/// The room for the text is the width minus the widest of
+/// [`Options::initial_indent`](crate::Options::initial_indent) and
+/// [`Options::subsequent_indent`](crate::Options::subsequent_indent),
+/// or of all the [`Options::indents`](crate::Options::indents) if
+/// they are set.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{try_wrap, wrap, Error, MinWidth, Options};
+///
+/// let options = Options::new(4).initial_indent("-> ");
+/// assert_eq!(wrap("foo bar", &options), vec!["-> ", "foo", "bar"]);
+///
+/// let options = options.min_width(MinWidth::Clamp(3));
+/// assert_eq!(wrap("foo bar", &options), vec!["-> foo", "bar"]);
+///
+/// let options = options.min_width(MinWidth::Error(3));
+/// assert_eq!(try_wrap("foo bar", &options), Err(Error::WidthTooSmall));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MinWidth {
+    /// Wrap the text no matter how little room there is.
+    #[default]
+    Proceed,
+    /// Widen the text so that there is room for at least this many
+    /// columns after the indentation. The lines will then be wider
+    /// than [`Options::width`](crate::Options::width), but never
+    /// wider than [`Options::max_width`](crate::Options::max_width).
+    Clamp(usize),
+    /// Return [`Error::WidthTooSmall`](crate::Error::WidthTooSmall)
+    /// if there is room for fewer than this many columns after the
+    /// indentation. The functions which cannot return an error, such
+    /// as [`wrap()`](crate::wrap()), wrap the text anyway like with
+    /// [`MinWidth::Proceed`].
+    Error(usize),
+}
@@ -0,0 +1,130 @@
+//! Unicode normalization of the input text.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// Describes if text is normalized to [Unicode Normalization Form
+/// C][nfc] (NFC) when wrapping.
+///
+/// Accented letters can be written with a single precomposed
+/// character, such as `'é'`, or with a base letter followed by a
+/// combining accent, such as `"e\u{301}"`. Some terminals and some
+/// width computations treat the two forms differently. Normalizing
+/// the text to NFC turns the decomposed form into the precomposed
+/// form, so that both are measured the same way.
+///
+/// Without the `unicode-normalization` Cargo feature, only
+/// [`Normalization::Keep`] is available.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unicode-normalization")] {
+/// use textwrap::{wrap, Normalization, Options};
+///
+/// let options = Options::new(10).normalization(Normalization::Nfc);
+/// assert_eq!(wrap("Cafe\u{301} au lait", &options), vec!["Caf\u{e9} au", "lait"]);
+/// # }
+/// ```
+///
+/// Offsets in [`Options::protected_ranges`] refer to the text before
+/// it is normalized, they are moved along with the normalized text.
+///
+/// [nfc]: https://unicode.org/reports/tr15/
+/// [`Options::protected_ranges`]: crate::Options::protected_ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Normalization {
+    /// Use the text as-is.
+    #[default]
+    Keep,
+    /// Measure the width of each word in its NFC form, but return
+    /// the text unchanged.
+    ///
+    /// **Note:** Only available when the `unicode-normalization`
+    /// Cargo feature is enabled.
+    #[cfg(feature = "unicode-normalization")]
+    MeasureNfc,
+    /// Normalize the text to NFC before wrapping. The wrapped lines
+    /// are in NFC as well.
+    ///
+    /// **Note:** Only available when the `unicode-normalization`
+    /// Cargo feature is enabled.
+    #[cfg(feature = "unicode-normalization")]
+    Nfc,
+}
+
+impl Normalization {
+    /// Apply this normalization to `text`.
+    ///
+    /// Only [`Normalization::Nfc`] changes the text. The text is
+    /// borrowed unchanged when it is already normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::Normalization;
+    ///
+    /// assert_eq!(Normalization::Keep.normalize("e\u{301}"), "e\u{301}");
+    /// #[cfg(feature = "unicode-normalization")]
+    /// assert_eq!(Normalization::Nfc.normalize("e\u{301}"), "\u{e9}");
+    /// ```
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            #[cfg(feature = "unicode-normalization")]
+            Normalization::Nfc if !is_nfc(text) => Cow::Owned(text.nfc().collect()),
+            _ => Cow::Borrowed(text),
+        }
+    }
+
+    /// The form of `word` used for measuring its width with this
+    /// normalization.
+    pub(crate) fn measured<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        match self {
+            #[cfg(feature = "unicode-normalization")]
+            Normalization::MeasureNfc if !is_nfc(word) => Cow::Owned(word.nfc().collect()),
+            _ => Cow::Borrowed(word),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "unicode-normalization")]
+mod tests {
+    use super::*;
+    use crate::core::display_width;
+
+    #[test]
+    fn normalize_borrows_nfc_text() {
+        assert!(matches!(
+            Normalization::Nfc.normalize("Caf\u{e9}"),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            Normalization::MeasureNfc.normalize("e\u{301}"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn protected_ranges_move_with_normalized_text() {
+        let text = "e\u{301} see https://example.com/path now";
+        let url = 8..32;
+        assert_eq!(&text[url.clone()], "https://example.com/path");
+        let options = crate::Options::new(10)
+            .normalization(Normalization::Nfc)
+            .protected_ranges(std::slice::from_ref(&url));
+        assert_eq!(
+            crate::wrap(text, &options),
+            vec!["\u{e9} see", "https://example.com/path", "now"]
+        );
+    }
+
+    #[test]
+    fn measure_nfc_width() {
+        let measured = Normalization::MeasureNfc.measured("\u{1b}[1me\u{301}\u{1b}[0m");
+        assert_eq!(display_width(&measured), 1);
+    }
+}
@@ -1,25 +1,108 @@
 //! Options for wrapping text.
 
-use crate::{LineEnding, WordSeparator, WordSplitter, WrapAlgorithm};
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+use crate::core::{max_indent_width, Word, DEFAULT_TAB_WIDTH};
+use crate::{
+    BreakCandidate, Compact, ControlChars, Error, LineEnding, MinWidth, Normalization,
+    SentenceSpacing, WordOverflow, WordSeparator, WordSplitter, WrapAlgorithm,
+};
 
 /// Holds configuration options for wrapping and filling text.
+///
+/// The customization points are function pointers and enums rather
+/// than trait objects. This makes `Options` both `Send` and `Sync`,
+/// so you can store it in a `static` or share it between threads:
+///
+/// ```
+/// use textwrap::{fill, Options};
+///
+/// static OPTIONS: Options<'static> = Options::new(10);
+///
+/// let filled = std::thread::spawn(|| fill("Shared between threads", &OPTIONS));
+/// assert_eq!(filled.join().unwrap(), "Shared\nbetween\nthreads");
+/// ```
+///
+/// A hyphenation dictionary can be shared cheaply between threads
+/// with `WordSplitter::SharedHyphenation`.
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Options<'a> {
     /// The width in columns at which the text will be wrapped.
     pub width: usize,
-    /// Line ending used for breaking lines.
+    /// Upper limit for [`self.width`](#structfield.width). See the
+    /// [`Options::max_width`] method.
+    pub max_width: usize,
+    /// Number of columns reserved at the end of the very last line.
+    /// See the [`Options::last_line_reserve`] method.
+    pub last_line_reserve: usize,
+    /// Width of short last lines and the allowed overflow when they
+    /// are merged into the previous line. See the
+    /// [`Options::compact`] method.
+    pub compact: Compact,
+    /// What to do when the width leaves too little room for the
+    /// text. See the [`Options::min_width`] method.
+    pub min_width: MinWidth,
+    /// Line ending used for breaking lines in the output. The input
+    /// is always split at both `"\n"` and `"\r\n"`.
     pub line_ending: LineEnding,
+    /// Recognize U+2028 and U+2029 as line and paragraph breaks in
+    /// the input. See the [`Options::unicode_separators`] method.
+    pub unicode_separators: bool,
+    /// Treat U+2003 EM SPACE and U+3000 IDEOGRAPHIC SPACE as
+    /// breakable whitespace. See the [`Options::wide_spaces`] method.
+    pub wide_spaces: bool,
     /// Indentation used for the first line of output. See the
     /// [`Options::initial_indent`] method.
-    pub initial_indent: &'a str,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub initial_indent: Cow<'a, str>,
     /// Indentation used for subsequent lines of output. See the
     /// [`Options::subsequent_indent`] method.
-    pub subsequent_indent: &'a str,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub subsequent_indent: Cow<'a, str>,
+    /// Indentation used for each line of output, with the last entry
+    /// used for the remaining lines. When this is not empty, it is
+    /// used instead of `self.initial_indent` and
+    /// `self.subsequent_indent`. See the [`Options::indents`] method.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub indents: &'a [&'a str],
+    /// Visible width of the indentation, which is used instead of
+    /// measuring the indents. See the [`Options::gutter`] method.
+    pub indent_width: Option<usize>,
+    /// Distance between tab stops, used when measuring tabs in the
+    /// indentation. See the [`Options::tab_width`] method.
+    pub tab_width: usize,
     /// Allow long words to be broken if they cannot fit on a line.
     /// When set to `false`, some lines may be longer than
     /// `self.width`. See the [`Options::break_words`] method.
     pub break_words: bool,
+    /// What to do with words longer than `self.width`. When set to
+    /// [`WordOverflow::Truncate`], long words are truncated and
+    /// `self.break_words` is ignored. Otherwise `self.break_words`
+    /// decides if long words are broken, even if this field says
+    /// differently. See the [`Options::word_overflow`] method.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub word_overflow: WordOverflow<'a>,
+    /// Function deciding which long words can be broken. See the
+    /// [`Options::break_words_if`] method. Function pointers are not
+    /// compared, so options with this set never compare equal.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub break_words_if: Option<fn(&Word<'_>) -> bool>,
+    /// Padding added to lines which are one column short because
+    /// the next character is double-width. See the
+    /// [`Options::wide_char_padding`] method.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub wide_char_padding: Cow<'a, str>,
+    /// Drop the whitespace where lines are broken. When set to
+    /// `false`, the whitespace is kept at the end of the lines. See
+    /// the [`Options::drop_whitespace`] method.
+    pub drop_whitespace: bool,
+    /// Keep all but one space of the whitespace where lines are
+    /// broken. See the [`Options::preserve_inner_whitespace`] method.
+    pub preserve_inner_whitespace: bool,
     /// Wrapping algorithm to use, see the implementations of the
     /// [`WrapAlgorithm`] trait for details.
     pub wrap_algorithm: WrapAlgorithm,
@@ -30,20 +113,297 @@ pub struct Options<'a> {
     /// splitting words on hyphens, or it can be used to implement
     /// language-aware machine hyphenation.
     pub word_splitter: WordSplitter,
+    /// String inserted at the end of a line when a word is split
+    /// there. See the [`Options::hyphen`] method.
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_hyphen"))]
+    pub hyphen: &'static str,
+    /// Minimum number of characters before and after a split point
+    /// found by the word splitter. See the
+    /// [`Options::min_fragment_lengths`] method.
+    pub min_fragment_lengths: (usize, usize),
+    /// Function deciding which words are hyphenated using a
+    /// dictionary. See the [`Options::hyphenation_filter`] method.
+    /// Function pointers are not compared, so options with this set
+    /// never compare equal.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub hyphenation_filter: Option<fn(&str) -> bool>,
+    /// How control characters in the text are handled. See the
+    /// [`Options::sanitize_control_chars`] method.
+    pub sanitize_control_chars: ControlChars,
+    /// Unicode normalization applied to the text. See the
+    /// [`Options::normalization`] method.
+    pub normalization: Normalization,
+    /// Function used to reject break points found by the word
+    /// separator and the word splitter. See the
+    /// [`Options::break_filter`] method. Function pointers are not
+    /// compared, so options with this set never compare equal.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub break_filter: Option<fn(&BreakCandidate<'_>) -> bool>,
+    /// Characters which must not start a line. See the
+    /// [`Options::no_break_before`] method.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub no_break_before: Cow<'a, str>,
+    /// Characters which must not end a line. See the
+    /// [`Options::no_break_after`] method.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub no_break_after: Cow<'a, str>,
+    /// Markers for hard line breaks, which are kept by [`refill()`].
+    /// See the [`Options::hard_break_markers`] method.
+    ///
+    /// [`refill()`]: crate::refill()
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub hard_break_markers: &'a [&'a str],
+    /// Spacing between sentences used by [`refill()`]. See the
+    /// [`Options::sentence_spacing`] method.
+    ///
+    /// [`refill()`]: crate::refill()
+    pub sentence_spacing: SentenceSpacing,
+    /// Byte ranges of the text which must not be broken. See the
+    /// [`Options::protected_ranges`] method.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub protected_ranges: &'a [Range<usize>],
+    /// Character widths which replace the computed widths. See the
+    /// [`Options::width_overrides`] method.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub width_overrides: &'a [(char, usize)],
+    /// Set by [`Options::line_ending`] so that
+    /// [`refill()`](crate::refill()) knows if the line ending was
+    /// given explicitly.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    line_ending_set: bool,
+}
+
+/// The [`Options::hyphen`] used when deserializing options.
+#[cfg(feature = "serde")]
+fn default_hyphen() -> &'static str {
+    "-"
 }
 
 impl<'a> From<&'a Options<'a>> for Options<'a> {
     fn from(options: &'a Options<'a>) -> Self {
         Self {
             width: options.width,
+            max_width: options.max_width,
+            last_line_reserve: options.last_line_reserve,
+            compact: options.compact,
+            min_width: options.min_width,
             line_ending: options.line_ending,
-            initial_indent: options.initial_indent,
-            subsequent_indent: options.subsequent_indent,
+            unicode_separators: options.unicode_separators,
+            wide_spaces: options.wide_spaces,
+            initial_indent: options.initial_indent.clone(),
+            subsequent_indent: options.subsequent_indent.clone(),
+            indents: options.indents,
+            indent_width: options.indent_width,
+            tab_width: options.tab_width,
             break_words: options.break_words,
+            word_overflow: options.word_overflow,
+            break_words_if: options.break_words_if,
+            wide_char_padding: options.wide_char_padding.clone(),
+            drop_whitespace: options.drop_whitespace,
+            preserve_inner_whitespace: options.preserve_inner_whitespace,
             word_separator: options.word_separator,
             wrap_algorithm: options.wrap_algorithm,
             word_splitter: options.word_splitter.clone(),
+            hyphen: options.hyphen,
+            min_fragment_lengths: options.min_fragment_lengths,
+            hyphenation_filter: options.hyphenation_filter,
+            sanitize_control_chars: options.sanitize_control_chars,
+            normalization: options.normalization,
+            break_filter: options.break_filter,
+            no_break_before: options.no_break_before.clone(),
+            no_break_after: options.no_break_after.clone(),
+            hard_break_markers: options.hard_break_markers,
+            sentence_spacing: options.sentence_spacing,
+            protected_ranges: options.protected_ranges,
+            width_overrides: options.width_overrides,
+            line_ending_set: options.line_ending_set,
+        }
+    }
+}
+
+impl std::fmt::Debug for Options<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Deriving `Debug` does not work for function pointers which
+        // take references with older compilers, and the pointers are
+        // not useful to print anyway.
+        struct FnPointer(bool);
+
+        impl std::fmt::Debug for FnPointer {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(if self.0 { "Some(..)" } else { "None" })
+            }
         }
+
+        f.debug_struct("Options")
+            .field("width", &self.width)
+            .field("max_width", &self.max_width)
+            .field("last_line_reserve", &self.last_line_reserve)
+            .field("compact", &self.compact)
+            .field("min_width", &self.min_width)
+            .field("line_ending", &self.line_ending)
+            .field("unicode_separators", &self.unicode_separators)
+            .field("wide_spaces", &self.wide_spaces)
+            .field("initial_indent", &self.initial_indent)
+            .field("subsequent_indent", &self.subsequent_indent)
+            .field("indents", &self.indents)
+            .field("indent_width", &self.indent_width)
+            .field("tab_width", &self.tab_width)
+            .field("break_words", &self.break_words)
+            .field("word_overflow", &self.word_overflow)
+            .field("break_words_if", &FnPointer(self.break_words_if.is_some()))
+            .field("wide_char_padding", &self.wide_char_padding)
+            .field("drop_whitespace", &self.drop_whitespace)
+            .field("preserve_inner_whitespace", &self.preserve_inner_whitespace)
+            .field("wrap_algorithm", &self.wrap_algorithm)
+            .field("word_separator", &self.word_separator)
+            .field("word_splitter", &self.word_splitter)
+            .field("hyphen", &self.hyphen)
+            .field("min_fragment_lengths", &self.min_fragment_lengths)
+            .field(
+                "hyphenation_filter",
+                &FnPointer(self.hyphenation_filter.is_some()),
+            )
+            .field("sanitize_control_chars", &self.sanitize_control_chars)
+            .field("normalization", &self.normalization)
+            .field("break_filter", &FnPointer(self.break_filter.is_some()))
+            .field("no_break_before", &self.no_break_before)
+            .field("no_break_after", &self.no_break_after)
+            .field("hard_break_markers", &self.hard_break_markers)
+            .field("sentence_spacing", &self.sentence_spacing)
+            .field("protected_ranges", &self.protected_ranges)
+            .field("width_overrides", &self.width_overrides)
+            .finish()
+    }
+}
+
+impl PartialEq for Options<'_> {
+    /// Compare two options.
+    ///
+    /// Options compare equal when all their fields are equal and the
+    /// line ending was either given explicitly in both or left at its
+    /// default in both. This matters since an explicit line ending
+    /// changes the output of functions such as [`refill()`]. Like
+    /// the `Custom` variants of [`WrapAlgorithm`], [`WordSeparator`],
+    /// and [`WordSplitter`], a [`break_filter`], a
+    /// [`hyphenation_filter`], or a [`break_words_if`] predicate
+    /// never compares equal, not even to itself:
+    ///
+    /// ```
+    /// use textwrap::Options;
+    ///
+    /// assert_eq!(Options::new(80).initial_indent("* "),
+    ///            Options::new(80).initial_indent("* "));
+    ///
+    /// let options = Options::new(80).break_filter(|_| true);
+    /// assert_ne!(options, options);
+    /// ```
+    ///
+    /// This is why `Options` implements [`Hash`] but not [`Eq`]. Use
+    /// [`Options::cache_key()`] if you need to store options in a
+    /// `HashMap`.
+    ///
+    /// [`break_filter`]: Options::break_filter
+    /// [`hyphenation_filter`]: Options::hyphenation_filter
+    /// [`break_words_if`]: Options::break_words_if
+    /// [`refill()`]: crate::refill
+    fn eq(&self, other: &Self) -> bool {
+        // Function pointers are deliberately not compared: the same
+        // function can have several addresses and different functions
+        // can share one, so only "both unset" counts as equal.
+        self.width == other.width
+            && self.max_width == other.max_width
+            && self.last_line_reserve == other.last_line_reserve
+            && self.compact == other.compact
+            && self.min_width == other.min_width
+            && self.line_ending == other.line_ending
+            && self.has_explicit_line_ending() == other.has_explicit_line_ending()
+            && self.unicode_separators == other.unicode_separators
+            && self.wide_spaces == other.wide_spaces
+            && self.initial_indent == other.initial_indent
+            && self.subsequent_indent == other.subsequent_indent
+            && self.indents == other.indents
+            && self.indent_width == other.indent_width
+            && self.tab_width == other.tab_width
+            && self.break_words == other.break_words
+            && self.word_overflow == other.word_overflow
+            && self.break_words_if.is_none()
+            && other.break_words_if.is_none()
+            && self.wide_char_padding == other.wide_char_padding
+            && self.drop_whitespace == other.drop_whitespace
+            && self.preserve_inner_whitespace == other.preserve_inner_whitespace
+            && self.wrap_algorithm == other.wrap_algorithm
+            && self.word_separator == other.word_separator
+            && self.word_splitter == other.word_splitter
+            && self.hyphen == other.hyphen
+            && self.min_fragment_lengths == other.min_fragment_lengths
+            && self.hyphenation_filter.is_none()
+            && other.hyphenation_filter.is_none()
+            && self.sanitize_control_chars == other.sanitize_control_chars
+            && self.normalization == other.normalization
+            && self.break_filter.is_none()
+            && other.break_filter.is_none()
+            && self.no_break_before == other.no_break_before
+            && self.no_break_after == other.no_break_after
+            && self.hard_break_markers == other.hard_break_markers
+            && self.sentence_spacing == other.sentence_spacing
+            && self.protected_ranges == other.protected_ranges
+            && self.width_overrides == other.width_overrides
+    }
+}
+
+impl Hash for Options<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.max_width.hash(state);
+        self.last_line_reserve.hash(state);
+        self.compact.hash(state);
+        self.min_width.hash(state);
+        self.line_ending.hash(state);
+        self.has_explicit_line_ending().hash(state);
+        self.unicode_separators.hash(state);
+        self.wide_spaces.hash(state);
+        self.initial_indent.hash(state);
+        self.subsequent_indent.hash(state);
+        self.indents.hash(state);
+        self.indent_width.hash(state);
+        self.tab_width.hash(state);
+        self.break_words.hash(state);
+        self.word_overflow.hash(state);
+        self.break_words_if.is_some().hash(state);
+        self.wide_char_padding.hash(state);
+        self.drop_whitespace.hash(state);
+        self.preserve_inner_whitespace.hash(state);
+        self.wrap_algorithm.hash(state);
+        self.word_separator.hash(state);
+        self.word_splitter.hash(state);
+        self.hyphen.hash(state);
+        self.min_fragment_lengths.hash(state);
+        self.hyphenation_filter.is_some().hash(state);
+        self.sanitize_control_chars.hash(state);
+        self.normalization.hash(state);
+        self.break_filter.is_some().hash(state);
+        self.no_break_before.hash(state);
+        self.no_break_after.hash(state);
+        self.hard_break_markers.hash(state);
+        self.sentence_spacing.hash(state);
+        self.protected_ranges.hash(state);
+        self.width_overrides.hash(state);
+    }
+}
+
+/// Hashable key for [`Options`], created by [`Options::cache_key()`].
+///
+/// Unlike `Options`, this implements [`Eq`], which makes it suitable
+/// as a key in a `HashMap` or `HashSet`.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct OptionsKey<'a>(Options<'a>);
+
+impl Eq for OptionsKey<'_> {}
+
+impl<'a> OptionsKey<'a> {
+    /// The options used to create this key.
+    pub fn options(&self) -> &Options<'a> {
+        &self.0
     }
 }
 
@@ -59,13 +419,27 @@ impl<'a> Options<'a> {
     /// The other fields are given default values as follows:
     ///
     /// ```
-    /// # use textwrap::{LineEnding, Options, WordSplitter, WordSeparator, WrapAlgorithm};
+    /// # use textwrap::{Compact, ControlChars, LineEnding, MinWidth, Normalization, Options, SentenceSpacing, WordOverflow, WordSplitter, WordSeparator, WrapAlgorithm};
     /// # let width = 80;
     /// let options = Options::new(width);
+    /// assert_eq!(options.max_width, usize::MAX);
+    /// assert_eq!(options.last_line_reserve, 0);
+    /// assert_eq!(options.compact, Compact::default());
+    /// assert_eq!(options.min_width, MinWidth::Proceed);
     /// assert_eq!(options.line_ending, LineEnding::LF);
+    /// assert_eq!(options.unicode_separators, false);
+    /// assert_eq!(options.wide_spaces, false);
     /// assert_eq!(options.initial_indent, "");
     /// assert_eq!(options.subsequent_indent, "");
+    /// assert!(options.indents.is_empty());
+    /// assert_eq!(options.indent_width, None);
+    /// assert_eq!(options.tab_width, 8);
     /// assert_eq!(options.break_words, true);
+    /// assert_eq!(options.word_overflow, WordOverflow::Break);
+    /// assert!(options.break_words_if.is_none());
+    /// assert_eq!(options.wide_char_padding, "");
+    /// assert_eq!(options.drop_whitespace, true);
+    /// assert_eq!(options.preserve_inner_whitespace, false);
     ///
     /// #[cfg(feature = "unicode-linebreak")]
     /// assert_eq!(options.word_separator, WordSeparator::UnicodeBreakProperties);
@@ -78,6 +452,18 @@ impl<'a> Options<'a> {
     /// assert_eq!(options.wrap_algorithm, WrapAlgorithm::FirstFit);
     ///
     /// assert_eq!(options.word_splitter, WordSplitter::HyphenSplitter);
+    /// assert_eq!(options.hyphen, "-");
+    /// assert_eq!(options.min_fragment_lengths, (1, 1));
+    /// assert!(options.hyphenation_filter.is_none());
+    /// assert_eq!(options.sanitize_control_chars, ControlChars::Keep);
+    /// assert_eq!(options.normalization, Normalization::Keep);
+    /// assert!(options.break_filter.is_none());
+    /// assert_eq!(options.no_break_before, "");
+    /// assert_eq!(options.no_break_after, "");
+    /// assert!(options.hard_break_markers.is_empty());
+    /// assert_eq!(options.sentence_spacing, SentenceSpacing::Keep);
+    /// assert!(options.protected_ranges.is_empty());
+    /// assert!(options.width_overrides.is_empty());
     /// ```
     ///
     /// Note that the default word separator and wrap algorithms
@@ -86,19 +472,163 @@ impl<'a> Options<'a> {
     pub const fn new(width: usize) -> Self {
         Options {
             width,
+            max_width: usize::MAX,
+            last_line_reserve: 0,
+            compact: Compact::new(0, 0),
+            min_width: MinWidth::Proceed,
             line_ending: LineEnding::LF,
-            initial_indent: "",
-            subsequent_indent: "",
+            unicode_separators: false,
+            wide_spaces: false,
+            initial_indent: Cow::Borrowed(""),
+            subsequent_indent: Cow::Borrowed(""),
+            indents: &[],
+            indent_width: None,
+            tab_width: DEFAULT_TAB_WIDTH,
             break_words: true,
+            word_overflow: WordOverflow::Break,
+            break_words_if: None,
+            wide_char_padding: Cow::Borrowed(""),
+            drop_whitespace: true,
+            preserve_inner_whitespace: false,
             word_separator: WordSeparator::new(),
             wrap_algorithm: WrapAlgorithm::new(),
             word_splitter: WordSplitter::HyphenSplitter,
+            hyphen: "-",
+            min_fragment_lengths: (1, 1),
+            hyphenation_filter: None,
+            sanitize_control_chars: ControlChars::Keep,
+            normalization: Normalization::Keep,
+            break_filter: None,
+            no_break_before: Cow::Borrowed(""),
+            no_break_after: Cow::Borrowed(""),
+            hard_break_markers: &[],
+            sentence_spacing: SentenceSpacing::Keep,
+            protected_ranges: &[],
+            width_overrides: &[],
+            line_ending_set: false,
         }
     }
 
+    /// Creates a new [`Options`] suitable for plain text email. This
+    /// is a preset for mail clients and similar tools.
+    ///
+    /// Words are only found at ASCII spaces, long words are not
+    /// broken, and words are not split at hyphens. This keeps URLs
+    /// and email addresses intact so that they remain clickable, at
+    /// the cost of the occasional line which is longer than `width`.
+    /// A `width` of 72 columns is customary.
+    ///
+    /// Equivalent to:
+    ///
+    /// ```
+    /// use textwrap::{Options, WordSeparator, WordSplitter};
+    /// # let width = 72;
+    ///
+    /// let options = Options::new(width)
+    ///     .break_words(false)
+    ///     .word_separator(WordSeparator::AsciiSpace)
+    ///     .word_splitter(WordSplitter::NoHyphenation);
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let text = "See https://docs.rs/textwrap/ for the full details.";
+    /// assert_eq!(wrap(text, Options::for_email(20)),
+    ///            vec!["See",
+    ///                 "https://docs.rs/textwrap/",
+    ///                 "for the full",
+    ///                 "details."]);
+    /// ```
+    pub fn for_email(width: usize) -> Self {
+        Options::new(width)
+            .break_words(false)
+            .word_separator(WordSeparator::AsciiSpace)
+            .word_splitter(WordSplitter::NoHyphenation)
+    }
+
+    /// Creates a new [`Options`] suitable for the body of a Git
+    /// commit message. This follows the common 50/72 convention:
+    /// the subject line is at most 50 columns and the body is
+    /// wrapped at 72 columns.
+    ///
+    /// The options are meant for the body of the message: the subject
+    /// line should never be wrapped, so wrap the body separately.
+    /// Like [`Options::for_email`], long words are not broken and
+    /// words are not split at hyphens, which keeps URLs, file paths,
+    /// and commit hashes intact.
+    ///
+    /// The subject width is available as
+    /// [`Options::COMMIT_SUBJECT_WIDTH`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{fill, Options};
+    ///
+    /// let body = "Wrap the body of commit messages at 72 columns so that \
+    ///             it looks good in `git log` even on narrow terminals.";
+    /// assert_eq!(fill(body, Options::for_commit_message()),
+    ///            "Wrap the body of commit messages at 72 columns so that it looks good in\n\
+    ///             `git log` even on narrow terminals.");
+    /// ```
+    pub fn for_commit_message() -> Self {
+        Options::for_email(72)
+    }
+
+    /// Maximum width of the subject line of a commit message. See
+    /// [`Options::for_commit_message`].
+    pub const COMMIT_SUBJECT_WIDTH: usize = 50;
+
+    /// Creates a new [`Options`] suitable for text formatted like a
+    /// man page. This is a preset for `--help` output and similar
+    /// documentation.
+    ///
+    /// Man pages are traditionally formatted for 80 columns with the
+    /// body text indented by 7 spaces.
+    ///
+    /// Equivalent to:
+    ///
+    /// ```
+    /// use textwrap::Options;
+    ///
+    /// let options = Options::new(80)
+    ///     .initial_indent("       ")
+    ///     .subsequent_indent("       ");
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let options = Options::for_man_page().width(30);
+    /// assert_eq!(wrap("Display the manual page for a command.", options),
+    ///            vec!["       Display the manual page",
+    ///                 "       for a command."]);
+    /// ```
+    pub fn for_man_page() -> Self {
+        Options::new(80)
+            .initial_indent("       ")
+            .subsequent_indent("       ")
+    }
+
     /// Change [`self.line_ending`]. This specifies which of the
     /// supported line endings should be used to break the lines of the
-    /// input text.
+    /// output text.
+    ///
+    /// The line ending only affects the output. The input text is
+    /// always split on both `"\r\n"` and `"\n"`, whatever this is
+    /// set to, so text with mixed line endings is normalized when it
+    /// is filled. A lone `'\r'` does not end a line. See
+    /// [`LineEnding::normalize`] if you need to normalize text
+    /// without wrapping it.
+    ///
+    /// A line ending set with this method is used by
+    /// [`refill()`](crate::refill()) instead of the line ending found
+    /// in the text.
     ///
     /// # Examples
     ///
@@ -114,15 +644,269 @@ impl<'a> Options<'a> {
     pub fn line_ending(self, line_ending: LineEnding) -> Self {
         Options {
             line_ending,
+            line_ending_set: true,
+            ..self
+        }
+    }
+
+    /// Check if the line ending was given explicitly, either with
+    /// [`Options::line_ending()`] or by assigning a non-default
+    /// value to [`self.line_ending`].
+    ///
+    /// [`self.line_ending`]: #structfield.line_ending
+    pub(crate) fn has_explicit_line_ending(&self) -> bool {
+        self.line_ending_set || self.line_ending != LineEnding::LF
+    }
+
+    /// Change [`self.unicode_separators`]. When enabled, U+2028 LINE
+    /// SEPARATOR in the input is treated like `"\n"` and U+2029
+    /// PARAGRAPH SEPARATOR is treated like `"\n\n"`, i.e., as the
+    /// end of a paragraph. The separators are common in text which
+    /// has been transported as JSON.
+    ///
+    /// The output always uses [`self.line_ending`]. Offsets in
+    /// [`self.protected_ranges`] refer to the text with the
+    /// separators, they are moved when the separators are replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{fill, Options};
+    ///
+    /// let text = "First line\u{2028}Second line\u{2029}Next paragraph";
+    /// let options = Options::new(15).unicode_separators(true);
+    /// assert_eq!(fill(text, &options),
+    ///            "First line\nSecond line\n\nNext paragraph");
+    /// ```
+    ///
+    /// [`self.unicode_separators`]: #structfield.unicode_separators
+    /// [`self.line_ending`]: #structfield.line_ending
+    /// [`self.protected_ranges`]: #structfield.protected_ranges
+    pub fn unicode_separators(self, unicode_separators: bool) -> Self {
+        Options {
+            unicode_separators,
+            ..self
+        }
+    }
+
+    /// Change [`self.wide_spaces`]. When enabled, U+2003 EM SPACE
+    /// and U+3000 IDEOGRAPHIC SPACE separate words like `' '`, also
+    /// with lightweight separators such as
+    /// [`WordSeparator::AsciiSpace`]. The spaces are dropped at the
+    /// end of lines, so Chinese and Japanese text does not keep
+    /// full-width spaces hanging at the line ends. This is disabled
+    /// by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator};
+    ///
+    /// // The long word is broken apart in the middle of the spaces.
+    /// let options = Options::new(10).word_separator(WordSeparator::AsciiSpace);
+    /// assert_eq!(wrap("東京\u{3000}大阪\u{3000}京都", &options),
+    ///            vec!["東京\u{3000}大阪", "\u{3000}京都"]);
+    ///
+    /// let options = options.wide_spaces(true);
+    /// assert_eq!(wrap("東京\u{3000}大阪\u{3000}京都", &options),
+    ///            vec!["東京\u{3000}大阪", "京都"]);
+    /// ```
+    ///
+    /// [`self.wide_spaces`]: #structfield.wide_spaces
+    pub fn wide_spaces(self, wide_spaces: bool) -> Self {
+        Options {
+            wide_spaces,
             ..self
         }
     }
 
     /// Set [`self.width`] to the given value.
     ///
+    /// The width is clamped to [`self.max_width`].
+    ///
     /// [`self.width`]: #structfield.width
+    /// [`self.max_width`]: #structfield.max_width
     pub fn width(self, width: usize) -> Self {
-        Options { width, ..self }
+        Options {
+            width: std::cmp::min(width, self.max_width),
+            ..self
+        }
+    }
+
+    /// Change [`self.max_width`]. This limits the current width as
+    /// well as later changes made with [`Options::width`].
+    ///
+    /// Very long lines are hard to read, so this is useful when the
+    /// width comes from somewhere outside your control, such as the
+    /// width of a huge terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::Options;
+    ///
+    /// let options = Options::new(300).max_width(100);
+    /// assert_eq!(options.width, 100);
+    ///
+    /// let options = options.width(120);
+    /// assert_eq!(options.width, 100);
+    ///
+    /// let options = options.width(60);
+    /// assert_eq!(options.width, 60);
+    /// ```
+    ///
+    /// [`self.max_width`]: #structfield.max_width
+    pub fn max_width(self, max_width: usize) -> Self {
+        Options {
+            width: std::cmp::min(self.width, max_width),
+            max_width,
+            ..self
+        }
+    }
+
+    /// Change [`self.last_line_reserve`]. This reserves room for a
+    /// suffix such as “… read more” at the end of the very last line
+    /// of output.
+    ///
+    /// The last line is wrapped to be `last_line_reserve` columns
+    /// narrower than the other lines. This is done after wrapping
+    /// the text normally: if the last line is too long, its trailing
+    /// words are moved to a new line, which is indented like the
+    /// other subsequent lines. The last line is kept if not even its
+    /// last word would leave room on the new line. The other lines
+    /// are not affected. Long words are not broken further because
+    /// of the reserved room.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{fill, Options, WrapAlgorithm};
+    ///
+    /// let options = Options::new(15).wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(fill("Memory safety without a GC", &options),
+    ///            "Memory safety\nwithout a GC");
+    ///
+    /// let options = options.last_line_reserve(5);
+    /// let mut text = fill("Memory safety without a GC", &options);
+    /// text.push_str(" more");
+    /// assert_eq!(text, "Memory safety\nwithout\na GC more");
+    /// ```
+    ///
+    /// [`self.last_line_reserve`]: #structfield.last_line_reserve
+    pub fn last_line_reserve(self, last_line_reserve: usize) -> Self {
+        Options {
+            last_line_reserve,
+            ..self
+        }
+    }
+
+    /// Change [`self.compact`]. This merges a short last line of a
+    /// paragraph into the previous line, which is useful in small
+    /// user interfaces where a line which is slightly too long is
+    /// better than an extra line.
+    ///
+    /// A last line which is narrower than [`Compact::short_width`]
+    /// columns is merged into the previous line if the merged line
+    /// is at most [`Compact::overflow`] columns wider than the line
+    /// width. The default never merges lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Compact, Options, WrapAlgorithm};
+    ///
+    /// let options = Options::new(15).wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(wrap("Compact mode on.", &options),
+    ///            vec!["Compact mode", "on."]);
+    ///
+    /// let options = options.compact(Compact::new(4, 2));
+    /// assert_eq!(wrap("Compact mode on.", &options),
+    ///            vec!["Compact mode on."]);
+    ///
+    /// // Merging would overflow the line by more than 2 columns:
+    /// assert_eq!(wrap("Compact mode is on.", &options),
+    ///            vec!["Compact mode is", "on."]);
+    /// ```
+    ///
+    /// [`self.compact`]: #structfield.compact
+    pub fn compact(self, compact: Compact) -> Self {
+        Options { compact, ..self }
+    }
+
+    /// Change [`self.min_width`]. This controls what happens when
+    /// the width leaves too little room for the text after the
+    /// indentation. See [`MinWidth`] for details.
+    ///
+    /// The policy is applied by [`Options::build`], which is called
+    /// by the functions which wrap text.
+    ///
+    /// [`self.min_width`]: #structfield.min_width
+    pub fn min_width(self, min_width: MinWidth) -> Self {
+        Options { min_width, ..self }
+    }
+
+    /// Validate the options and apply [`self.min_width`].
+    ///
+    /// The width is first limited to [`self.max_width`], which
+    /// matters if [`self.width`] was assigned directly. With
+    /// [`MinWidth::Clamp`], the width is then increased if there is
+    /// too little room for the text after the indentation, but never
+    /// beyond [`self.max_width`]. With [`MinWidth::Error`], an error
+    /// is returned instead.
+    ///
+    /// You do not need to call this before wrapping text, but it
+    /// lets you detect a too narrow width up front, e.g., when a
+    /// window is resized.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::WidthTooSmall`] if [`self.min_width`] is
+    /// [`MinWidth::Error`] and there is too little room for the
+    /// text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{Error, MinWidth, Options};
+    ///
+    /// let options = Options::new(5).subsequent_indent("    ");
+    /// assert_eq!(options.clone().build(), Ok(options.clone()));
+    ///
+    /// let clamped = options.clone().min_width(MinWidth::Clamp(10)).build().unwrap();
+    /// assert_eq!(clamped.width, 14);
+    ///
+    /// let result = options.min_width(MinWidth::Error(10)).build();
+    /// assert_eq!(result, Err(Error::WidthTooSmall));
+    /// ```
+    ///
+    /// [`self.width`]: #structfield.width
+    /// [`self.min_width`]: #structfield.min_width
+    /// [`self.max_width`]: #structfield.max_width
+    pub fn build(self) -> Result<Self, Error> {
+        let width = std::cmp::min(self.width, self.max_width);
+        let indent_width = max_indent_width(&self);
+        let room = width.saturating_sub(indent_width);
+        match self.min_width {
+            MinWidth::Clamp(min_width) if room < min_width => Ok(Options {
+                width: std::cmp::min(indent_width.saturating_add(min_width), self.max_width),
+                ..self
+            }),
+            MinWidth::Error(min_width) if room < min_width => Err(Error::WidthTooSmall),
+            _ => Ok(Options { width, ..self }),
+        }
+    }
+
+    /// Replace [`MinWidth::Error`] with [`MinWidth::Proceed`]. The
+    /// functions which cannot return an error, such as
+    /// [`wrap()`](crate::wrap()), use this to wrap the text anyway.
+    pub(crate) fn proceed_on_min_width_error(self) -> Self {
+        match self.min_width {
+            MinWidth::Error(_) => Options {
+                min_width: MinWidth::Proceed,
+                ..self
+            },
+            _ => self,
+        }
     }
 
     /// Change [`self.initial_indent`]. The initial indentation is
@@ -142,10 +926,25 @@ impl<'a> Options<'a> {
     ///                 "little example."]);
     /// ```
     ///
+    /// The indentation can be borrowed or owned, which is convenient
+    /// when it is computed at runtime:
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let depth = 2;
+    /// let options = Options::new(16).initial_indent(" ".repeat(4 * depth));
+    /// assert_eq!(wrap("Indented text", options),
+    ///            vec!["        Indented",
+    ///                 "text"]);
+    /// ```
+    ///
     /// [`self.initial_indent`]: #structfield.initial_indent
-    pub fn initial_indent(self, initial_indent: &'a str) -> Self {
+    pub fn initial_indent(self, initial_indent: impl Into<Cow<'a, str>>) -> Self {
         Options {
-            initial_indent,
+            initial_indent: initial_indent.into(),
+            indents: &[],
+            indent_width: None,
             ..self
         }
     }
@@ -179,13 +978,130 @@ impl<'a> Options<'a> {
     /// ```
     ///
     /// [`self.subsequent_indent`]: #structfield.subsequent_indent
-    pub fn subsequent_indent(self, subsequent_indent: &'a str) -> Self {
+    pub fn subsequent_indent(self, subsequent_indent: impl Into<Cow<'a, str>>) -> Self {
+        Options {
+            subsequent_indent: subsequent_indent.into(),
+            indents: &[],
+            indent_width: None,
+            ..self
+        }
+    }
+
+    /// Change [`self.indents`]. The first entry is used for the first
+    /// line of output, the second entry for the second line, and so
+    /// on. The last entry is used for the remaining lines.
+    ///
+    /// This generalizes [`Options::initial_indent`] and
+    /// [`Options::subsequent_indent`]: they are set to the first and
+    /// the last entry, and setting one of them clears the indents
+    /// again. An empty slice means that the initial and subsequent
+    /// indentation is used.
+    ///
+    /// # Examples
+    ///
+    /// A chat message where the second line points back to the
+    /// sender:
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let options = Options::new(20).indents(&["user: ", "   └─ ", "      "]);
+    /// assert_eq!(wrap("Hello, is anybody out there?", &options),
+    ///            vec!["user: Hello, is",
+    ///                 "   └─ anybody out",
+    ///                 "      there?"]);
+    /// ```
+    ///
+    /// [`self.indents`]: #structfield.indents
+    pub fn indents(self, indents: &'a [&'a str]) -> Self {
+        Options {
+            initial_indent: Cow::Borrowed(indents.first().copied().unwrap_or("")),
+            subsequent_indent: Cow::Borrowed(indents.last().copied().unwrap_or("")),
+            indents,
+            indent_width: None,
+            ..self
+        }
+    }
+
+    /// The indentation of output line `line`, counting from zero.
+    pub(crate) fn indent_for(&self, line: usize) -> &str {
+        match self.indents.get(line).or(self.indents.last()) {
+            Some(indent) => indent,
+            None if line == 0 => &self.initial_indent,
+            None => &self.subsequent_indent,
+        }
+    }
+
+    /// Use `prefix` as a gutter which is `visible_width` columns
+    /// wide. This sets both [`self.initial_indent`] and
+    /// [`self.subsequent_indent`] to `prefix` and
+    /// [`self.indent_width`] to `visible_width`. Any
+    /// [`self.indents`] are cleared. Setting the indentation again
+    /// with [`Options::initial_indent`], [`Options::subsequent_indent`],
+    /// or [`Options::indents`] clears the `visible_width`.
+    ///
+    /// The width of the indentation is normally measured with
+    /// [`display_width()`], which skips ANSI escape sequences. Use
+    /// this method for prefixes where this gives the wrong result,
+    /// such as prefixes which move the cursor or which contain
+    /// control characters. The `visible_width` is trusted as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WrapAlgorithm};
+    ///
+    /// // Move the cursor 2 columns to the right:
+    /// let gutter = "\x1b[2C";
+    /// let options = Options::new(15)
+    ///     .gutter(gutter, 2)
+    ///     .wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(wrap("Memory safety without GC", &options),
+    ///            vec![format!("{}Memory safety", gutter),
+    ///                 format!("{}without GC", gutter)]);
+    /// ```
+    ///
+    /// [`self.initial_indent`]: #structfield.initial_indent
+    /// [`self.subsequent_indent`]: #structfield.subsequent_indent
+    /// [`self.indent_width`]: #structfield.indent_width
+    /// [`self.indents`]: #structfield.indents
+    /// [`display_width()`]: crate::core::display_width
+    pub fn gutter(self, prefix: impl Into<Cow<'a, str>>, visible_width: usize) -> Self {
+        let prefix = prefix.into();
         Options {
-            subsequent_indent,
+            initial_indent: prefix.clone(),
+            subsequent_indent: prefix,
+            indents: &[],
+            indent_width: Some(visible_width),
             ..self
         }
     }
 
+    /// Change [`self.tab_width`]. A `'\t'` in the indentation
+    /// advances to the next multiple of the tab width, just like in
+    /// a terminal. The default of 8 columns matches most terminals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WrapAlgorithm};
+    ///
+    /// let options = Options::new(10)
+    ///     .subsequent_indent("\t")
+    ///     .wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(wrap("aa bb cc dd ee ff", &options),
+    ///            vec!["aa bb cc", "\tdd", "\tee", "\tff"]);
+    ///
+    /// let options = options.tab_width(4);
+    /// assert_eq!(wrap("aa bb cc dd ee ff", &options),
+    ///            vec!["aa bb cc", "\tdd ee", "\tff"]);
+    /// ```
+    ///
+    /// [`self.tab_width`]: #structfield.tab_width
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        Options { tab_width, ..self }
+    }
+
     /// Change [`self.break_words`]. This controls if words longer
     /// than `self.width` can be broken, or if they will be left
     /// sticking out into the right margin.
@@ -212,44 +1128,240 @@ impl<'a> Options<'a> {
     pub fn break_words(self, break_words: bool) -> Self {
         Options {
             break_words,
+            word_overflow: if break_words {
+                WordOverflow::Break
+            } else {
+                WordOverflow::Overflow
+            },
             ..self
         }
     }
 
-    /// Change [`self.word_separator`].
+    /// Change [`self.break_words_if`]. Long words are only broken if
+    /// the predicate returns `true` for them. Other long words are
+    /// left sticking out into the right margin. This also sets
+    /// [`self.break_words`] to `true`.
     ///
-    /// See the [`WordSeparator`] trait for details on the choices.
+    /// The predicate is only called for words which are wider than
+    /// the line, so it can be used to break, e.g., URLs and hashes
+    /// while keeping normal words intact.
     ///
-    /// [`self.word_separator`]: #structfield.word_separator
-    pub fn word_separator(self, word_separator: WordSeparator) -> Options<'a> {
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let options = Options::new(10).break_words_if(|word| {
+    ///     word.chars().all(|ch| ch.is_ascii_hexdigit())
+    /// });
+    /// assert_eq!(wrap("Fixed in 0123456789abcdef for Constantinople", &options),
+    ///            vec!["Fixed in", "0123456789", "abcdef for", "Constantinople"]);
+    /// ```
+    ///
+    /// [`self.break_words_if`]: #structfield.break_words_if
+    /// [`self.break_words`]: #structfield.break_words
+    pub fn break_words_if(self, break_words_if: fn(&Word<'_>) -> bool) -> Self {
         Options {
-            word_separator,
-            ..self
+            break_words_if: Some(break_words_if),
+            ..self.break_words(true)
         }
     }
 
-    /// Change [`self.wrap_algorithm`].
+    /// Change [`self.word_overflow`]. This controls what happens to
+    /// words longer than `self.width`: they can be broken, left
+    /// sticking out into the right margin, or truncated.
     ///
-    /// See the [`WrapAlgorithm`] trait for details on the choices.
+    /// Setting [`WordOverflow::Break`] or [`WordOverflow::Overflow`]
+    /// is the same as calling [`Options::break_words`] with `true` or
+    /// `false`, respectively.
     ///
-    /// [`self.wrap_algorithm`]: #structfield.wrap_algorithm
-    pub fn wrap_algorithm(self, wrap_algorithm: WrapAlgorithm) -> Options<'a> {
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordOverflow};
+    ///
+    /// let options = Options::new(10).word_overflow(WordOverflow::Truncate("..."));
+    /// assert_eq!(wrap("It is supercalifragilistic indeed.", options),
+    ///            vec!["It is",
+    ///                 "superca...",
+    ///                 "indeed."]);
+    /// ```
+    ///
+    /// [`self.word_overflow`]: #structfield.word_overflow
+    pub fn word_overflow(self, word_overflow: WordOverflow<'a>) -> Self {
         Options {
-            wrap_algorithm,
+            break_words: word_overflow == WordOverflow::Break,
+            word_overflow,
             ..self
         }
     }
 
-    /// Change [`self.word_splitter`]. The [`WordSplitter`] is used to
-    /// fit part of a word into the current line when wrapping text.
+    /// Truncate words wider than `self.width` and mark them with
+    /// `marker`. This is a shorthand for setting
+    /// [`self.word_overflow`] to [`WordOverflow::Truncate`].
     ///
-    /// See [`Options::break_words`] instead if you want to control the
-    /// handling of words longer than the line width.
+    /// The truncation uses the display width: the kept part of the
+    /// word and the marker together fit within the line. A wide
+    /// character which would make the line too long is dropped.
     ///
     /// # Examples
     ///
     /// ```
-    /// use textwrap::{wrap, Options, WordSplitter};
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let options = Options::new(12).overflow_marker("...");
+    /// assert_eq!(wrap("Call verylongidentifier() now", &options),
+    ///            vec!["Call", "verylongi...", "now"]);
+    /// ```
+    ///
+    /// [`self.word_overflow`]: #structfield.word_overflow
+    pub fn overflow_marker(self, marker: &'a str) -> Self {
+        self.word_overflow(WordOverflow::Truncate(marker))
+    }
+
+    /// Change [`self.wide_char_padding`]. The padding is added to
+    /// the end of a line which is exactly one column too short
+    /// because the next line starts with a double-width character.
+    ///
+    /// This happens when text with CJK characters is wrapped to an
+    /// odd width: a character which takes up two columns cannot fit
+    /// in the last remaining column. Set the padding to a
+    /// one-column-wide string, such as `" "`, to make all lines
+    /// equally wide. This is useful for grid-aligned output. By
+    /// default, the padding is empty and lines are left short.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let options = Options::new(5);
+    /// assert_eq!(wrap("東京都庁舎です", &options),
+    ///            vec!["東京", "都庁", "舎で", "す"]);
+    ///
+    /// let options = options.wide_char_padding("·");
+    /// assert_eq!(wrap("東京都庁舎です", &options),
+    ///            vec!["東京·", "都庁·", "舎で·", "す"]);
+    /// ```
+    ///
+    /// [`self.wide_char_padding`]: #structfield.wide_char_padding
+    pub fn wide_char_padding(self, wide_char_padding: impl Into<Cow<'a, str>>) -> Self {
+        Options {
+            wide_char_padding: wide_char_padding.into(),
+            ..self
+        }
+    }
+
+    /// Change [`self.drop_whitespace`]. This controls what happens
+    /// to the whitespace where a line is broken.
+    ///
+    /// By default, the whitespace is dropped together with trailing
+    /// whitespace at the end of the input lines. When set to `false`,
+    /// the whitespace is kept at the end of the lines. This is useful
+    /// when the spacing is meaningful: joining the wrapped lines
+    /// gives back the original input line. The kept whitespace counts
+    /// towards the line width, so the lines still fit in the width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options};
+    ///
+    /// let text = "let x  =  1;  ";
+    /// let options = Options::new(7).drop_whitespace(false);
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["let x  ", "=  1;  "]);
+    /// assert_eq!(wrap(text, &options).concat(), text);
+    ///
+    /// assert_eq!(wrap(text, Options::new(7)),
+    ///            vec!["let x", "=  1;"]);
+    /// ```
+    ///
+    /// [`self.drop_whitespace`]: #structfield.drop_whitespace
+    pub fn drop_whitespace(self, drop_whitespace: bool) -> Self {
+        Options {
+            drop_whitespace,
+            ..self
+        }
+    }
+
+    /// Change [`self.preserve_inner_whitespace`]. This controls how
+    /// much whitespace is dropped where a line is broken.
+    ///
+    /// Whitespace between words on the same line is always kept
+    /// as-is. When a line is broken at a run of several spaces, the
+    /// whole run is normally dropped. When this is set to `true`,
+    /// only a single space is dropped and the rest of the run is kept
+    /// at the end of the line. The kept whitespace counts towards the
+    /// line width. When the lines are joined again with a single
+    /// space, e.g., by [`refill()`], the original spacing is
+    /// restored. This keeps two spaces after sentences for people
+    /// following that convention.
+    ///
+    /// Whitespace at the end of the input lines is still dropped.
+    /// This setting has no effect when [`self.drop_whitespace`] is
+    /// `false` since all whitespace is then kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{refill, wrap, Options, WrapAlgorithm};
+    ///
+    /// let text = "One.  Two.  Three.";
+    /// let options = Options::new(11)
+    ///     .wrap_algorithm(WrapAlgorithm::FirstFit)
+    ///     .preserve_inner_whitespace(true);
+    /// assert_eq!(wrap(text, &options), vec!["One.  Two. ", "Three."]);
+    ///
+    /// let narrow = refill(text, options.clone().width(6));
+    /// assert_eq!(narrow, "One. \nTwo. \nThree.");
+    /// assert_eq!(refill(&narrow, options.width(20)), text);
+    /// ```
+    ///
+    /// [`refill()`]: crate::refill()
+    /// [`self.drop_whitespace`]: #structfield.drop_whitespace
+    /// [`self.preserve_inner_whitespace`]: #structfield.preserve_inner_whitespace
+    pub fn preserve_inner_whitespace(self, preserve_inner_whitespace: bool) -> Self {
+        Options {
+            preserve_inner_whitespace,
+            ..self
+        }
+    }
+
+    /// Change [`self.word_separator`].
+    ///
+    /// See the [`WordSeparator`] trait for details on the choices.
+    ///
+    /// [`self.word_separator`]: #structfield.word_separator
+    pub fn word_separator(self, word_separator: WordSeparator) -> Options<'a> {
+        Options {
+            word_separator,
+            ..self
+        }
+    }
+
+    /// Change [`self.wrap_algorithm`].
+    ///
+    /// See the [`WrapAlgorithm`] trait for details on the choices.
+    ///
+    /// [`self.wrap_algorithm`]: #structfield.wrap_algorithm
+    pub fn wrap_algorithm(self, wrap_algorithm: WrapAlgorithm) -> Options<'a> {
+        Options {
+            wrap_algorithm,
+            ..self
+        }
+    }
+
+    /// Change [`self.word_splitter`]. The [`WordSplitter`] is used to
+    /// fit part of a word into the current line when wrapping text.
+    ///
+    /// See [`Options::break_words`] instead if you want to control the
+    /// handling of words longer than the line width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSplitter};
     ///
     /// // The default is WordSplitter::HyphenSplitter.
     /// let options = Options::new(5);
@@ -277,24 +1389,680 @@ impl<'a> Options<'a> {
             ..self
         }
     }
+
+    /// Change [`self.hyphen`]. This is the string inserted at the end
+    /// of a line when the [`WordSplitter`] splits a word there. The
+    /// default is `"-"`. Some languages use other marks, such as the
+    /// Armenian hyphen `"֊"` or the Hebrew maqaf `"־"`, and some
+    /// styles prefer U+2010 HYPHEN `"‐"`.
+    ///
+    /// No hyphen is inserted if the split point comes right after a
+    /// hyphen or a dash in the text, or after the `hyphen` itself.
+    /// The width of the hyphen is taken into account when wrapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSplitter};
+    ///
+    /// let options = Options::new(8)
+    ///     .word_splitter(WordSplitter::Custom(|word| vec![word.len() / 2]))
+    ///     .hyphen("‐");
+    /// assert_eq!(wrap("Hyphenation", &options), vec!["Hyphe‐", "nation"]);
+    /// ```
+    ///
+    /// [`self.hyphen`]: #structfield.hyphen
+    pub fn hyphen(self, hyphen: &'static str) -> Options<'a> {
+        Options { hyphen, ..self }
+    }
+
+    /// Change [`self.min_fragment_lengths`]. This sets the minimum
+    /// number of characters which must come before and after a
+    /// split point found by the [`WordSplitter`].
+    ///
+    /// Only alphanumeric characters are counted, so a hyphen or
+    /// punctuation after the fragment does not make it longer. This
+    /// prevents unsightly breaks such as `"a-"` or `"-ly."`. The
+    /// default of `(1, 1)` allows all split points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSplitter};
+    ///
+    /// let options = Options::new(7).word_splitter(WordSplitter::Custom(|word| {
+    ///     // Allow a split between any two characters.
+    ///     word.char_indices().skip(1).map(|(idx, _)| idx).collect()
+    /// }));
+    /// assert_eq!(wrap("a quickly", &options),
+    ///            vec!["a quic-", "kly"]);
+    /// assert_eq!(wrap("a quickly", options.min_fragment_lengths((2, 5))),
+    ///            vec!["a qu-", "ickly"]);
+    /// ```
+    ///
+    /// [`self.min_fragment_lengths`]: #structfield.min_fragment_lengths
+    pub fn min_fragment_lengths(self, min_fragment_lengths: (usize, usize)) -> Options<'a> {
+        Options {
+            min_fragment_lengths,
+            ..self
+        }
+    }
+
+    /// Change [`self.hyphenation_filter`]. The filter decides which
+    /// words are hyphenated when the [`WordSplitter`] uses a
+    /// hyphenation dictionary. Words are only given to the dictionary
+    /// if the function returns `true`.
+    ///
+    /// By default, all words are hyphenated. Use
+    /// [`is_hyphenatable()`] to skip acronyms, words with digits, and
+    /// identifiers such as "getValue".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hyphenation")] {
+    /// use hyphenation::{Language, Load, Standard};
+    /// use textwrap::{wrap, Options, WordSplitter, WrapAlgorithm};
+    ///
+    /// let dictionary = Standard::from_embedded(Language::EnglishUS).unwrap();
+    /// let options = Options::new(9)
+    ///     .word_splitter(WordSplitter::Hyphenation(dictionary))
+    ///     .wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(wrap("Call getValue", &options), vec!["Call get-", "Value"]);
+    ///
+    /// // Leave identifiers alone:
+    /// let options = options.hyphenation_filter(textwrap::word_splitters::is_hyphenatable);
+    /// assert_eq!(wrap("Call getValue", &options), vec!["Call", "getValue"]);
+    /// # }
+    /// ```
+    ///
+    /// [`self.hyphenation_filter`]: #structfield.hyphenation_filter
+    /// [`is_hyphenatable()`]: crate::word_splitters::is_hyphenatable
+    pub fn hyphenation_filter(self, hyphenation_filter: fn(&str) -> bool) -> Options<'a> {
+        Options {
+            hyphenation_filter: Some(hyphenation_filter),
+            ..self
+        }
+    }
+
+    /// Change [`self.sanitize_control_chars`]. This controls if
+    /// control characters such as `'\0'` and `'\x0c'` (form feed)
+    /// are kept, stripped, or replaced with visible escapes before
+    /// the text is wrapped. See [`ControlChars`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{fill, ControlChars, Options};
+    ///
+    /// let options = Options::new(10).sanitize_control_chars(ControlChars::Escape);
+    /// assert_eq!(fill("Ring the\x07 bell", options), "Ring the^G\nbell");
+    /// ```
+    ///
+    /// [`self.sanitize_control_chars`]: #structfield.sanitize_control_chars
+    pub fn sanitize_control_chars(self, sanitize_control_chars: ControlChars) -> Options<'a> {
+        Options {
+            sanitize_control_chars,
+            ..self
+        }
+    }
+
+    /// Change [`self.normalization`]. This controls if the text is
+    /// normalized to Unicode Normalization Form C before it is
+    /// wrapped, or if the normal form is only used to measure the
+    /// width of words. See [`Normalization`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "unicode-normalization")] {
+    /// use textwrap::{fill, Normalization, Options};
+    ///
+    /// let options = Options::new(10).normalization(Normalization::MeasureNfc);
+    /// assert_eq!(fill("Cafe\u{301} au lait", options), "Cafe\u{301} au\nlait");
+    /// # }
+    /// ```
+    ///
+    /// **Note:** Only [`Normalization::Keep`] is available unless the
+    /// `unicode-normalization` Cargo feature is enabled.
+    ///
+    /// [`self.normalization`]: #structfield.normalization
+    pub fn normalization(self, normalization: Normalization) -> Options<'a> {
+        Options {
+            normalization,
+            ..self
+        }
+    }
+
+    /// Change [`self.break_filter`]. The filter is called for every
+    /// break point found by the [`WordSeparator`] and the
+    /// [`WordSplitter`]. Break points are rejected if the function
+    /// returns `false`, which means that the text will not be broken
+    /// there. Line endings in the input cannot be rejected.
+    ///
+    /// Use [`analyze()`](crate::analyze()) to see the break points
+    /// which remain after filtering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, BreakKind, Options};
+    ///
+    /// // Keep ticket numbers such as ABC-1234 together and never
+    /// // break after "e.g.":
+    /// let options = Options::new(16).break_filter(|candidate| {
+    ///     let ticket_number = candidate.kind == BreakKind::Hyphenation
+    ///         && candidate.after().starts_with(|ch: char| ch.is_ascii_digit());
+    ///     !ticket_number && !candidate.before().ends_with("e.g. ")
+    /// });
+    /// assert_eq!(wrap("Fixed in e.g. ABC-1234.", &options),
+    ///            vec!["Fixed in",
+    ///                 "e.g. ABC-1234."]);
+    ///
+    /// // Without the filter, the text is broken after "e.g.":
+    /// assert_eq!(wrap("Fixed in e.g. ABC-1234.", 16),
+    ///            vec!["Fixed in e.g.",
+    ///                 "ABC-1234."]);
+    /// ```
+    ///
+    /// [`self.break_filter`]: #structfield.break_filter
+    pub fn break_filter(self, break_filter: fn(&BreakCandidate<'_>) -> bool) -> Options<'a> {
+        Options {
+            break_filter: Some(break_filter),
+            ..self
+        }
+    }
+
+    /// Change [`self.no_break_before`]. Lines will not start with
+    /// any of the characters in `no_break_before`.
+    ///
+    /// This prevents lines starting with closing punctuation such as
+    /// `","` or `")"`, which can happen with a custom
+    /// [`WordSplitter`] or when the input has a space before the
+    /// punctuation. Break points before the characters are removed
+    /// like with a [`break_filter`], so the text is broken one word
+    /// earlier instead. Use [`Options::no_break_after`] for opening
+    /// brackets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator, WrapAlgorithm};
+    ///
+    /// let options = Options::new(15)
+    ///     .word_separator(WordSeparator::AsciiSpace)
+    ///     .wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// let text = "Items are sorted by name , then by date";
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["Items are",
+    ///                 "sorted by name",
+    ///                 ", then by date"]);
+    /// assert_eq!(wrap(text, options.no_break_before(",.;:!?)]}")),
+    ///            vec!["Items are",
+    ///                 "sorted by",
+    ///                 "name , then by",
+    ///                 "date"]);
+    /// ```
+    ///
+    /// [`self.no_break_before`]: #structfield.no_break_before
+    /// [`break_filter`]: Options::break_filter
+    pub fn no_break_before(self, no_break_before: impl Into<Cow<'a, str>>) -> Options<'a> {
+        Options {
+            no_break_before: no_break_before.into(),
+            ..self
+        }
+    }
+
+    /// Change [`self.no_break_after`]. Lines will not end with any
+    /// of the characters in `no_break_after`, not counting trailing
+    /// whitespace.
+    ///
+    /// This keeps opening brackets and quotes together with the
+    /// following word. See [`Options::no_break_before`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator, WrapAlgorithm};
+    ///
+    /// let options = Options::new(12)
+    ///     .word_separator(WordSeparator::AsciiSpace)
+    ///     .wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(wrap("Call foo( bar ) now", &options),
+    ///            vec!["Call foo(", "bar ) now"]);
+    /// assert_eq!(wrap("Call foo( bar ) now", options.no_break_after("([{")),
+    ///            vec!["Call", "foo( bar )", "now"]);
+    /// ```
+    ///
+    /// [`self.no_break_after`]: #structfield.no_break_after
+    pub fn no_break_after(self, no_break_after: impl Into<Cow<'a, str>>) -> Options<'a> {
+        Options {
+            no_break_after: no_break_after.into(),
+            ..self
+        }
+    }
+
+    /// Change [`self.hard_break_markers`]. Lines ending with one of
+    /// these markers are not joined with the following line by
+    /// [`refill()`] and [`unfill_with_hard_breaks()`]. Use `"  "` for
+    /// the hard line breaks in Markdown.
+    ///
+    /// The markers have no effect on [`wrap()`] and [`fill()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{refill, Options};
+    ///
+    /// let options = Options::new(30).hard_break_markers(&["\\"]);
+    /// assert_eq!(refill("cargo build \\\n  --release", options),
+    ///            "cargo build \\\n  --release");
+    /// ```
+    ///
+    /// [`self.hard_break_markers`]: #structfield.hard_break_markers
+    /// [`refill()`]: crate::refill()
+    /// [`unfill_with_hard_breaks()`]: crate::unfill_with_hard_breaks()
+    /// [`wrap()`]: crate::wrap()
+    /// [`fill()`]: crate::fill()
+    pub fn hard_break_markers(self, hard_break_markers: &'a [&'a str]) -> Options<'a> {
+        Options {
+            hard_break_markers,
+            ..self
+        }
+    }
+
+    /// Change [`self.sentence_spacing`]. This controls the number of
+    /// spaces after a sentence when text is reflowed by [`refill()`].
+    /// See [`SentenceSpacing`] for details.
+    ///
+    /// The spacing has no effect on [`wrap()`] and [`fill()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{refill, Options, SentenceSpacing};
+    ///
+    /// let options = Options::new(50).sentence_spacing(SentenceSpacing::Double);
+    /// assert_eq!(refill("One sentence.\nAnother one. And a third.", options),
+    ///            "One sentence.  Another one.  And a third.");
+    /// ```
+    ///
+    /// [`self.sentence_spacing`]: #structfield.sentence_spacing
+    /// [`refill()`]: crate::refill()
+    /// [`wrap()`]: crate::wrap()
+    /// [`fill()`]: crate::fill()
+    pub fn sentence_spacing(self, sentence_spacing: SentenceSpacing) -> Options<'a> {
+        Options {
+            sentence_spacing,
+            ..self
+        }
+    }
+
+    /// Change [`self.protected_ranges`]. The text is never broken
+    /// inside these byte ranges, which is useful for inline code
+    /// spans, URLs, and other verbatim text.
+    ///
+    /// Each range is treated as a single word: breaks found by the
+    /// [`WordSeparator`] and [`WordSplitter`] inside the range are
+    /// ignored, and a word containing the range is not broken apart
+    /// when [`self.break_words`] is `true`. A range wider than the
+    /// line thus sticks out into the right margin. Breaks at the
+    /// start and end of the ranges are still allowed.
+    ///
+    /// The ranges are byte offsets into the text passed to
+    /// [`wrap()`] or [`fill()`]. When [`self.sanitize_control_chars`],
+    /// [`self.unicode_separators`], or [`self.normalization`] change
+    /// a line, the ranges are moved along with the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator, WrapAlgorithm};
+    ///
+    /// let text = "Run `cargo test --all-features` to test everything.";
+    /// let options = Options::new(20)
+    ///     .word_separator(WordSeparator::AsciiSpace)
+    ///     .wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["Run `cargo test", "--all-features` to", "test everything."]);
+    ///
+    /// let code = text.find('`').unwrap()..text.rfind('`').unwrap() + 1;
+    /// let ranges = [code];
+    /// let options = options.protected_ranges(&ranges);
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["Run", "`cargo test --all-features`", "to test everything."]);
+    /// ```
+    ///
+    /// [`self.protected_ranges`]: #structfield.protected_ranges
+    /// [`self.break_words`]: #structfield.break_words
+    /// [`self.sanitize_control_chars`]: #structfield.sanitize_control_chars
+    /// [`self.unicode_separators`]: #structfield.unicode_separators
+    /// [`self.normalization`]: #structfield.normalization
+    /// [`wrap()`]: crate::wrap()
+    /// [`fill()`]: crate::fill()
+    pub fn protected_ranges(self, protected_ranges: &'a [Range<usize>]) -> Options<'a> {
+        Options {
+            protected_ranges,
+            ..self
+        }
+    }
+
+    /// Change [`self.width_overrides`]. Each `(char, width)` pair
+    /// replaces the width computed for the character, both in the
+    /// text and in the indentation.
+    ///
+    /// Terminals disagree about the width of some characters, such
+    /// as emojis and the East Asian characters with ambiguous width.
+    /// Use this to match the behavior of the terminal you are
+    /// printing to. The list is searched linearly, so it should be
+    /// kept short.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WrapAlgorithm};
+    ///
+    /// let options = Options::new(10).wrap_algorithm(WrapAlgorithm::FirstFit);
+    /// assert_eq!(wrap("5±1 × 3±1 cm", &options), vec!["5±1 × 3±1", "cm"]);
+    ///
+    /// // A terminal which shows '±' and '×' as two columns:
+    /// let options = options.width_overrides(&[('±', 2), ('×', 2)]);
+    /// assert_eq!(wrap("5±1 × 3±1 cm", &options), vec!["5±1 ×", "3±1 cm"]);
+    /// ```
+    ///
+    /// [`self.width_overrides`]: #structfield.width_overrides
+    pub fn width_overrides(self, width_overrides: &'a [(char, usize)]) -> Options<'a> {
+        Options {
+            width_overrides,
+            ..self
+        }
+    }
+
+    /// Create a key which can be used to cache results computed with
+    /// these options.
+    ///
+    /// This returns `None` if the options contain a function pointer,
+    /// i.e., a `Custom` [`WrapAlgorithm`], [`WordSeparator`], or
+    /// [`WordSplitter`], a [`break_filter`], a
+    /// [`hyphenation_filter`], or a [`break_words_if`] predicate.
+    /// Function pointers
+    /// cannot be compared reliably, so such options never compare
+    /// equal and cannot be used for caching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use textwrap::{fill, Options, WordSplitter};
+    ///
+    /// let mut cache = HashMap::new();
+    /// let options = Options::new(15).initial_indent("> ");
+    /// let key = options.cache_key().unwrap();
+    /// cache.insert(key, fill("Cached results", &options));
+    ///
+    /// let same = Options::new(15).initial_indent(String::from("> "));
+    /// assert_eq!(cache[&same.cache_key().unwrap()], "> Cached\nresults");
+    ///
+    /// let custom = options.word_splitter(WordSplitter::Custom(|_| Vec::new()));
+    /// assert_eq!(custom.cache_key(), None);
+    /// ```
+    ///
+    /// [`break_filter`]: Options::break_filter
+    /// [`hyphenation_filter`]: Options::hyphenation_filter
+    /// [`break_words_if`]: Options::break_words_if
+    pub fn cache_key(&self) -> Option<OptionsKey<'a>> {
+        let is_custom = matches!(self.wrap_algorithm, WrapAlgorithm::Custom(_))
+            || matches!(self.word_separator, WordSeparator::Custom(_))
+            || self.word_splitter.is_custom()
+            || self.break_filter.is_some()
+            || self.hyphenation_filter.is_some()
+            || self.break_words_if.is_some();
+        if is_custom {
+            None
+        } else {
+            Some(OptionsKey(self.clone()))
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn options_are_send_and_sync() {
+        assert_send_sync::<Options<'static>>();
+        assert_send_sync::<OptionsKey<'static>>();
+        assert_send_sync::<WrapAlgorithm>();
+        assert_send_sync::<WordSeparator>();
+        assert_send_sync::<WordSplitter>();
+        assert_send_sync::<WordOverflow<'static>>();
+    }
+
     #[test]
     fn options_agree_with_usize() {
         let opt_usize = Options::from(42_usize);
         let opt_options = Options::new(42);
 
         assert_eq!(opt_usize.width, opt_options.width);
+        assert_eq!(opt_usize.max_width, opt_options.max_width);
         assert_eq!(opt_usize.initial_indent, opt_options.initial_indent);
         assert_eq!(opt_usize.subsequent_indent, opt_options.subsequent_indent);
         assert_eq!(opt_usize.break_words, opt_options.break_words);
+        assert_eq!(opt_usize.word_overflow, opt_options.word_overflow);
         assert_eq!(
             opt_usize.word_splitter.split_points("hello-world"),
             opt_options.word_splitter.split_points("hello-world")
         );
     }
+
+    #[test]
+    fn word_overflow_agrees_with_break_words() {
+        let options = Options::new(10).break_words(false);
+        assert_eq!(options.word_overflow, WordOverflow::Overflow);
+        let options = options.word_overflow(WordOverflow::Break);
+        assert!(options.break_words);
+        let options = options.word_overflow(WordOverflow::Truncate("…"));
+        assert!(!options.break_words);
+    }
+
+    #[test]
+    fn for_email_keeps_long_words() {
+        let options = Options::for_email(72);
+        assert!(!options.break_words);
+        assert_eq!(options.word_overflow, WordOverflow::Overflow);
+        assert_eq!(options.word_separator, WordSeparator::AsciiSpace);
+        assert_eq!(options.word_splitter, WordSplitter::NoHyphenation);
+    }
+
+    #[test]
+    fn clamp_respects_max_width() {
+        let options = Options::new(5)
+            .subsequent_indent("    ")
+            .max_width(10)
+            .min_width(MinWidth::Clamp(10));
+        assert_eq!(options.build().unwrap().width, 10);
+
+        let options = Options::new(5)
+            .subsequent_indent("    ")
+            .min_width(MinWidth::Clamp(usize::MAX));
+        assert_eq!(options.build().unwrap().width, usize::MAX);
+    }
+
+    #[test]
+    fn function_pointers_never_compare_equal() {
+        fn always(_: &str) -> bool {
+            true
+        }
+        let options = Options::new(20).hyphenation_filter(always);
+        assert_ne!(options, options.clone());
+        let options = Options::new(20).break_words_if(|_| true);
+        assert_ne!(options, options.clone());
+        let options = Options::new(20).break_filter(|_| true);
+        assert_ne!(options, options.clone());
+        assert_eq!(Options::new(20), Options::new(20));
+    }
+
+    #[test]
+    fn equal_options_have_equal_cache_keys() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |key: &OptionsKey<'_>| {
+            let mut hasher = DefaultHasher::new();
+            key.hash(&mut hasher);
+            hasher.finish()
+        };
+        let a = Options::new(20)
+            .subsequent_indent("  ")
+            .cache_key()
+            .unwrap();
+        let b = Options::new(20)
+            .subsequent_indent(" ".repeat(2))
+            .cache_key()
+            .unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(
+            a,
+            Options::new(21)
+                .subsequent_indent("  ")
+                .cache_key()
+                .unwrap()
+        );
+        assert_ne!(a, Options::new(20).cache_key().unwrap());
+    }
+
+    #[test]
+    fn explicit_line_ending_changes_cache_key() {
+        let default = Options::new(20).cache_key().unwrap();
+        let explicit = Options::new(20)
+            .line_ending(LineEnding::LF)
+            .cache_key()
+            .unwrap();
+        assert_ne!(default, explicit);
+        assert_ne!(
+            Options::new(20),
+            Options::new(20).line_ending(LineEnding::LF)
+        );
+    }
+
+    #[test]
+    fn cache_key_rejects_custom_functions() {
+        let options = Options::new(20);
+        assert!(options.cache_key().is_some());
+        assert!(options
+            .clone()
+            .wrap_algorithm(WrapAlgorithm::Custom(|words, _| vec![words]))
+            .cache_key()
+            .is_none());
+        assert!(options.break_filter(|_| false).cache_key().is_none());
+    }
+
+    #[test]
+    fn cache_key_rejects_chain_with_custom_splitter() {
+        let options = Options::new(20);
+        assert!(options
+            .clone()
+            .word_splitter(WordSplitter::Chain(vec![
+                WordSplitter::HyphenSplitter,
+                WordSplitter::NoHyphenation,
+            ]))
+            .cache_key()
+            .is_some());
+        assert!(options
+            .word_splitter(WordSplitter::Chain(vec![
+                WordSplitter::HyphenSplitter,
+                WordSplitter::Custom(|_| vec![]),
+            ]))
+            .cache_key()
+            .is_none());
+    }
+
+    #[test]
+    fn break_words_field_decides_word_overflow() {
+        let mut options = Options::new(3);
+        options.break_words = false;
+        assert_eq!(options.word_overflow, WordOverflow::Break);
+        assert_eq!(crate::wrap("foobar", &options), vec!["foobar"]);
+
+        let mut options = Options::new(3).break_words(false);
+        options.break_words = true;
+        assert_eq!(crate::wrap("foobar", &options), vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn debug_hides_function_pointers() {
+        let options = Options::new(10).break_filter(|_| true);
+        let debug = format!("{:?}", options);
+        assert!(debug.contains("break_filter: Some(..)"));
+        assert!(debug.contains("hyphenation_filter: None"));
+        assert!(debug.contains("break_words_if: None"));
+    }
+
+    #[test]
+    fn max_width_clamps_assigned_width() {
+        let mut options = Options::new(80).max_width(10);
+        options.width = 20;
+        assert_eq!(options.clone().build().unwrap().width, 10);
+        assert_eq!(
+            crate::wrap("foo bar baz quux", &options),
+            vec!["foo bar", "baz quux"]
+        );
+        assert_eq!(
+            crate::fill("foo bar baz quux", &options),
+            "foo bar\nbaz quux"
+        );
+    }
+
+    #[test]
+    fn max_width_clamps_width() {
+        let options = Options::new(80).max_width(50);
+        assert_eq!(options.width, 50);
+        assert_eq!(options.clone().width(70).width, 50);
+        assert_eq!(options.clone().width(30).width, 30);
+        assert_eq!(options.max_width(100).width(70).width, 70);
+    }
+
+    #[test]
+    fn min_width_zero_width() {
+        let options = Options::new(0).min_width(MinWidth::Clamp(1));
+        assert_eq!(crate::wrap("ab", &options), vec!["a", "b"]);
+        assert_eq!(crate::fill("ab", &options), "a\nb");
+
+        let options = options.min_width(MinWidth::Error(1));
+        assert_eq!(crate::try_fill("ab", &options), Err(Error::WidthTooSmall));
+        assert_eq!(
+            crate::try_wrap_stats("ab", &options),
+            Err(Error::WidthTooSmall)
+        );
+        assert!(options.min_width(MinWidth::Error(0)).build().is_ok());
+    }
+
+    #[test]
+    fn min_width_uses_widest_indent() {
+        let options = Options::new(10)
+            .indents(&["", "12345678", ""])
+            .min_width(MinWidth::Error(3));
+        assert_eq!(options.clone().build(), Err(Error::WidthTooSmall));
+        let clamped = options.min_width(MinWidth::Clamp(3)).build().unwrap();
+        assert_eq!(clamped.width, 11);
+    }
+
+    #[test]
+    fn min_width_error_proceeds_in_infallible_functions() {
+        let options = Options::new(0).min_width(MinWidth::Error(1));
+        let proceed = options.clone().min_width(MinWidth::Proceed);
+        assert_eq!(crate::fill("ab", &options), crate::fill("ab", &proceed));
+        assert_eq!(crate::wrap("ab", &options), crate::wrap("ab", &proceed));
+        let mut lines = Vec::new();
+        crate::wrap_into("ab", &options, &mut lines);
+        assert_eq!(lines, crate::wrap("ab", &proceed));
+        assert_eq!(
+            crate::annotate_wrap("ab", &options),
+            crate::annotate_wrap("ab", &proceed)
+        );
+        assert_eq!(crate::fill_height("ab", &options), 2);
+    }
 }
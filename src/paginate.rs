@@ -0,0 +1,130 @@
+//! Functionality for splitting wrapped text into pages.
+
+use std::borrow::Cow;
+
+use crate::{wrap, Options};
+
+/// Wrap text and split the lines into pages of `height` lines.
+///
+/// The `text` is wrapped with [`wrap()`] and the lines are split into
+/// pages. Paragraphs are separated by blank lines. Like in
+/// typesetting, the pages are broken so that a paragraph does not
+/// leave
+///
+/// * its first line alone at the bottom of a page (an _orphan_), or
+/// * its last line alone at the top of the next page (a _widow_).
+///
+/// This is done by moving lines to the next page, which makes the
+/// page shorter than `height`. A page is never left empty, so a
+/// paragraph can still be broken like this if `height` is very
+/// small. Blank lines at the top of a page are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::paginate;
+///
+/// let text = "The first paragraph.\n\nThe second paragraph is longer.";
+/// assert_eq!(paginate(text, 15, 4), vec![
+///     vec!["The first", "paragraph.", ""],
+///     vec!["The second", "paragraph is", "longer."],
+/// ]);
+/// ```
+///
+/// Without the orphan control, the last page would have started
+/// with "paragraph is".
+///
+/// # Panics
+///
+/// Panics if `height` is zero.
+pub fn paginate<'a, Opt>(
+    text: &'a str,
+    width_or_options: Opt,
+    height: usize,
+) -> Vec<Vec<Cow<'a, str>>>
+where
+    Opt: Into<Options<'a>>,
+{
+    assert!(height > 0, "page height must be positive");
+    let lines = wrap(text, width_or_options);
+    let is_blank = |idx: usize| lines.get(idx).map_or(true, |line| line.trim().is_empty());
+    // Line `idx` is the first line of a paragraph with more lines.
+    let is_first =
+        |idx: usize| !is_blank(idx) && (idx == 0 || is_blank(idx - 1)) && !is_blank(idx + 1);
+    // Line `idx` is the last line of a paragraph with more lines.
+    let is_last = |idx: usize| !is_blank(idx) && idx > 0 && !is_blank(idx - 1) && is_blank(idx + 1);
+
+    let mut page_breaks = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        if !page_breaks.is_empty() {
+            while start < lines.len() && is_blank(start) {
+                start += 1;
+            }
+            if start == lines.len() {
+                break;
+            }
+        }
+
+        let mut end = std::cmp::min(start + height, lines.len());
+        if end < lines.len() && !is_blank(end) {
+            if is_last(end) && end - 1 > start {
+                end -= 1;
+            }
+            if is_first(end - 1) && end - 1 > start {
+                end -= 1;
+            }
+        }
+        page_breaks.push((start, end));
+        start = end;
+    }
+
+    let mut lines = lines.into_iter();
+    let mut pages = Vec::with_capacity(page_breaks.len());
+    let mut offset = 0;
+    for (start, end) in page_breaks {
+        let page = lines.by_ref().skip(start - offset).take(end - start);
+        pages.push(page.collect());
+        offset = end;
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginate_empty() {
+        assert_eq!(paginate("", 10, 3), vec![vec![""]]);
+    }
+
+    #[test]
+    fn paginate_widow() {
+        // Without widow control, "d" would be alone on the second page.
+        let text = "a\nb\nc\nd";
+        assert_eq!(paginate(text, 10, 3), vec![vec!["a", "b"], vec!["c", "d"]]);
+    }
+
+    #[test]
+    fn paginate_orphan() {
+        let text = "a\nb\n\nc\nd\ne";
+        assert_eq!(
+            paginate(text, 10, 4),
+            vec![vec!["a", "b", ""], vec!["c", "d", "e"]]
+        );
+    }
+
+    #[test]
+    fn paginate_small_height() {
+        let text = "a\nb\nc";
+        assert_eq!(paginate(text, 10, 1), vec![vec!["a"], vec!["b"], vec!["c"]]);
+        assert_eq!(paginate(text, 10, 2), vec![vec!["a"], vec!["b", "c"]]);
+    }
+
+    #[test]
+    fn paginate_drops_blank_lines() {
+        let text = "a\nb\n\n\nc\nd";
+        assert_eq!(paginate(text, 10, 2), vec![vec!["a", "b"], vec!["c", "d"]]);
+    }
+}
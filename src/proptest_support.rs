@@ -0,0 +1,184 @@
+//! Generators and invariants for property-based tests.
+//!
+//! The strategies generate text and [`Options`] which are fed to
+//! [`proptest!`](proptest::proptest) tests, both in this crate and in
+//! crates which wrap text with `textwrap`. The invariants are the
+//! properties which must hold for all wrapped text: no line is wider
+//! than the width unless it cannot be avoided, and no text is lost or
+//! duplicated.
+//!
+//! # Examples
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use textwrap::proptest_support::{any_algorithm_options, check_content, text};
+//!
+//! proptest! {
+//!     fn keeps_content(text in text(), options in any_algorithm_options()) {
+//!         let lines = textwrap::wrap(&text, &options);
+//!         check_content(&text, &lines, &options)?;
+//!     }
+//! }
+//! # keeps_content();
+//! ```
+//!
+//! **Note:** Only available when the `proptest` Cargo feature is
+//! enabled.
+
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use crate::core::{display_width, line_widths_for};
+use crate::{Options, WordSeparator, WordSplitter, WrapAlgorithm};
+
+/// Indentations used by [`options()`]. None of them use the prefix
+/// characters detected by [`unfill()`](crate::unfill()), except for
+/// spaces, so they can be stripped again.
+const INDENTS: [&str; 4] = ["", "  ", "    ", "| "];
+
+/// Text with words from `[a-z]`, punctuation, and hyphens separated
+/// by runs of spaces and line breaks.
+pub fn text() -> impl Strategy<Value = String> {
+    "[a-z.,-]{1,15}( {1,3}[a-z.,-]{1,15}| ?\n[a-z.,-]{1,15}){0,25}"
+}
+
+/// Words from `[a-z]`, separated by single spaces. This is the kind
+/// of text which [`unfill()`](crate::unfill()) restores exactly.
+pub fn single_spaced_words() -> impl Strategy<Value = String> {
+    prop::collection::vec("[a-z]{1,15}", 1..30).prop_map(|words| words.join(" "))
+}
+
+/// Options with a random width, indentation, word breaking, and
+/// handling of whitespace. The options use
+/// [`WrapAlgorithm::FirstFit`], see [`check_line_widths()`].
+pub fn options() -> impl Strategy<Value = Options<'static>> {
+    (
+        (
+            1..60usize,
+            prop::sample::select(INDENTS.to_vec()),
+            prop::sample::select(INDENTS.to_vec()),
+            0..4usize,
+        ),
+        (any::<bool>(), any::<bool>(), any::<bool>(), any::<bool>()),
+    )
+        .prop_map(
+            |(
+                (width, initial_indent, subsequent_indent, last_line_reserve),
+                (break_words, unicode, hyphens, preserve_inner_whitespace),
+            )| {
+                Options::new(width)
+                    .initial_indent(initial_indent)
+                    .subsequent_indent(subsequent_indent)
+                    .last_line_reserve(last_line_reserve)
+                    .break_words(break_words)
+                    .preserve_inner_whitespace(preserve_inner_whitespace)
+                    .word_separator(match unicode {
+                        #[cfg(feature = "unicode-linebreak")]
+                        true => WordSeparator::UnicodeBreakProperties,
+                        _ => WordSeparator::AsciiSpace,
+                    })
+                    .word_splitter(match hyphens {
+                        true => WordSplitter::HyphenSplitter,
+                        false => WordSplitter::NoHyphenation,
+                    })
+                    .wrap_algorithm(WrapAlgorithm::FirstFit)
+            },
+        )
+}
+
+/// The built-in wrap algorithms: [`WrapAlgorithm::FirstFit`], and
+/// [`WrapAlgorithm::OptimalFit`] and [`WrapAlgorithm::Adaptive`] when
+/// the `smawk` Cargo feature is enabled.
+pub fn wrap_algorithm() -> impl Strategy<Value = WrapAlgorithm> {
+    #[allow(unused_mut)]
+    let mut algorithms = vec![WrapAlgorithm::FirstFit];
+    #[cfg(feature = "smawk")]
+    algorithms.extend([
+        WrapAlgorithm::new_optimal_fit(),
+        WrapAlgorithm::Adaptive {
+            optimal_below_words: 10,
+        },
+    ]);
+    prop::sample::select(algorithms)
+}
+
+/// Options like [`options()`], but with any of the built-in wrap
+/// algorithms from [`wrap_algorithm()`]. Use these with
+/// [`check_content()`].
+pub fn any_algorithm_options() -> impl Strategy<Value = Options<'static>> {
+    (options(), wrap_algorithm()).prop_map(|(options, algorithm)| options.wrap_algorithm(algorithm))
+}
+
+/// Remove the indentation added by `options` from the wrapped
+/// `lines`.
+pub fn strip_indents<'a>(lines: &'a [impl AsRef<str>], options: &Options<'_>) -> Vec<&'a str> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let line = line.as_ref();
+            let indent = match idx {
+                0 => &options.initial_indent,
+                _ => &options.subsequent_indent,
+            };
+            line.strip_prefix(indent.as_ref())
+                .or_else(|| line.strip_prefix(indent.trim_end()))
+                .unwrap_or(line)
+        })
+        .collect()
+}
+
+/// Check that no line is wider than `options.width`, unless the line
+/// holds a single word which cannot be broken further. The words are
+/// found with `options.word_separator`.
+///
+/// Long words are broken to fit the subsequent lines, so a broken
+/// word can still overflow the first line if the initial indentation
+/// is wider than the subsequent indentation.
+///
+/// This assumes [`WrapAlgorithm::FirstFit`]: the optimal-fit
+/// algorithm will sometimes let a line overflow by a character
+/// instead of leaving a large gap.
+pub fn check_line_widths(
+    lines: &[impl AsRef<str>],
+    options: &Options<'_>,
+) -> Result<(), TestCaseError> {
+    let broken_width = std::cmp::max(line_widths_for(options)[1], 1);
+    for (line, content) in lines.iter().zip(strip_indents(lines, options)) {
+        let line = line.as_ref();
+        if display_width(line) <= options.width {
+            continue;
+        }
+        let unavoidable = match options.word_separator.find_words(content).count() {
+            0 => true,
+            1 => !options.break_words || display_width(content.trim_end()) <= broken_width,
+            _ => false,
+        };
+        prop_assert!(
+            unavoidable,
+            "line {:?} is wider than {}",
+            line,
+            options.width
+        );
+    }
+    Ok(())
+}
+
+/// Check that the wrapped `lines` contain the same characters as
+/// `text`, ignoring whitespace and hyphens. The hyphens are ignored
+/// since the word splitter can insert them.
+pub fn check_content(
+    text: &str,
+    lines: &[impl AsRef<str>],
+    options: &Options<'_>,
+) -> Result<(), TestCaseError> {
+    let significant = |ch: &char| !ch.is_whitespace() && *ch != '-';
+    let expected = text.chars().filter(significant).collect::<String>();
+    let actual = strip_indents(lines, options)
+        .concat()
+        .chars()
+        .filter(significant)
+        .collect::<String>();
+    prop_assert_eq!(actual, expected);
+    Ok(())
+}
@@ -1,14 +1,180 @@
 //! Functionality for unfilling and refilling text.
 
-use crate::core::display_width;
+use std::borrow::Cow;
+
+use crate::break_points::hyphenation_penalty;
+use crate::core::{display_width, is_sentence_end};
 use crate::line_ending::NonEmptyLines;
-use crate::{fill, LineEnding, Options};
+use crate::{
+    wrap, Compact, ControlChars, LineEnding, Normalization, Options, WordSeparator, WordSplitter,
+    WrapAlgorithm,
+};
+
+/// Spacing between sentences when refilling text.
+///
+/// Some people put two spaces after the end of a sentence, others use
+/// a single space. When text is reflowed with [`refill()`], the lines
+/// are first joined with a single space, which loses the spacing of
+/// sentences which ended at a line break. This setting lets you
+/// choose the spacing used in the reflowed text.
+///
+/// A sentence ends with a word ending with `'.'`, `'!'`, or `'?'`,
+/// possibly followed by closing quotes or brackets. Abbreviations
+/// such as `"e.g."` look like the end of a sentence, so two spaces
+/// are only used before words starting with an uppercase letter.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{refill, Options, SentenceSpacing};
+///
+/// let text = "Hello.  How are\nyou? Fine, e.g. good.";
+/// let options = Options::new(50);
+/// assert_eq!(refill(text, &options), "Hello.  How are you? Fine, e.g. good.");
+///
+/// let options = options.sentence_spacing(SentenceSpacing::Single);
+/// assert_eq!(refill(text, &options), "Hello. How are you? Fine, e.g. good.");
+///
+/// let options = options.sentence_spacing(SentenceSpacing::Double);
+/// assert_eq!(refill(text, &options), "Hello.  How are you?  Fine, e.g. good.");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SentenceSpacing {
+    /// Keep the spacing found in the text. Sentences ending at a
+    /// line break are followed by a single space.
+    #[default]
+    Keep,
+    /// Use a single space after every sentence.
+    Single,
+    /// Use two spaces after every sentence.
+    Double,
+}
+
+impl SentenceSpacing {
+    /// Apply this spacing to the sentences in `text`.
+    ///
+    /// Only runs of `' '` between two words are changed, so
+    /// whitespace at the end of lines is kept. The text is borrowed
+    /// if nothing changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::SentenceSpacing;
+    ///
+    /// assert_eq!(SentenceSpacing::Double.apply("Yes. No.  Maybe."), "Yes.  No.  Maybe.");
+    /// assert_eq!(SentenceSpacing::Single.apply("Yes. No.  Maybe."), "Yes. No. Maybe.");
+    /// ```
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let spacing = match self {
+            SentenceSpacing::Keep => return Cow::Borrowed(text),
+            SentenceSpacing::Single => " ",
+            SentenceSpacing::Double => "  ",
+        };
+
+        let bytes = text.as_bytes();
+        let mut result = String::new();
+        let mut last = 0;
+        let mut idx = 0;
+        while idx < bytes.len() {
+            if bytes[idx] != b' ' {
+                idx += 1;
+                continue;
+            }
+            let start = idx;
+            while idx < bytes.len() && bytes[idx] == b' ' {
+                idx += 1;
+            }
+
+            let word = text[..start].rsplit(char::is_whitespace).next();
+            let next = text[idx..].chars().next();
+            let is_sentence_break = match (word, next) {
+                (Some(word), Some(next)) => {
+                    is_sentence_end(word)
+                        && !next.is_whitespace()
+                        && (*self == SentenceSpacing::Single || next.is_uppercase())
+                }
+                _ => false,
+            };
+            if is_sentence_break && &text[start..idx] != spacing {
+                result.push_str(&text[last..start]);
+                result.push_str(spacing);
+                last = idx;
+            }
+        }
+
+        if last == 0 {
+            return Cow::Borrowed(text);
+        }
+        result.push_str(&text[last..]);
+        Cow::Owned(result)
+    }
+}
+
+/// The options detected by [`unfill()`] which are kept by
+/// [`refill_with()`].
+///
+/// A field set to `true` means that the value detected in the text
+/// is used, `false` means that the value from the given [`Options`]
+/// is used instead. [`refill()`] uses [`RefillKeep::new()`], which
+/// keeps everything except the width.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{refill_with, LineEnding, Options, RefillKeep};
+///
+/// // Convert the line endings, but keep the "> " indentation:
+/// let keep = RefillKeep { line_ending: false, ..RefillKeep::new() };
+/// let options = Options::new(20).line_ending(LineEnding::CRLF);
+/// assert_eq!(refill_with("> foo\n> bar\n", options, keep), "> foo bar\r\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RefillKeep {
+    /// Keep the width of the widest line in the text.
+    pub width: bool,
+    /// Keep the [`Options::initial_indent`] and
+    /// [`Options::subsequent_indent`] found in the text.
+    pub indents: bool,
+    /// Keep the line ending found in the text. The given
+    /// [`Options::line_ending`] is used if the text has no line
+    /// endings.
+    pub line_ending: bool,
+}
+
+impl RefillKeep {
+    /// Keep the detected indents and line ending, but not the width.
+    pub const fn new() -> Self {
+        RefillKeep {
+            width: false,
+            indents: true,
+            line_ending: true,
+        }
+    }
+
+    /// Keep nothing: all options are taken from the given
+    /// [`Options`].
+    pub const fn nothing() -> Self {
+        RefillKeep {
+            width: false,
+            indents: false,
+            line_ending: false,
+        }
+    }
+}
+
+impl Default for RefillKeep {
+    fn default() -> Self {
+        RefillKeep::new()
+    }
+}
 
 /// Unpack a paragraph of already-wrapped text.
 ///
 /// This function attempts to recover the original text from a single
-/// paragraph of wrapped text, such as what [`fill()`] would produce.
-/// This means that it turns
+/// paragraph of wrapped text, such as what
+/// [`fill()`](crate::fill()) would produce. This means that it turns
 ///
 /// ```text
 /// textwrap: a small
@@ -38,6 +204,16 @@ use crate::{fill, LineEnding, Options};
 /// (`'>'`) in Markdown as well as characters often used for inline
 /// comments (`'#'` and `'/'`).
 ///
+/// Paragraphs with a hanging indent are recognized as well. If the
+/// other lines are indented more than the first line, and the first
+/// line has whitespace where the text of the other lines start, the
+/// start of the first line can be a marker which is returned in
+/// [`Options::initial_indent`]. This is common in numbered lists and
+/// in man pages. The marker must be a list item number such as
+/// `"1. "` or `"a) "`, a bullet (`'-'`, `'*'`, or `'•'`), or
+/// command line options such as `"-v, --verbose  "` or
+/// `"-f FILE  "`. Indented prose is not mistaken for a marker.
+///
 /// The text must come from a single wrapped paragraph. This means
 /// that there can be no empty lines (`"\n\n"` or `"\r\n\r\n"`) within
 /// the text. It is unspecified what happens if `unfill` is called on
@@ -58,47 +234,126 @@ use crate::{fill, LineEnding, Options};
 /// assert_eq!(options.initial_indent, "* ");
 /// assert_eq!(options.subsequent_indent, "  ");
 /// assert_eq!(options.line_ending, LineEnding::LF);
+///
+/// let (text, options) = unfill("\
+/// 1.  This is a
+///     numbered item.
+/// ");
+///
+/// assert_eq!(text, "This is a numbered item.\n");
+/// assert_eq!(options.initial_indent, "1.  ");
+/// assert_eq!(options.subsequent_indent, "    ");
 /// ```
 pub fn unfill(text: &str) -> (String, Options<'_>) {
+    unfill_with_hard_breaks(text, &[])
+}
+
+/// Unpack a paragraph of already-wrapped text, but keep hard line
+/// breaks.
+///
+/// This works like [`unfill()`], except that lines ending with one of
+/// the `hard_break_markers` are not joined with the following line.
+/// Typical markers are two trailing spaces, which is a hard line
+/// break in Markdown, and a trailing `\`. The markers are kept in
+/// the unfilled text and the line breaks use the line ending of the
+/// input.
+///
+/// The markers are returned in [`Options::hard_break_markers`] so
+/// that [`refill()`] can keep the line breaks.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::unfill_with_hard_breaks;
+///
+/// let (text, options) = unfill_with_hard_breaks("\
+/// > Roses are red,\x20\x20
+/// > violets are
+/// > blue.
+/// ", &["  "]);
+///
+/// assert_eq!(text, "Roses are red,  \nviolets are blue.\n");
+/// assert_eq!(options.initial_indent, "> ");
+/// assert_eq!(options.hard_break_markers, &["  "]);
+/// ```
+pub fn unfill_with_hard_breaks<'a>(
+    text: &'a str,
+    hard_break_markers: &'a [&'a str],
+) -> (String, Options<'a>) {
     let prefix_chars: &[_] = &[' ', '-', '+', '*', '>', '#', '/'];
 
-    let mut options = Options::new(0);
-    for (idx, line) in text.lines().enumerate() {
+    let mut options = Options::new(0).hard_break_markers(hard_break_markers);
+    let mut unfilled = String::with_capacity(text.len());
+    let mut detected_line_ending = None;
+    let mut unfilled_lines = 0;
+    let mut separator = None;
+    let mut offset = 0;
+
+    // This is a single pass over the text: the common prefix of the
+    // subsequent lines is narrowed down as we go. Lines which are
+    // already unfilled were stripped of a longer prefix, so in the
+    // rare case where the prefix shrinks, we unfill them again.
+    for (idx, segment) in text.split_inclusive('\n').enumerate() {
+        let (line, ending) = match segment.strip_suffix('\n') {
+            Some(line) => match line.strip_suffix('\r') {
+                Some(line) => (line, Some(LineEnding::CRLF)),
+                None => (line, Some(LineEnding::LF)),
+            },
+            None => (segment, None),
+        };
+
         options.width = std::cmp::max(options.width, display_width(line));
         let without_prefix = line.trim_start_matches(prefix_chars);
         let prefix = &line[..line.len() - without_prefix.len()];
 
         if idx == 0 {
-            options.initial_indent = prefix;
+            options.initial_indent = Cow::Borrowed(prefix);
         } else if idx == 1 {
-            options.subsequent_indent = prefix;
+            options.subsequent_indent = Cow::Borrowed(prefix);
         } else if idx > 1 {
-            for ((idx, x), y) in prefix.char_indices().zip(options.subsequent_indent.chars()) {
-                if x != y {
-                    options.subsequent_indent = &prefix[..idx];
-                    break;
-                }
+            let old_len = options.subsequent_indent.len();
+            let mismatch = prefix
+                .char_indices()
+                .zip(options.subsequent_indent.chars())
+                .find(|((_, x), y)| x != y);
+            if let Some(((idx, _), _)) = mismatch {
+                options.subsequent_indent = Cow::Borrowed(&prefix[..idx]);
             }
             if prefix.len() < options.subsequent_indent.len() {
-                options.subsequent_indent = prefix;
+                options.subsequent_indent = Cow::Borrowed(prefix);
+            }
+            if options.subsequent_indent.len() < old_len && unfilled_lines > 1 {
+                unfilled.clear();
+                let mut separator = None;
+                for (line, ending) in NonEmptyLines(&text[..offset]) {
+                    push_unfilled_line(&mut unfilled, line, separator, &options);
+                    separator = Some(line_separator(line, ending, &options));
+                }
             }
         }
-    }
 
-    let mut unfilled = String::with_capacity(text.len());
-    let mut detected_line_ending = None;
-
-    for (idx, (line, ending)) in NonEmptyLines(text).enumerate() {
-        if idx == 0 {
-            unfilled.push_str(&line[options.initial_indent.len()..]);
-        } else {
-            unfilled.push(' ');
-            unfilled.push_str(&line[options.subsequent_indent.len()..]);
+        if !line.is_empty() {
+            push_unfilled_line(&mut unfilled, line, separator, &options);
+            separator = Some(line_separator(line, ending, &options));
+            unfilled_lines += 1;
+            match (detected_line_ending, ending) {
+                (None, Some(_)) => detected_line_ending = ending,
+                (Some(LineEnding::CRLF), Some(LineEnding::LF)) => detected_line_ending = ending,
+                _ => (),
+            }
         }
-        match (detected_line_ending, ending) {
-            (None, Some(_)) => detected_line_ending = ending,
-            (Some(LineEnding::CRLF), Some(LineEnding::LF)) => detected_line_ending = ending,
-            _ => (),
+
+        offset += segment.len();
+    }
+
+    if unfilled_lines > 1 {
+        if let Some(marker) = text
+            .lines()
+            .next()
+            .and_then(|line| hanging_marker(line, &options))
+        {
+            unfilled.drain(..marker.len() - options.initial_indent.len());
+            options.initial_indent = Cow::Borrowed(marker);
         }
     }
 
@@ -113,15 +368,119 @@ pub fn unfill(text: &str) -> (String, Options<'_>) {
     (unfilled, options)
 }
 
+/// Find the marker of a paragraph with a hanging indent, such as
+/// `"1. "` or `"-f FILE  "`, in the `first_line`. The marker is the
+/// start of the first line which is as wide as the subsequent indent.
+/// It must end with whitespace and the subsequent indent must consist
+/// of spaces which are wider than the initial indent. See
+/// [`is_marker_text`] for the allowed markers.
+fn hanging_marker<'t>(first_line: &'t str, options: &Options<'_>) -> Option<&'t str> {
+    let width = options.subsequent_indent.len();
+    if width <= options.initial_indent.len()
+        || !options.subsequent_indent.bytes().all(|b| b == b' ')
+    {
+        return None;
+    }
+    // The marker can contain wide characters such as '•', so we
+    // look for the end of the first `width` columns.
+    let end = first_line
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .find(|&idx| display_width(&first_line[..idx]) >= width)?;
+    let (marker, rest) = first_line.split_at(end);
+    let is_marker = marker.ends_with(' ')
+        && !rest.starts_with(char::is_whitespace)
+        && !rest.is_empty()
+        && display_width(marker) == width
+        && is_marker_text(marker.trim());
+    is_marker.then_some(marker)
+}
+
+/// Check if `marker` is a list item number followed by `'.'` or
+/// `')'`, a bullet, or starts with a command line option such as
+/// `-x` or `--flag`. Options may be followed by more options and by
+/// arguments, e.g., `"-v, --verbose"` or `"-f FILE"`.
+fn is_marker_text(marker: &str) -> bool {
+    let mut tokens = marker.split_whitespace();
+    let first = match tokens.next() {
+        Some(first) => first,
+        None => return false,
+    };
+    let is_option = |token: &str| {
+        let name = token.strip_prefix("--").or_else(|| token.strip_prefix('-'));
+        name.map_or(false, |name| {
+            let name = name.trim_end_matches(',');
+            let name = name.split('=').next().unwrap_or(name);
+            name.starts_with(|c: char| c.is_alphanumeric())
+                && name.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+    };
+    if is_option(first) {
+        return true;
+    }
+    if tokens.next().is_some() {
+        return false;
+    }
+    let enumerator = first
+        .strip_suffix('.')
+        .or_else(|| first.strip_suffix(')'))
+        .unwrap_or("");
+    matches!(first, "-" | "*" | "•")
+        || (!enumerator.is_empty() && enumerator.chars().all(char::is_alphanumeric))
+}
+
+/// Push `line` to `unfilled` without the indentation from `options`.
+/// The `separator` is pushed first, it is `None` for the first line.
+fn push_unfilled_line(
+    unfilled: &mut String,
+    line: &str,
+    separator: Option<&str>,
+    options: &Options<'_>,
+) {
+    match separator {
+        None => unfilled.push_str(&line[options.initial_indent.len()..]),
+        Some(separator) => {
+            unfilled.push_str(separator);
+            unfilled.push_str(&line[options.subsequent_indent.len()..]);
+        }
+    }
+}
+
+/// The separator between `line` and the following line: a space,
+/// or the line ending if `line` ends with a hard break marker.
+fn line_separator(line: &str, ending: Option<LineEnding>, options: &Options<'_>) -> &'static str {
+    let hard_break = options
+        .hard_break_markers
+        .iter()
+        .any(|marker| !marker.is_empty() && line.ends_with(marker));
+    match ending {
+        Some(ending) if hard_break => ending.as_str(),
+        _ => " ",
+    }
+}
+
 /// Refill a paragraph of wrapped text with a new width.
 ///
 /// This function will first use [`unfill()`] to remove newlines from
-/// the text. Afterwards the text is filled again using [`fill()`].
+/// the text. Afterwards the text is filled again using
+/// [`fill()`](crate::fill()).
 ///
 /// The `new_width_or_options` argument specify the new width and can
 /// specify other options as well — except for
 /// [`Options::initial_indent`] and [`Options::subsequent_indent`],
-/// which are deduced from `filled_text`.
+/// which are deduced from `filled_text`. The line ending found in
+/// `filled_text` is kept unless one is given with
+/// [`Options::line_ending()`]. Use [`refill_with()`] to override the
+/// deduced options.
+///
+/// ```
+/// use textwrap::{refill, LineEnding, Options};
+///
+/// assert_eq!(refill("foo\r\nbar\r\n", 10), "foo bar\r\n");
+///
+/// let options = Options::new(10).line_ending(LineEnding::LF);
+/// assert_eq!(refill("foo\r\nbar\r\n", options), "foo bar\n");
+/// ```
 ///
 /// # Examples
 ///
@@ -151,6 +510,26 @@ pub fn unfill(text: &str) -> (String, Options<'_>) {
 /// ");
 /// ```
 ///
+/// Hard line breaks are kept if you specify their markers with
+/// [`Options::hard_break_markers`]:
+///
+/// ```
+/// use textwrap::{refill, Options};
+///
+/// let text = "\
+/// Roses are red,\x20\x20
+/// violets are blue.
+/// ";
+///
+/// let options = Options::new(12).hard_break_markers(&["  "]);
+/// assert_eq!(refill(text, options), "\
+/// Roses are\n\
+/// red,\x20\x20
+/// violets are
+/// blue.
+/// ");
+/// ```
+///
 /// You can also reshape bullet points:
 ///
 /// ```
@@ -167,18 +546,143 @@ pub fn unfill(text: &str) -> (String, Options<'_>) {
 /// ");
 /// ```
 pub fn refill<'a, Opt>(filled_text: &str, new_width_or_options: Opt) -> String
+where
+    Opt: Into<Options<'a>>,
+{
+    let options = new_width_or_options.into();
+    let keep = RefillKeep {
+        line_ending: !options.has_explicit_line_ending(),
+        ..RefillKeep::new()
+    };
+    refill_with(filled_text, options, keep)
+}
+
+/// Refill a paragraph of wrapped text, keeping the options selected
+/// by `keep`.
+///
+/// This works like [`refill()`], but `keep` decides which of the
+/// options deduced by [`unfill()`] are kept. The other options are
+/// taken from `new_width_or_options`.
+///
+/// Text which is already filled with the resulting options is
+/// returned as it is, without wrapping it again. This is detected
+/// when the width, indentation and line ending match the text and
+/// [`WrapAlgorithm::FirstFit`] is used: the text is filled if the
+/// first word of every line, or the first piece of it found by the
+/// [`WordSplitter`], does not fit at the end of the line before.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{refill_with, Options, RefillKeep, SentenceSpacing};
+///
+/// let text = "\
+/// * One. Two.
+///   Three, four.
+/// ";
+///
+/// // Change the sentence spacing without changing the width:
+/// let options = Options::new(0).sentence_spacing(SentenceSpacing::Double);
+/// let keep = RefillKeep { width: true, ..RefillKeep::new() };
+/// assert_eq!(refill_with(text, options, keep), "\
+/// * One.  Two.
+///   Three, four.
+/// ");
+///
+/// // Replace the list marker with a block quote:
+/// let options = Options::new(20).initial_indent("> ").subsequent_indent("> ");
+/// assert_eq!(refill_with(text, options, RefillKeep::nothing()), "\
+/// > One. Two. Three,
+/// > four.
+/// ");
+/// ```
+pub fn refill_with<'a, Opt>(
+    filled_text: &str,
+    new_width_or_options: Opt,
+    keep: RefillKeep,
+) -> String
 where
     Opt: Into<Options<'a>>,
 {
     let mut new_options = new_width_or_options.into();
-    let (text, options) = unfill(filled_text);
+    let (text, options) = unfill_with_hard_breaks(filled_text, new_options.hard_break_markers);
+    let spaced = match new_options.sentence_spacing.apply(&text) {
+        Cow::Owned(spaced) => Some(spaced),
+        Cow::Borrowed(_) => None,
+    };
+    let text = spaced.as_deref().unwrap_or(&text);
     // The original line ending is kept by `unfill`.
     let stripped = text.strip_suffix(options.line_ending.as_str());
+    if keep.line_ending && filled_text.contains('\n') {
+        new_options.line_ending = options.line_ending;
+    }
     let new_line_ending = new_options.line_ending.as_str();
 
-    new_options.initial_indent = options.initial_indent;
-    new_options.subsequent_indent = options.subsequent_indent;
-    let mut refilled = fill(stripped.unwrap_or(&text), new_options);
+    if keep.width {
+        new_options.width = options.width;
+    }
+
+    if keep.indents {
+        new_options.initial_indent = options.initial_indent.clone();
+        new_options.subsequent_indent = options.subsequent_indent.clone();
+        new_options.indents = &[];
+    }
+
+    // The text is already filled with the new options if the width,
+    // the indentation, and the line ending are unchanged and no line
+    // can take a word from the next line. Mixed line endings are
+    // detected as LF and must still be normalized.
+    if new_options.width == options.width
+        && new_options.indents.is_empty()
+        && new_options.initial_indent == options.initial_indent
+        && new_options.subsequent_indent == options.subsequent_indent
+        && new_options.line_ending == options.line_ending
+        && (options.line_ending == LineEnding::CRLF || !filled_text.contains("\r\n"))
+        && spaced.is_none()
+        && is_filled(filled_text, &new_options)
+    {
+        return String::from(filled_text);
+    }
+    let mut lines = Vec::new();
+    let last_line_reserve = new_options.last_line_reserve;
+    // Lines in the unfilled text end with hard break markers, so we
+    // wrap them one by one and make sure the markers survive. The
+    // markers are removed before wrapping and room is reserved for
+    // them, so they stay on the line of the last word.
+    for (idx, line) in stripped.unwrap_or(text).split('\n').enumerate() {
+        if idx == 1 {
+            new_options.initial_indent = new_options.subsequent_indent.clone();
+        }
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let content = new_options
+            .hard_break_markers
+            .iter()
+            .find(|marker| !marker.is_empty() && line.ends_with(*marker))
+            .map_or(line, |marker| line[..line.len() - marker.len()].trim_end());
+        let suffix = &line[content.len()..];
+        new_options.last_line_reserve = last_line_reserve + display_width(suffix);
+        let mut wrapped = wrap(content, &new_options);
+        if let Some(last) = wrapped.last_mut() {
+            if !suffix.is_empty() {
+                last.to_mut().push_str(suffix);
+            }
+        }
+        lines.extend(wrapped);
+    }
+
+    // Allocate room for the trailing line ending up front. Pushing
+    // it onto the result of `fill` would often reallocate and copy
+    // the entire text a second time.
+    let line_endings = lines.len() - 1 + usize::from(stripped.is_some());
+    let capacity =
+        lines.iter().map(|line| line.len()).sum::<usize>() + line_endings * new_line_ending.len();
+    let mut refilled = String::with_capacity(capacity);
+    for (idx, line) in lines.iter().enumerate() {
+        if idx > 0 {
+            refilled.push_str(new_line_ending);
+        }
+        refilled.push_str(line);
+    }
 
     // Add back right line ending if we stripped one off above.
     if stripped.is_some() {
@@ -187,6 +691,239 @@ where
     refilled
 }
 
+/// Check if [`WrapAlgorithm::FirstFit`] would wrap the text of
+/// `filled_text` into the same lines. This is the case if every line
+/// fits and if the first word of the next line, or the first piece
+/// of it allowed by the word splitter, does not fit after it.
+///
+/// Options which can move words in other ways are not handled, the
+/// text is then assumed to need refilling.
+fn is_filled(filled_text: &str, options: &Options<'_>) -> bool {
+    let simple_options = matches!(options.wrap_algorithm, WrapAlgorithm::FirstFit)
+        && options.width <= options.max_width
+        && options.last_line_reserve == 0
+        && options.compact == Compact::default()
+        && options.indent_width.is_none()
+        && options.drop_whitespace
+        && !options.preserve_inner_whitespace
+        && options.wide_char_padding.is_empty()
+        && options.width_overrides.is_empty()
+        && options.break_filter.is_none()
+        && options.no_break_before.is_empty()
+        && options.no_break_after.is_empty()
+        && options.protected_ranges.is_empty()
+        && options.hard_break_markers.is_empty()
+        && options.sanitize_control_chars == ControlChars::Keep
+        && options.normalization == Normalization::Keep
+        && !options.unicode_separators
+        // Words on the first line are broken to fit the subsequent
+        // lines, see `prepare_words`.
+        && display_width(&options.initial_indent) >= display_width(&options.subsequent_indent);
+    if !simple_options || filled_text.contains('\t') {
+        return false;
+    }
+
+    let mut lines = filled_text.lines().peekable();
+    while let Some(line) = lines.next() {
+        let width = display_width(line);
+        if width > options.width || line.ends_with(' ') {
+            return false;
+        }
+        let next = match lines.peek() {
+            Some(next) if !line.is_empty() && !next.is_empty() => next,
+            _ => continue,
+        };
+        let content = match next.strip_prefix(&*options.subsequent_indent) {
+            Some(content) => content,
+            None => return false,
+        };
+        let word = match options.word_separator.find_words(content).next() {
+            Some(word) if !word.is_empty() => word.word,
+            _ => return false,
+        };
+        // The text is unfilled with a single space between the lines.
+        let first_piece_width = match options.word_splitter.split_points(word).first() {
+            Some(&idx) => {
+                display_width(&word[..idx])
+                    + display_width(hyphenation_penalty(
+                        word,
+                        idx,
+                        options.hyphen,
+                        options.word_splitter.has_unicode_hyphens(),
+                    ))
+            }
+            None => display_width(word),
+        };
+        if width + 1 + first_piece_width <= options.width {
+            return false;
+        }
+    }
+    true
+}
+
+/// Refill lines which are continued with a trailing backslash.
+///
+/// Shell scripts, Makefiles, and C macros use a `\` at the end of a
+/// line to continue a long command on the next line. This function
+/// finds such logical lines, joins them, wraps them with the new
+/// width, and adds the backslashes back. The backslashes are aligned
+/// one space after the widest line of each logical line.
+///
+/// The indentation of the first line of a logical line is kept, and
+/// the indentation of its second line is used for all continuation
+/// lines. Lines which are not continued, such as the last line of a
+/// command, are left unchanged. A line which ends with an even number
+/// of backslashes is not continued since the backslashes escape each
+/// other.
+///
+/// Since the text is code, it is only broken at ASCII spaces, see
+/// [`WordSeparator::AsciiSpace`](crate::WordSeparator::AsciiSpace),
+/// and words are never split or broken. The text is not broken
+/// inside single or double quotes either. Lines are joined like the
+/// shell and the C preprocessor do: a line which continues a word
+/// without any whitespace around the `\` is glued to that word. The
+/// indentation and these options are set by this function, the
+/// other options are used as given. The width includes the trailing
+/// `" \"`.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::refill_continued_lines;
+///
+/// let script = "\
+/// ./configure --prefix=/usr \\
+///     --enable-shared --disable-static \\
+///     --with-pic
+/// make
+/// ";
+///
+/// assert_eq!(refill_continued_lines(script, 30), "\
+/// ./configure --prefix=/usr \\
+///     --enable-shared       \\
+///     --disable-static      \\
+///     --with-pic
+/// make
+/// ");
+/// ```
+pub fn refill_continued_lines<'a, Opt>(text: &str, new_width_or_options: Opt) -> String
+where
+    Opt: Into<Options<'a>>,
+{
+    let mut options = new_width_or_options
+        .into()
+        .word_separator(WordSeparator::AsciiSpace)
+        .word_splitter(WordSplitter::NoHyphenation)
+        .break_words(false);
+    options.width = options.width.saturating_sub(2);
+
+    let mut refilled = String::with_capacity(text.len());
+    let mut continued = Vec::new();
+    for segment in text.split_inclusive('\n') {
+        let line = segment.trim_end_matches(['\r', '\n']);
+        let ending = &segment[line.len()..];
+        let content = line.trim_end();
+        let backslashes = content.len() - content.trim_end_matches('\\').len();
+        if backslashes % 2 == 1 && !ending.is_empty() {
+            continued.push((&content[..content.len() - 1], ending));
+            continue;
+        }
+        if continued.is_empty() {
+            refilled.push_str(segment);
+            continue;
+        }
+
+        continued.push((line, ending));
+        push_continued_lines(&mut refilled, &continued, &mut options);
+        continued.clear();
+    }
+    // The text ended with a continued line: keep it as it is.
+    for (line, ending) in continued {
+        refilled.push_str(line.trim_end());
+        refilled.push_str(" \\");
+        refilled.push_str(ending);
+    }
+
+    refilled
+}
+
+/// Wrap the `continued` lines as a single logical line and push it
+/// with aligned backslashes to `refilled`.
+fn push_continued_lines<'a>(
+    refilled: &mut String,
+    continued: &[(&'a str, &str)],
+    options: &mut Options<'a>,
+) {
+    let indent = |line: &'a str| &line[..line.len() - line.trim_start().len()];
+    let (first_line, first_ending) = continued[0];
+    let (_, last_ending) = continued[continued.len() - 1];
+    options.initial_indent = Cow::Borrowed(indent(first_line));
+    options.subsequent_indent = Cow::Borrowed(indent(continued[1].0));
+
+    // Whitespace around a backslash separates two words, otherwise
+    // the words are glued together.
+    let mut joined = String::new();
+    let mut separated = false;
+    for (line, _) in continued {
+        let words = line.trim();
+        separated |= line.starts_with(char::is_whitespace);
+        if !words.is_empty() {
+            if separated && !joined.is_empty() {
+                joined.push(' ');
+            }
+            joined.push_str(words);
+            separated = false;
+        }
+        separated |= line.ends_with(char::is_whitespace);
+    }
+    let quotes = quoted_ranges(&joined);
+    let options = options.clone().protected_ranges(&quotes);
+    let lines = wrap(&joined, &options);
+    let (last, lines) = lines.split_last().unwrap();
+    let column = lines
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0);
+    for line in lines {
+        refilled.push_str(line);
+        let padding = column - display_width(line);
+        refilled.extend(std::iter::repeat(' ').take(padding));
+        refilled.push_str(" \\");
+        refilled.push_str(first_ending);
+    }
+    refilled.push_str(last);
+    refilled.push_str(last_ending);
+}
+
+/// Find the strings in single or double quotes in `text`. A quote
+/// which is not closed extends to the end of the text.
+fn quoted_ranges(text: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut char_indices = text.char_indices();
+    while let Some((start, ch)) = char_indices.next() {
+        match ch {
+            '\\' => {
+                char_indices.next();
+            }
+            '\'' | '"' => {
+                let mut end = text.len();
+                while let Some((idx, next)) = char_indices.next() {
+                    if next == '\\' && ch == '"' {
+                        char_indices.next();
+                    } else if next == ch {
+                        end = idx + 1;
+                        break;
+                    }
+                }
+                ranges.push(start..end);
+            }
+            _ => {}
+        }
+    }
+    ranges
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,6 +1064,135 @@ mod tests {
         assert_eq!(unfill("foo   bar").0, "foo   bar");
     }
 
+    #[test]
+    fn unfill_hard_breaks() {
+        let (text, options) = unfill_with_hard_breaks("foo\\\r\nbar\r\nbaz", &["\\"]);
+        assert_eq!(text, "foo\\\r\nbar baz");
+        assert_eq!(options.line_ending, LineEnding::CRLF);
+    }
+
+    #[test]
+    fn unfill_hard_breaks_last_line() {
+        let (text, _) = unfill_with_hard_breaks("foo\nbar  ", &["  "]);
+        assert_eq!(text, "foo bar  ");
+    }
+
+    #[test]
+    fn unfill_hard_breaks_shrinking_prefix() {
+        let (text, options) = unfill_with_hard_breaks("> foo  \n> bar  \n> baz\n", &["  "]);
+        assert_eq!(text, "foo  \nbar  \nbaz\n");
+        assert_eq!(options.subsequent_indent, "> ");
+
+        let (text, options) = unfill_with_hard_breaks("# foo  \n# bar  \n#baz\n", &["  "]);
+        assert_eq!(text, "foo  \n bar  \nbaz\n");
+        assert_eq!(options.subsequent_indent, "#");
+    }
+
+    #[test]
+    fn unfill_hanging_indent() {
+        let (text, options) = unfill("  10. foo\n      bar\n      baz\n");
+        assert_eq!(text, "foo bar baz\n");
+        assert_eq!(options.initial_indent, "  10. ");
+        assert_eq!(options.subsequent_indent, "      ");
+    }
+
+    #[test]
+    fn unfill_hanging_indent_needs_marker() {
+        // The first line must have whitespace where the text of the
+        // other lines start.
+        let (text, options) = unfill("foobar\n  baz\n");
+        assert_eq!(text, "foobar baz\n");
+        assert_eq!(options.initial_indent, "");
+        // A single line has no hanging indent.
+        let (text, options) = unfill("1. foo\n");
+        assert_eq!(text, "1. foo\n");
+        assert_eq!(options.initial_indent, "");
+    }
+
+    #[test]
+    fn unfill_hanging_indent_rejects_prose() {
+        let (text, options) = unfill("This is a\n     test\n");
+        assert_eq!(text, "This is a test\n");
+        assert_eq!(options.initial_indent, "");
+        assert_eq!(options.subsequent_indent, "     ");
+        let (text, options) = unfill("SYNOPSIS: wrap a paragraph\n          of text.\n");
+        assert_eq!(text, "SYNOPSIS: wrap a paragraph of text.\n");
+        assert_eq!(options.initial_indent, "");
+    }
+
+    #[test]
+    fn unfill_hanging_indent_markers() {
+        for marker in ["1.  ", "a)  ", "•   ", "-f FILE  ", "--all   "] {
+            let indent = " ".repeat(display_width(marker));
+            let text = format!("{}foo\n{}bar\n", marker, indent);
+            let (text, options) = unfill(&text);
+            assert_eq!(text, "foo bar\n");
+            assert_eq!(options.initial_indent, marker);
+        }
+    }
+
+    #[test]
+    fn refill_man_page_paragraphs() {
+        let text = "       -v, --verbose  Print a message for\n                      each file.\n";
+        assert_eq!(
+            refill(text, 50),
+            "       -v, --verbose  Print a message for each\n                      file.\n"
+        );
+        let text = "\
+SYNOPSIS: wrap a paragraph
+          of text.
+";
+        assert_eq!(
+            refill(text, 20),
+            "SYNOPSIS: wrap a\n          paragraph\n          of text.\n"
+        );
+    }
+
+    #[test]
+    fn refill_continued_lines_c_macro() {
+        let text = "#define MAX(a, b) \\\r\n  ((a) > (b) ? (a) : (b))\r\nint x;\r\n";
+        assert_eq!(
+            refill_continued_lines(text, 20),
+            "#define MAX(a, b)  \\\r\n  ((a) > (b) ? (a) \\\r\n  : (b))\r\nint x;\r\n"
+        );
+    }
+
+    #[test]
+    fn refill_continued_lines_glues_words() {
+        let text = "echo foo\\\nbar baz \\\n  qux\\\n    quux\n";
+        assert_eq!(
+            refill_continued_lines(text, 40),
+            "echo foobar baz qux quux\n"
+        );
+    }
+
+    #[test]
+    fn refill_continued_lines_keeps_quotes() {
+        let text = "echo 'a  b  c' \\\n  \"d \\\" e\" f\n";
+        assert_eq!(
+            refill_continued_lines(text, 12),
+            "echo        \\\n  'a  b  c' \\\n  \"d \\\" e\"  \\\n  f\n"
+        );
+        assert_eq!(quoted_ranges("a 'b' \\'c \"d"), vec![2..5, 10..12]);
+    }
+
+    #[test]
+    fn refill_continued_lines_escaped_backslash() {
+        let text = "echo foo\\\\\necho bar \\\n";
+        assert_eq!(refill_continued_lines(text, 5), text);
+    }
+
+    #[test]
+    fn refill_hard_breaks() {
+        let options = Options::new(10)
+            .hard_break_markers(&["\\"])
+            .wrap_algorithm(crate::WrapAlgorithm::FirstFit);
+        assert_eq!(
+            refill("> foo bar \\\n> baz\n", options),
+            "> foo\n> bar \\\n> baz\n"
+        );
+    }
+
     #[test]
     fn refill_convert_lf_to_crlf() {
         let options = Options::new(5).line_ending(LineEnding::CRLF);
@@ -345,8 +1211,159 @@ mod tests {
         assert_eq!(refill("foo\r\nbar\n", options), "foo\r\nbar\r\n",);
     }
 
+    #[test]
+    fn refill_keeps_detected_line_ending() {
+        assert_eq!(refill("foo\r\nbar\r\n", 10), "foo bar\r\n");
+        assert_eq!(refill("foo\r\nbar\r\n", Options::new(10)), "foo bar\r\n");
+        let options = Options::new(5).line_ending(LineEnding::CRLF);
+        assert_eq!(refill("foo bar", &options), "foo\r\nbar");
+        let keep = RefillKeep::new();
+        assert_eq!(refill_with("foo\nbar\n", &options, keep), "foo\nbar\n");
+    }
+
+    #[test]
+    fn refill_with_keep_width() {
+        let keep = RefillKeep {
+            width: true,
+            ..RefillKeep::new()
+        };
+        assert_eq!(
+            refill_with("> foo bar\n> baz", 80, keep),
+            "> foo bar\n> baz"
+        );
+    }
+
     #[test]
     fn refill_defaults_to_lf() {
         assert_eq!(refill("foo bar baz", 5), "foo\nbar\nbaz");
     }
+
+    #[test]
+    fn refill_full_lines_unchanged() {
+        let options = Options::new(9).wrap_algorithm(WrapAlgorithm::FirstFit);
+        let text = "> foo bar\r\n> baz qux\r\n> end\r\n";
+        assert_eq!(refill(text, &options), text);
+        let keep = RefillKeep {
+            width: true,
+            ..RefillKeep::new()
+        };
+        assert_eq!(refill_with(text, &options, keep), text);
+    }
+
+    #[test]
+    fn refill_filled_lines_unchanged() {
+        let options = Options::new(12).wrap_algorithm(WrapAlgorithm::FirstFit);
+        let text = "foo bar baz\nbazquuxxx\nfoo-bar";
+        assert!(is_filled(text, &options));
+        assert_eq!(refill(text, &options), text);
+        // The "foo-" piece fits after "bazquux".
+        let text = "foo bar baz\nbazquux\nfoo-bar";
+        assert!(!is_filled(text, &options));
+        assert_eq!(refill(text, &options), "foo bar baz\nbazquux foo-\nbar");
+        #[cfg(feature = "smawk")]
+        {
+            let options = options.wrap_algorithm(WrapAlgorithm::new_optimal_fit());
+            assert!(!is_filled(text, &options));
+        }
+    }
+
+    #[test]
+    fn refill_loose_lines_not_unchanged() {
+        let options = Options::new(9).wrap_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(
+            refill("> foo bar\n> a\n> b\n", &options),
+            "> foo bar\n> a b\n"
+        );
+        assert_eq!(refill("foo\r\nbar\n", 3), "foo\nbar\n");
+    }
+
+    #[test]
+    fn sentence_spacing_edge_cases() {
+        assert!(matches!(
+            SentenceSpacing::Double.apply(""),
+            Cow::Borrowed("")
+        ));
+        assert!(matches!(
+            SentenceSpacing::Single.apply("One. Two.  "),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            SentenceSpacing::Double.apply(" \"Quoted.\" Next"),
+            " \"Quoted.\"  Next"
+        );
+        assert_eq!(
+            SentenceSpacing::Double.apply("End.\nNext. "),
+            "End.\nNext. "
+        );
+    }
+
+    #[test]
+    fn refill_with_double_sentence_spacing() {
+        let options = Options::new(12).sentence_spacing(SentenceSpacing::Double);
+        assert_eq!(
+            refill("> One. Two.\n> Three.", &options),
+            "> One.  Two.\n> Three."
+        );
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn unfill_reverses_fill(
+            text in crate::proptest_support::single_spaced_words(),
+            width in 1..60usize,
+        ) {
+            let options = Options::new(width)
+                .word_separator(crate::WordSeparator::AsciiSpace)
+                .word_splitter(crate::WordSplitter::NoHyphenation)
+                .break_words(false);
+            let (unfilled, _) = unfill(&crate::fill(&text, &options));
+            proptest::prop_assert_eq!(unfilled, text);
+        }
+
+        #[test]
+        fn refill_matches_fill(
+            text in crate::proptest_support::single_spaced_words(),
+            width in 1..60usize,
+            new_width in 1..60usize,
+        ) {
+            let options = Options::new(width)
+                .word_separator(crate::WordSeparator::AsciiSpace)
+                .word_splitter(crate::WordSplitter::NoHyphenation)
+                .break_words(false);
+            let filled = crate::fill(&text, &options);
+            let new_options = options.width(new_width);
+            proptest::prop_assert_eq!(refill(&filled, new_options.clone()), crate::fill(&text, &new_options));
+        }
+
+        #[test]
+        fn is_filled_matches_fill(
+            text in crate::proptest_support::text(),
+            width in 1..60usize,
+        ) {
+            let options = Options::new(width)
+                .word_separator(crate::WordSeparator::AsciiSpace)
+                .wrap_algorithm(WrapAlgorithm::FirstFit);
+            // Like `refill_with`, use the indentation found by `unfill`.
+            let (unfilled, detected) = unfill(&text);
+            let options = options
+                .initial_indent(detected.initial_indent)
+                .subsequent_indent(detected.subsequent_indent);
+            if is_filled(&text, &options) {
+                proptest::prop_assert_eq!(crate::fill(&unfilled, &options), text);
+            }
+        }
+
+        #[test]
+        fn filled_text_is_filled(
+            text in crate::proptest_support::single_spaced_words(),
+            width in 1..60usize,
+        ) {
+            let options = Options::new(width)
+                .word_separator(crate::WordSeparator::AsciiSpace)
+                .wrap_algorithm(WrapAlgorithm::FirstFit)
+                .break_words(false);
+            let filled = crate::fill(&text, &options);
+            proptest::prop_assert!(is_filled(&filled, &options) || filled.lines().any(|line| line.len() > width));
+        }
+    }
 }
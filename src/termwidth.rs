@@ -1,6 +1,10 @@
 //! Functions related to the terminal size.
 
-use crate::Options;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{fill, wrap, Options};
 
 /// Return the current terminal width.
 ///
@@ -25,7 +29,105 @@ use crate::Options;
 /// **Note:** Only available when the `terminal_size` Cargo feature is
 /// enabled.
 pub fn termwidth() -> usize {
-    terminal_size::terminal_size().map_or(80, |(terminal_size::Width(w), _)| w.into())
+    termwidth_opt().unwrap_or(80)
+}
+
+/// Return the current terminal width, or `None` if it cannot be
+/// determined.
+///
+/// Unlike [`termwidth()`], this does not fall back to a default
+/// width. This lets you tell if a terminal is connected, e.g., to
+/// disable wrapping when the output is piped to another program.
+///
+/// # Examples
+///
+/// ```no_run
+/// use textwrap::{fill, termwidth_opt};
+///
+/// let text = "Output which is only wrapped in a terminal.";
+/// match termwidth_opt() {
+///     Some(width) => println!("{}", fill(text, width)),
+///     None => println!("{}", text),
+/// }
+/// ```
+///
+/// **Note:** Only available when the `terminal_size` Cargo feature is
+/// enabled.
+pub fn termwidth_opt() -> Option<usize> {
+    terminal_size::terminal_size().map(|(terminal_size::Width(w), _)| w.into())
+}
+
+/// Return the terminal width, preferring the `COLUMNS` environment
+/// variable.
+///
+/// Many shells and command-line frameworks use `COLUMNS` to let users
+/// override the width of the output. This is also useful when no
+/// terminal is connected, e.g., in CI environments. If `COLUMNS` is
+/// unset or is not a positive integer, this falls back to
+/// [`termwidth()`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use textwrap::{termwidth_with_env, Options};
+///
+/// // Running the program with `COLUMNS=40` gives a width of 40.
+/// let options = Options::new(termwidth_with_env());
+/// ```
+///
+/// **Note:** Only available when the `terminal_size` Cargo feature is
+/// enabled.
+pub fn termwidth_with_env() -> usize {
+    let columns = std::env::var("COLUMNS").ok();
+    parse_columns(columns.as_deref()).unwrap_or_else(termwidth)
+}
+
+/// Parse the value of the `COLUMNS` environment variable.
+fn parse_columns(value: Option<&str>) -> Option<usize> {
+    value
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|&width| width > 0)
+}
+
+/// Return the width of the terminal connected to `fd`.
+///
+/// Unlike [`termwidth()`], which looks for a terminal connected to
+/// the standard streams, this function only looks at `fd`. Use this
+/// if you print wrapped text to, e.g., the standard error while the
+/// standard output may be redirected to a file. If the terminal width
+/// cannot be determined, a default width of 80 characters will be
+/// used.
+///
+/// # Examples
+///
+/// ```no_run
+/// use textwrap::{termwidth_of, Options};
+///
+/// let options = Options::new(termwidth_of(std::io::stderr()));
+/// eprintln!("{}", textwrap::fill("Warning: something happened.", &options));
+/// ```
+///
+/// **Note:** Only available when the `terminal_size` Cargo feature is
+/// enabled.
+#[cfg(unix)]
+pub fn termwidth_of<Fd: std::os::fd::AsFd>(fd: Fd) -> usize {
+    terminal_size::terminal_size_of(fd).map_or(80, |(terminal_size::Width(w), _)| w.into())
+}
+
+/// Return the width of the terminal connected to `handle`.
+///
+/// Unlike [`termwidth()`], which looks for a terminal connected to
+/// the standard streams, this function only looks at `handle`. Use
+/// this if you print wrapped text to, e.g., the standard error while
+/// the standard output may be redirected to a file. If the terminal
+/// width cannot be determined, a default width of 80 characters will
+/// be used.
+///
+/// **Note:** Only available when the `terminal_size` Cargo feature is
+/// enabled.
+#[cfg(windows)]
+pub fn termwidth_of<Handle: std::os::windows::io::AsHandle>(handle: Handle) -> usize {
+    terminal_size::terminal_size_of(handle).map_or(80, |(terminal_size::Width(w), _)| w.into())
 }
 
 impl<'a> Options<'a> {
@@ -49,4 +151,251 @@ impl<'a> Options<'a> {
     pub fn with_termwidth() -> Self {
         Self::new(termwidth())
     }
+
+    /// Creates a new [`Options`] with `width` set to the current
+    /// terminal width, or to `fallback` if the terminal width cannot
+    /// be determined. Other settings use the same defaults as
+    /// [`Options::new`].
+    ///
+    /// Equivalent to:
+    ///
+    /// ```no_run
+    /// use textwrap::{termwidth_opt, Options};
+    ///
+    /// let options = Options::new(termwidth_opt().unwrap_or(100));
+    /// ```
+    ///
+    /// **Note:** Only available when the `terminal_size` feature is
+    /// enabled.
+    pub fn with_termwidth_or(fallback: usize) -> Self {
+        Self::new(termwidth_opt().unwrap_or(fallback))
+    }
+
+    /// Creates a new [`Options`] with `width` set to the current
+    /// terminal width, but at most `max_width`. Lines longer than
+    /// 80–100 columns are hard to read, so this lets you use the
+    /// terminal width on narrow terminals without producing overly
+    /// long lines on very wide terminals.
+    ///
+    /// Equivalent to:
+    ///
+    /// ```no_run
+    /// use textwrap::Options;
+    ///
+    /// let options = Options::with_termwidth().max_width(100);
+    /// ```
+    ///
+    /// **Note:** Only available when the `terminal_size` feature is
+    /// enabled.
+    pub fn with_termwidth_capped(max_width: usize) -> Self {
+        Self::with_termwidth().max_width(max_width)
+    }
+
+    /// Creates a new [`Options`] suitable for text shown in a
+    /// terminal. This is a preset for command line programs.
+    ///
+    /// The width is taken from the `COLUMNS` environment variable, if
+    /// set, and otherwise from the terminal, see
+    /// [`termwidth_with_env()`]. The width is capped at 100 columns
+    /// since longer lines are hard to read. Other settings use the
+    /// same defaults as [`Options::new`].
+    ///
+    /// Equivalent to:
+    ///
+    /// ```no_run
+    /// use textwrap::{termwidth_with_env, Options};
+    ///
+    /// let options = Options::new(termwidth_with_env()).max_width(100);
+    /// ```
+    ///
+    /// **Note:** Only available when the `terminal_size` feature is
+    /// enabled.
+    pub fn for_terminal() -> Self {
+        Self::new(termwidth_with_env()).max_width(100)
+    }
+
+    /// Creates a new [`Options`] with `width` set to the width of the
+    /// terminal connected to `stream`. See [`termwidth_of()`] for
+    /// details. Other settings use the same defaults as
+    /// [`Options::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use textwrap::Options;
+    ///
+    /// let options = Options::with_termwidth_of(std::io::stderr());
+    /// ```
+    ///
+    /// **Note:** Only available when the `terminal_size` feature is
+    /// enabled.
+    #[cfg(unix)]
+    pub fn with_termwidth_of<Fd: std::os::fd::AsFd>(stream: Fd) -> Self {
+        Self::new(termwidth_of(stream))
+    }
+
+    /// Creates a new [`Options`] with `width` set to the width of the
+    /// terminal connected to `stream`. See [`termwidth_of()`] for
+    /// details. Other settings use the same defaults as
+    /// [`Options::new`].
+    ///
+    /// **Note:** Only available when the `terminal_size` feature is
+    /// enabled.
+    #[cfg(windows)]
+    pub fn with_termwidth_of<Handle: std::os::windows::io::AsHandle>(stream: Handle) -> Self {
+        Self::new(termwidth_of(stream))
+    }
+}
+
+/// Wraps text at the terminal width and follows resizes.
+///
+/// Long-running programs should re-wrap their output when the
+/// terminal is resized. A `TerminalWrapper` caches the terminal
+/// width so that the terminal is not queried every time text is
+/// wrapped. Call [`TerminalWrapper::refresh()`] to query the width
+/// again, or set the [`TerminalWrapper::resize_flag()`] when the
+/// terminal has been resized. The width is then refreshed the next
+/// time the wrapper is used.
+///
+/// On Unix, the program is sent a `SIGWINCH` signal when the
+/// terminal is resized. This crate has no `unsafe` code and can
+/// therefore not install a signal handler itself. Instead, register
+/// the resize flag with a crate such as [signal-hook]:
+///
+/// ```text
+/// signal_hook::flag::register(signal_hook::consts::SIGWINCH, wrapper.resize_flag())?;
+/// ```
+///
+/// The [`Options::width`] is replaced by the terminal width, see
+/// [`termwidth()`]. Use [`Options::max_width`] to limit the width
+/// on very wide terminals.
+///
+/// **Note:** Only available when the `terminal_size` Cargo feature is
+/// enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::atomic::Ordering;
+/// use textwrap::{Options, TerminalWrapper};
+///
+/// let mut wrapper = TerminalWrapper::new(Options::new(80).max_width(100));
+/// let resized = wrapper.resize_flag();
+/// println!("{}", wrapper.fill("Text which is re-wrapped after resizes."));
+///
+/// // Typically done by a signal handler:
+/// resized.store(true, Ordering::Relaxed);
+/// println!("{}", wrapper.fill("The terminal width is queried again."));
+/// ```
+///
+/// [signal-hook]: https://docs.rs/signal-hook/
+#[derive(Debug)]
+pub struct TerminalWrapper<'a> {
+    options: Options<'a>,
+    resized: Arc<AtomicBool>,
+}
+
+impl<'a> TerminalWrapper<'a> {
+    /// Create a new wrapper. The width of `options` is replaced by
+    /// the current terminal width.
+    pub fn new(options: Options<'a>) -> Self {
+        let mut wrapper = TerminalWrapper {
+            options,
+            resized: Arc::new(AtomicBool::new(false)),
+        };
+        wrapper.refresh();
+        wrapper
+    }
+
+    /// Query the terminal width again. The new width, limited by
+    /// [`Options::max_width`], is returned.
+    pub fn refresh(&mut self) -> usize {
+        self.resized.store(false, Ordering::Relaxed);
+        let options = std::mem::replace(&mut self.options, Options::new(0));
+        self.options = options.width(termwidth());
+        self.options.width
+    }
+
+    /// Flag which tells the wrapper that the terminal has been
+    /// resized. Set it to `true`, e.g., from a `SIGWINCH` handler,
+    /// and the width is refreshed the next time the wrapper is used.
+    pub fn resize_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.resized)
+    }
+
+    /// Refresh the width if the terminal has been resized.
+    fn sync(&mut self) {
+        if self.resized.load(Ordering::Relaxed) {
+            self.refresh();
+        }
+    }
+
+    /// The current terminal width.
+    pub fn width(&mut self) -> usize {
+        self.sync();
+        self.options.width
+    }
+
+    /// The options used for wrapping, with the current terminal
+    /// width.
+    pub fn options(&mut self) -> &Options<'a> {
+        self.sync();
+        &self.options
+    }
+
+    /// Wrap `text` at the current terminal width, see
+    /// [`wrap()`](crate::wrap()).
+    pub fn wrap<'t>(&mut self, text: &'t str) -> Vec<Cow<'t, str>> {
+        wrap(text, self.options())
+    }
+
+    /// Fill `text` at the current terminal width, see [`fill()`].
+    pub fn fill(&mut self, text: &str) -> String {
+        fill(text, self.options())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terminal_wrapper_resize_flag() {
+        let mut wrapper = TerminalWrapper::new(Options::new(10));
+        assert_eq!(wrapper.width(), termwidth());
+        wrapper.options.width = 10;
+        assert_eq!(wrapper.width(), 10);
+        wrapper.resize_flag().store(true, Ordering::Relaxed);
+        assert_eq!(wrapper.width(), termwidth());
+        assert!(!wrapper.resized.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn terminal_wrapper_resize_respects_max_width() {
+        let max_width = termwidth().min(5);
+        let mut wrapper = TerminalWrapper::new(Options::new(10).max_width(max_width));
+        assert_eq!(wrapper.width(), max_width);
+        wrapper.options.width = 1;
+        wrapper.resize_flag().store(true, Ordering::Relaxed);
+        assert_eq!(wrapper.width(), max_width);
+        assert!(wrapper
+            .fill("abcdef ghijkl")
+            .lines()
+            .all(|line| line.len() <= max_width));
+    }
+
+    #[test]
+    fn parse_columns_valid() {
+        assert_eq!(parse_columns(Some("42")), Some(42));
+        assert_eq!(parse_columns(Some(" 120\n")), Some(120));
+    }
+
+    #[test]
+    fn parse_columns_invalid() {
+        assert_eq!(parse_columns(None), None);
+        assert_eq!(parse_columns(Some("")), None);
+        assert_eq!(parse_columns(Some("0")), None);
+        assert_eq!(parse_columns(Some("-10")), None);
+        assert_eq!(parse_columns(Some("wide")), None);
+    }
 }
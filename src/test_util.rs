@@ -0,0 +1,137 @@
+//! Helpers for testing wrapped text.
+
+use crate::core::display_width;
+use crate::debug_ruler;
+
+/// Compare wrapped lines with an expected block of text.
+///
+/// The `expected` text is split into lines with [`str::lines()`],
+/// which means that a trailing newline is ignored. Trailing
+/// whitespace is removed from every line before the comparison. This
+/// makes it convenient to write the expected output as a multi-line
+/// string literal.
+///
+/// Returns `None` if the lines are equal. Otherwise, a description
+/// of the first difference is returned. The description shows the
+/// differing lines below a [`debug_ruler()`] with a `^` under the
+/// first differing column. This is used by [`assert_wrapped_eq!`].
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{diff_wrapped, wrap};
+///
+/// let lines = wrap("Hello, World!", 8);
+/// assert_eq!(diff_wrapped(&lines, "Hello,\nWorld!\n"), None);
+/// assert_eq!(diff_wrapped(&lines, "Hello,\nWorld?"), Some(String::from("\
+/// line 2, column 6:
+///           ....:.
+///   actual: World!
+/// expected: World?
+///                ^")));
+/// ```
+///
+/// [`assert_wrapped_eq!`]: crate::assert_wrapped_eq
+pub fn diff_wrapped<S: AsRef<str>>(actual: &[S], expected: &str) -> Option<String> {
+    let actual = actual
+        .iter()
+        .map(|line| line.as_ref().trim_end())
+        .collect::<Vec<_>>();
+    let expected = expected.lines().map(str::trim_end).collect::<Vec<_>>();
+
+    let row = (0..std::cmp::max(actual.len(), expected.len()))
+        .find(|&row| actual.get(row) != expected.get(row))?;
+    let actual_line = actual.get(row).copied();
+    let expected_line = expected.get(row).copied();
+
+    let common = match (actual_line, expected_line) {
+        (Some(a), Some(e)) => {
+            let len = a
+                .char_indices()
+                .zip(e.chars())
+                .find(|((_, a), e)| a != e)
+                .map_or(std::cmp::min(a.len(), e.len()), |((idx, _), _)| idx);
+            display_width(&a[..len])
+        }
+        _ => 0,
+    };
+
+    let width = [actual_line, expected_line]
+        .iter()
+        .map(|line| line.map_or(0, display_width))
+        .max()
+        .unwrap_or(0);
+    let width = std::cmp::max(width, common + 1);
+
+    Some(format!(
+        "line {}, column {}:\n          {}\n  actual: {}\nexpected: {}\n          {}^",
+        row + 1,
+        common + 1,
+        debug_ruler(width),
+        actual_line.unwrap_or("<missing>"),
+        expected_line.unwrap_or("<missing>"),
+        " ".repeat(common),
+    ))
+}
+
+/// Assert that wrapped lines are equal to a block of text.
+///
+/// The lines and the text are compared with [`diff_wrapped()`],
+/// which ignores trailing whitespace. On failure, the macro panics
+/// with a message which points out the first difference. This is
+/// easier to read than the output of [`assert_eq!`] when lines are
+/// long or differ by a single character.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{assert_wrapped_eq, wrap, Options, WrapAlgorithm};
+///
+/// let options = Options::new(15).wrap_algorithm(WrapAlgorithm::FirstFit);
+/// assert_wrapped_eq!(wrap("Wrapping text is fun and easy!", &options), "
+/// Wrapping text
+/// is fun and
+/// easy!
+/// ".trim_start());
+/// ```
+#[macro_export]
+macro_rules! assert_wrapped_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        if let Some(diff) = $crate::diff_wrapped(&$actual, $expected) {
+            panic!("wrapped lines differ at {}", diff);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_wrapped_trailing_whitespace() {
+        assert_eq!(diff_wrapped(&["foo  ", "bar"], "foo\nbar  \n"), None);
+        assert_eq!(diff_wrapped::<&str>(&[], ""), None);
+    }
+
+    #[test]
+    fn diff_wrapped_missing_line() {
+        assert_eq!(
+            diff_wrapped(&["foo"], "foo\nbar"),
+            Some(String::from(
+                "line 2, column 1:\n          ...\n  actual: <missing>\nexpected: bar\n          ^"
+            ))
+        );
+    }
+
+    #[test]
+    fn diff_wrapped_wide_chars() {
+        let diff = diff_wrapped(&["你好世界"], "你好地球").unwrap();
+        assert!(diff.starts_with("line 1, column 5:"), "{}", diff);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrapped lines differ at line 1, column 3")]
+    fn assert_wrapped_eq_panics() {
+        assert_wrapped_eq!(["foo"], "fox");
+    }
+}
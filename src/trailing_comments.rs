@@ -0,0 +1,197 @@
+//! Wrapping of lines with trailing comments.
+
+use std::borrow::Cow;
+
+use crate::core::display_width;
+use crate::{wrap, Options};
+
+/// Describes the trailing comments handled by
+/// [`wrap_trailing_comments()`].
+///
+/// A trailing comment starts with a marker such as `#` or `//` and
+/// follows some content on the same line. Lines which consist of a
+/// comment only are not considered to have a trailing comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrailingComment<'a> {
+    /// The marker which starts a comment. Set with
+    /// [`TrailingComment::new`].
+    pub marker: &'a str,
+    /// Text put in the comment column of the continuation lines of a
+    /// wrapped line. Set with [`TrailingComment::continuation`].
+    pub continuation: Option<&'a str>,
+}
+
+impl<'a> TrailingComment<'a> {
+    /// Creates a new description of trailing comments starting with
+    /// `marker`. By default, the comment is kept on the first line of
+    /// the wrapped content and the continuation lines have no
+    /// comment.
+    pub const fn new(marker: &'a str) -> Self {
+        TrailingComment {
+            marker,
+            continuation: None,
+        }
+    }
+
+    /// Change [`self.continuation`]. The `continuation` text is put
+    /// in the comment column of every line after the first when the
+    /// content of a commented line is wrapped. This is typically the
+    /// comment marker followed by a symbol such as `...`.
+    ///
+    /// [`self.continuation`]: #structfield.continuation
+    pub const fn continuation(self, continuation: &'a str) -> Self {
+        TrailingComment {
+            continuation: Some(continuation),
+            ..self
+        }
+    }
+
+    /// Split `line` into content and trailing comment. The
+    /// whitespace between the two stays with the content.
+    fn split<'b>(&self, line: &'b str) -> Option<(&'b str, &'b str)> {
+        if self.marker.is_empty() {
+            return None;
+        }
+        let idx = line.find(self.marker)?;
+        let (content, comment) = line.split_at(idx);
+        if content.trim().is_empty() {
+            return None;
+        }
+        Some((content, comment.trim_end()))
+    }
+
+    /// Check if `line` consists of a comment only.
+    fn is_comment_only(&self, line: &str) -> bool {
+        !self.marker.is_empty() && line.trim_start().starts_with(self.marker)
+    }
+}
+
+/// Wrap lines of text while keeping trailing comments aligned.
+///
+/// This is meant for configuration files and similar formats where
+/// comments are aligned in a column to the right of the content:
+///
+/// ```text
+/// timeout = 30       # seconds
+/// retries = 5        # before giving up
+/// ```
+///
+/// The content of each line is wrapped with [`wrap()`] using
+/// `width_or_options`, this width does not include the comments.
+/// The comment is then re-attached to the first wrapped line. The
+/// comment column is the right-most column where a trailing comment
+/// starts in `text`. A wrapped line which reaches the comment column
+/// is separated from its comment by a single space. Lines which
+/// consist of a comment only are passed through unchanged, since
+/// wrapping them would leave the continuation lines without the
+/// comment marker.
+///
+/// The comment marker is found by simple substring search. It is not
+/// recognized inside quoted strings or similar constructs.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap_trailing_comments, TrailingComment};
+///
+/// let text = "\
+/// include = src tests benches  # directories to scan
+/// exclude = target             # build output";
+///
+/// let comment = TrailingComment::new("#");
+/// assert_eq!(wrap_trailing_comments(text, &comment, 20),
+///            vec!["include = src tests          # directories to scan",
+///                 "benches",
+///                 "exclude = target             # build output"]);
+///
+/// let comment = comment.continuation("# ...");
+/// assert_eq!(wrap_trailing_comments(text, &comment, 20),
+///            vec!["include = src tests          # directories to scan",
+///                 "benches                      # ...",
+///                 "exclude = target             # build output"]);
+/// ```
+pub fn wrap_trailing_comments<'a, Opt>(
+    text: &str,
+    comment: &TrailingComment<'_>,
+    width_or_options: Opt,
+) -> Vec<String>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options = width_or_options.into();
+    let column = text
+        .lines()
+        .filter_map(|line| comment.split(line))
+        .map(|(content, _)| display_width(content))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let (content, trailing) = match comment.split(line) {
+            Some(split) => split,
+            None if comment.is_comment_only(line) => {
+                lines.push(line.to_string());
+                continue;
+            }
+            None => {
+                lines.extend(wrap(line, &options).into_iter().map(Cow::into_owned));
+                continue;
+            }
+        };
+        for (idx, wrapped) in wrap(content, &options).into_iter().enumerate() {
+            let trailing = match (idx, comment.continuation) {
+                (0, _) => trailing,
+                (_, Some(continuation)) => continuation,
+                (_, None) => {
+                    lines.push(wrapped.into_owned());
+                    continue;
+                }
+            };
+            let padding = std::cmp::max(column.saturating_sub(display_width(&wrapped)), 1);
+            lines.push(format!("{}{}{}", wrapped, " ".repeat(padding), trailing));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_without_comments() {
+        let comment = TrailingComment::new("#");
+        assert_eq!(
+            wrap_trailing_comments("foo bar baz\n  # a comment only line", &comment, 10),
+            vec!["foo bar", "baz", "  # a comment only line"]
+        );
+    }
+
+    #[test]
+    fn comment_column_is_kept() {
+        let comment = TrailingComment::new("//");
+        assert_eq!(
+            wrap_trailing_comments("a = 1;    // one\nbb = 22;  // two  ", &comment, 80),
+            vec!["a = 1;    // one", "bb = 22;  // two"]
+        );
+    }
+
+    #[test]
+    fn comments_are_aligned() {
+        let comment = TrailingComment::new("#");
+        assert_eq!(
+            wrap_trailing_comments("foo #x\nfoobar #y", &comment, 80),
+            vec!["foo    #x", "foobar #y"]
+        );
+    }
+
+    #[test]
+    fn empty_marker() {
+        let comment = TrailingComment::new("");
+        assert_eq!(
+            wrap_trailing_comments("foo bar", &comment, 3),
+            vec!["foo", "bar"]
+        );
+    }
+}
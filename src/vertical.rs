@@ -0,0 +1,174 @@
+//! Experimental vertical layout of text.
+//!
+//! Chinese, Japanese, and Korean text is traditionally written in
+//! vertical columns which are read top-to-bottom, with the columns
+//! read right-to-left. This is known as _tategaki_ in Japanese.
+//!
+//! The functions here lay out text like this in a monospace grid.
+//! Each character takes up one row and the [`Options::width`] is used
+//! as the height of the columns. The words are found, split, and
+//! wrapped exactly like [`wrap()`](crate::wrap()) does it, except
+//! that the [`Fragment`](crate::core::Fragment) width of a word is
+//! its height in rows. This is done with a
+//! [`ClosureWidth`] which counts
+//! characters.
+//!
+//! This module is experimental: punctuation is not rotated or moved
+//! and the API may change in a future version.
+//!
+//! # Examples
+//!
+//! ```
+//! use textwrap::vertical::{fill_vertical, wrap_vertical};
+//!
+//! let text = "春眠不覚暁処処聞啼鳥";
+//! assert_eq!(wrap_vertical(text, 5), vec!["春眠不覚暁", "処処聞啼鳥"]);
+//!
+//! # #[cfg(feature = "unicode-width")]
+//! assert_eq!(fill_vertical(text, 5), "\
+//! 処春
+//! 処眠
+//! 聞不
+//! 啼覚
+//! 鳥暁");
+//! ```
+
+use crate::core::ch_width;
+use crate::measure::ClosureWidth;
+use crate::Options;
+
+/// Wrap text into vertical columns.
+///
+/// The columns are returned in reading order, i.e., the first column
+/// is the rightmost one. Each column is read from the top to the
+/// bottom and is at most [`Options::width`] characters high. Each
+/// line of `text` starts a new column. Indentation is not supported
+/// and the indents in the options are ignored.
+///
+/// Use [`fill_vertical()`] to draw the columns in a grid.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::vertical::wrap_vertical;
+///
+/// assert_eq!(wrap_vertical("天気\n晴れ", 5), vec!["天気", "晴れ"]);
+/// assert_eq!(wrap_vertical("foo bar", 3), vec!["foo", "bar"]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if [`WrapAlgorithm::OptimalFit`](crate::WrapAlgorithm::OptimalFit)
+/// overflows, see [`wrap()`](crate::wrap()).
+pub fn wrap_vertical<'a, Opt>(text: &str, height_or_options: Opt) -> Vec<String>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options: Options = height_or_options.into();
+    // This cannot fail since `MinWidth::Error` is replaced.
+    let options = options.proceed_on_min_width_error().build().unwrap();
+    let height = std::cmp::max(options.width, 1);
+    let measure = ClosureWidth::new(|text: &str| text.chars().count() as f64);
+    let lines = measure.wrap(text, height as f64, &options).unwrap();
+
+    lines
+        .iter()
+        .map(|words| {
+            let mut column = String::new();
+            for (idx, word) in words.iter().enumerate() {
+                column.push_str(word.word.word);
+                if idx + 1 < words.len() {
+                    column.push_str(word.word.whitespace);
+                } else {
+                    column.push_str(word.word.penalty);
+                }
+            }
+            column
+        })
+        .collect()
+}
+
+/// Fill text into vertical columns and draw them in a grid.
+///
+/// The columns from [`wrap_vertical()`] are placed right-to-left
+/// and every character is drawn in a cell which is two columns wide.
+/// Narrow characters are padded with a `' '` so that the rows line
+/// up. Trailing whitespace is removed from the rows, which are joined
+/// with `'\n'`.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::vertical::fill_vertical;
+///
+/// # #[cfg(feature = "unicode-width")]
+/// assert_eq!(fill_vertical("日本語\nab", 3), "a 日\nb 本\n  語");
+/// ```
+pub fn fill_vertical<'a, Opt>(text: &str, height_or_options: Opt) -> String
+where
+    Opt: Into<Options<'a>>,
+{
+    let columns = wrap_vertical(text, height_or_options)
+        .iter()
+        .map(|column| column.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut result = String::new();
+    for row in 0..rows {
+        if row > 0 {
+            result.push('\n');
+        }
+        let start = result.len();
+        for column in columns.iter().rev() {
+            match column.get(row) {
+                Some(&ch) => {
+                    result.push(ch);
+                    if ch_width(ch) < 2 {
+                        result.push(' ');
+                    }
+                }
+                None => result.push_str("  "),
+            }
+        }
+        let trimmed = result[start..].trim_end().len();
+        result.truncate(start + trimmed);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_vertical_empty() {
+        assert_eq!(wrap_vertical("", 3), vec![""]);
+        assert_eq!(fill_vertical("", 3), "");
+    }
+
+    #[test]
+    fn wrap_vertical_long_word() {
+        assert_eq!(wrap_vertical("abcdefg", 3), vec!["abc", "def", "g"]);
+        let options = Options::new(3).break_words(false);
+        assert_eq!(wrap_vertical("abcdefg", &options), vec!["abcdefg"]);
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "smawk",
+        feature = "unicode-linebreak",
+        feature = "unicode-width"
+    ))]
+    fn wrap_vertical_punctuation() {
+        // A line break is never allowed before "。".
+        assert_eq!(
+            wrap_vertical("今日は晴れ。明日は雨。", 5),
+            vec!["今日は晴", "れ。明日は", "雨。"]
+        );
+        assert_eq!(
+            fill_vertical("今日は晴れ。明日は雨。", 5),
+            "雨れ今\n。。日\n  明は\n  日晴\n  は"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Handling of words which are too long to fit on a line.
+
+/// Describes what happens to words which are wider than the line
+/// width.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap, Options, WordOverflow};
+///
+/// let text = "Use the --ignore-whitespace-changes flag.";
+/// let options = Options::new(12).word_splitter(textwrap::WordSplitter::NoHyphenation);
+///
+/// assert_eq!(wrap(text, options.clone().word_overflow(WordOverflow::Break)),
+///            vec!["Use the", "--ignore-whi", "tespace-chan", "ges flag."]);
+/// assert_eq!(wrap(text, options.clone().word_overflow(WordOverflow::Overflow)),
+///            vec!["Use the", "--ignore-whitespace-changes", "flag."]);
+/// assert_eq!(wrap(text, options.clone().word_overflow(WordOverflow::Truncate("..."))),
+///            vec!["Use the", "--ignore-...", "flag."]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WordOverflow<'a> {
+    /// Break long words into pieces which fit on the lines. This
+    /// corresponds to [`Options::break_words`](crate::Options::break_words)
+    /// set to `true`.
+    #[default]
+    Break,
+    /// Leave long words intact and let them stick out into the right
+    /// margin. This corresponds to
+    /// [`Options::break_words`](crate::Options::break_words) set to
+    /// `false`.
+    Overflow,
+    /// Truncate long words so that they fit on the line together
+    /// with the given ellipsis string, which is appended to the
+    /// truncated word. The rest of the word is dropped.
+    Truncate(&'a str),
+}
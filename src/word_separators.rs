@@ -14,9 +14,10 @@
 //! there words are in a line of text. Please refer to the enum and
 //! its variants for more information.
 
-#[cfg(feature = "unicode-linebreak")]
-use crate::core::skip_ansi_escape_sequence;
-use crate::core::Word;
+use std::ops::Range;
+
+use crate::core::{ch_width, display_width, skip_ansi_escape_sequence, Word};
+use crate::ParseError;
 
 /// Describes where words occur in a line of text.
 ///
@@ -39,6 +40,7 @@ use crate::core::Word;
 /// assert_eq!(words, vec![Word::from("Hello "), Word::from("World!")]);
 /// ```
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WordSeparator {
     /// Find words by splitting on runs of `' '` characters.
     ///
@@ -54,6 +56,26 @@ pub enum WordSeparator {
     /// ```
     AsciiSpace,
 
+    /// Find words by splitting on runs of `' '` and `'\t'`
+    /// characters.
+    ///
+    /// This is like [`WordSeparator::AsciiSpace`], but tab-separated
+    /// words can be wrapped apart. When wrapping, each tab in the
+    /// whitespace between words counts as
+    /// [`Options::tab_width`](crate::Options::tab_width) columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::core::Word;
+    /// use textwrap::WordSeparator::AsciiWhitespace;
+    ///
+    /// let words = AsciiWhitespace.find_words("Hello\t World!").collect::<Vec<_>>();
+    /// assert_eq!(words, vec![Word::new("Hello", "\t ", ""),
+    ///                        Word::from("World!")]);
+    /// ```
+    AsciiWhitespace,
+
     /// Split `line` into words using Unicode break properties.
     ///
     /// This word separator uses the Unicode line breaking algorithm
@@ -118,7 +140,135 @@ pub enum WordSeparator {
     #[cfg(feature = "unicode-linebreak")]
     UnicodeBreakProperties,
 
+    /// Split `line` into chunks of a fixed number of columns.
+    ///
+    /// This ignores the content of the line and simply cuts it into
+    /// pieces which are the given number of columns wide (measured
+    /// with [`display_width()`](crate::core::display_width)). The last
+    /// chunk can be narrower, use [`WordSeparator::FixedWidthPadded`]
+    /// to pad it. A double-width character is never
+    /// split, so a chunk can also be narrower if the next character
+    /// does not fit. Trailing `' '` characters in a chunk become the
+    /// whitespace of the word, like for the other word separators.
+    ///
+    /// This is useful for re-flowing fixed-width records or hex dumps
+    /// where there are no natural word boundaries. A width of zero is
+    /// treated as a width of one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator};
+    ///
+    /// let options = Options::new(9).word_separator(WordSeparator::FixedWidth(2));
+    /// assert_eq!(wrap("deadbeefcafe", &options),
+    ///            vec!["deadbeef", "cafe"]);
+    /// ```
+    FixedWidth(usize),
+
+    /// Split `line` into chunks like [`WordSeparator::FixedWidth`] and
+    /// pad the last chunk with `' '` to the full width.
+    ///
+    /// The padding is added to the last line wrapped from each input
+    /// line. This keeps the columns of a hex dump aligned when the
+    /// wrapped lines are printed next to other text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator};
+    ///
+    /// let options = Options::new(9).word_separator(WordSeparator::FixedWidthPadded(4));
+    /// assert_eq!(wrap("deadbeefcafe12", &options),
+    ///            vec!["deadbeef", "cafe12  "]);
+    /// ```
+    FixedWidthPadded(usize),
+
+    /// Choose the word separator based on the content of each line,
+    /// see [`WordSeparator::detect()`].
+    ///
+    /// This is a good choice when wrapping user-provided text in
+    /// unknown languages: lines in Chinese, Japanese, or Korean are
+    /// split with [`WordSeparator::UnicodeBreakProperties`], while
+    /// other lines are split quickly with
+    /// [`WordSeparator::AsciiSpace`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator};
+    ///
+    /// let options = Options::new(10).word_separator(WordSeparator::Auto);
+    /// assert_eq!(wrap("Hello, World!", &options), vec!["Hello,", "World!"]);
+    ///
+    /// #[cfg(all(feature = "unicode-linebreak", feature = "unicode-width"))]
+    /// assert_eq!(wrap("你好世界，你好世界", &options), vec!["你好世界，", "你好世界"]);
+    /// ```
+    Auto,
+
+    /// Keep Markdown inline links such as `[text](url)` together.
+    ///
+    /// Words are found with [`WordSeparator::new()`], after which
+    /// the words making up a link are joined into a single word. This
+    /// prevents the URL from being broken across lines. Links which
+    /// are wider than the given number of columns can still be broken
+    /// between the words of the link text and after each `'/'` in the
+    /// URL. See [`MarkdownLinkIter`] for using this with another word
+    /// separator.
+    ///
+    /// Note that a [`WordSplitter`](crate::WordSplitter) can still
+    /// split the link at hyphens. Use
+    /// [`WordSplitter::NoHyphenation`](crate::WordSplitter::NoHyphenation)
+    /// if this is a problem.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSeparator, WordSplitter};
+    ///
+    /// let text = "See [the docs](https://docs.rs/textwrap) for more.";
+    /// let options = Options::new(15)
+    ///     .word_separator(WordSeparator::MarkdownLinks(80))
+    ///     .word_splitter(WordSplitter::NoHyphenation)
+    ///     .break_words(false);
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["See",
+    ///                 "[the docs](https://docs.rs/textwrap)",
+    ///                 "for more."]);
+    ///
+    /// let options = options.word_separator(WordSeparator::MarkdownLinks(20));
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["See [the docs](",
+    ///                 "https://",
+    ///                 "docs.rs/",
+    ///                 "textwrap) for",
+    ///                 "more."]);
+    /// ```
+    MarkdownLinks(usize),
+
+    /// Find words by splitting on runs of `' '` characters and next
+    /// to punctuation characters, see [`PunctuationSeparator`].
+    ///
+    /// This is useful when wrapping comma-separated lists, such as
+    /// lists of Cargo features, where there might not be any spaces
+    /// after the commas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::word_separators::PunctuationSeparator;
+    /// use textwrap::{wrap, Options, WordSeparator};
+    ///
+    /// let separator = PunctuationSeparator::new(&[',', ';']);
+    /// let options = Options::new(15).word_separator(WordSeparator::Punctuation(separator));
+    /// assert_eq!(wrap("serde,smawk,unicode-width;cache", &options),
+    ///            vec!["serde,smawk,", "unicode-width;", "cache"]);
+    /// ```
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Punctuation(PunctuationSeparator<'static>),
+
     /// Find words using a custom word separator
+    #[cfg_attr(feature = "serde", serde(skip))]
     Custom(fn(line: &str) -> Box<dyn Iterator<Item = Word<'_>> + '_>),
 }
 
@@ -149,30 +299,124 @@ impl PartialEq for WordSeparator {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (WordSeparator::AsciiSpace, WordSeparator::AsciiSpace) => true,
+            (WordSeparator::AsciiWhitespace, WordSeparator::AsciiWhitespace) => true,
             #[cfg(feature = "unicode-linebreak")]
             (WordSeparator::UnicodeBreakProperties, WordSeparator::UnicodeBreakProperties) => true,
+            (WordSeparator::FixedWidth(a), WordSeparator::FixedWidth(b)) => a == b,
+            (WordSeparator::FixedWidthPadded(a), WordSeparator::FixedWidthPadded(b)) => a == b,
+            (WordSeparator::Auto, WordSeparator::Auto) => true,
+            (WordSeparator::MarkdownLinks(a), WordSeparator::MarkdownLinks(b)) => a == b,
+            (WordSeparator::Punctuation(a), WordSeparator::Punctuation(b)) => a == b,
             (_, _) => false,
         }
     }
 }
 
+impl std::hash::Hash for WordSeparator {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            WordSeparator::FixedWidth(width)
+            | WordSeparator::FixedWidthPadded(width)
+            | WordSeparator::MarkdownLinks(width) => width.hash(state),
+            WordSeparator::Punctuation(separator) => separator.hash(state),
+            _ => {}
+        }
+    }
+}
+
 impl std::fmt::Debug for WordSeparator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             WordSeparator::AsciiSpace => f.write_str("AsciiSpace"),
+            WordSeparator::AsciiWhitespace => f.write_str("AsciiWhitespace"),
             #[cfg(feature = "unicode-linebreak")]
             WordSeparator::UnicodeBreakProperties => f.write_str("UnicodeBreakProperties"),
+            WordSeparator::FixedWidth(width) => write!(f, "FixedWidth({})", width),
+            WordSeparator::FixedWidthPadded(width) => write!(f, "FixedWidthPadded({})", width),
+            WordSeparator::Auto => f.write_str("Auto"),
+            WordSeparator::MarkdownLinks(width) => write!(f, "MarkdownLinks({})", width),
+            WordSeparator::Punctuation(separator) => write!(f, "Punctuation({:?})", separator),
             WordSeparator::Custom(_) => f.write_str("Custom(...)"),
         }
     }
 }
 
+/// Formats the word separator as `"ascii-space"`,
+/// `"ascii-whitespace"`, `"unicode-break-properties"`, `"fixed-width:N"`,
+/// `"fixed-width-padded:N"`, `"auto"`, `"markdown-links:N"`,
+/// `"punctuation"`, or `"custom"`.
+impl std::fmt::Display for WordSeparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WordSeparator::AsciiSpace => f.write_str("ascii-space"),
+            WordSeparator::AsciiWhitespace => f.write_str("ascii-whitespace"),
+            #[cfg(feature = "unicode-linebreak")]
+            WordSeparator::UnicodeBreakProperties => f.write_str("unicode-break-properties"),
+            WordSeparator::FixedWidth(width) => write!(f, "fixed-width:{}", width),
+            WordSeparator::FixedWidthPadded(width) => write!(f, "fixed-width-padded:{}", width),
+            WordSeparator::Auto => f.write_str("auto"),
+            WordSeparator::MarkdownLinks(width) => write!(f, "markdown-links:{}", width),
+            WordSeparator::Punctuation(_) => f.write_str("punctuation"),
+            WordSeparator::Custom(_) => f.write_str("custom"),
+        }
+    }
+}
+
+/// Parses the names produced by the [`Display`](std::fmt::Display)
+/// implementation, except for `"punctuation"` and `"custom"`.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{ParseError, WordSeparator};
+///
+/// assert_eq!("ascii-space".parse(), Ok(WordSeparator::AsciiSpace));
+/// assert_eq!("fixed-width:4".parse(), Ok(WordSeparator::FixedWidth(4)));
+///
+/// #[cfg(not(feature = "unicode-linebreak"))]
+/// assert_eq!("unicode-break-properties".parse::<WordSeparator>(),
+///            Err(ParseError::Unsupported { name: "unicode-break-properties",
+///                                          feature: "unicode-linebreak" }));
+/// ```
+impl std::str::FromStr for WordSeparator {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii-space" => Ok(WordSeparator::AsciiSpace),
+            "ascii-whitespace" => Ok(WordSeparator::AsciiWhitespace),
+            #[cfg(feature = "unicode-linebreak")]
+            "unicode-break-properties" => Ok(WordSeparator::UnicodeBreakProperties),
+            #[cfg(not(feature = "unicode-linebreak"))]
+            "unicode-break-properties" => Err(ParseError::Unsupported {
+                name: "unicode-break-properties",
+                feature: "unicode-linebreak",
+            }),
+            "auto" => Ok(WordSeparator::Auto),
+            _ => {
+                if let Some(Ok(width)) = s.strip_prefix("fixed-width:").map(str::parse) {
+                    return Ok(WordSeparator::FixedWidth(width));
+                }
+                if let Some(Ok(width)) = s.strip_prefix("fixed-width-padded:").map(str::parse) {
+                    return Ok(WordSeparator::FixedWidthPadded(width));
+                }
+                match s.strip_prefix("markdown-links:").map(str::parse) {
+                    Some(Ok(width)) => Ok(WordSeparator::MarkdownLinks(width)),
+                    _ => Err(ParseError::Unknown(s.to_string())),
+                }
+            }
+        }
+    }
+}
+
 impl WordSeparator {
     /// Create a new word separator.
     ///
     /// The best available algorithm is used by default, i.e.,
     /// [`WordSeparator::UnicodeBreakProperties`] if available,
     /// otherwise [`WordSeparator::AsciiSpace`].
+    #[allow(clippy::new_without_default)]
     pub const fn new() -> Self {
         #[cfg(feature = "unicode-linebreak")]
         {
@@ -185,45 +429,698 @@ impl WordSeparator {
         }
     }
 
+    /// Choose a word separator for `line`.
+    ///
+    /// Lines with characters from Chinese, Japanese, or Korean
+    /// scripts and lines without any `' '` use
+    /// [`WordSeparator::UnicodeBreakProperties`] since they need
+    /// breaks where there is no whitespace. Other lines use the
+    /// faster [`WordSeparator::AsciiSpace`]. Without the
+    /// `unicode-linebreak` Cargo feature, this always returns
+    /// [`WordSeparator::AsciiSpace`].
+    ///
+    /// This is used by [`WordSeparator::Auto`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::WordSeparator;
+    ///
+    /// assert_eq!(WordSeparator::detect("Hello, World!"), WordSeparator::AsciiSpace);
+    /// #[cfg(feature = "unicode-linebreak")] {
+    ///     assert_eq!(WordSeparator::detect("你好"), WordSeparator::UnicodeBreakProperties);
+    ///     assert_eq!(WordSeparator::detect("Hello/World"),
+    ///                WordSeparator::UnicodeBreakProperties);
+    /// }
+    /// ```
+    #[cfg_attr(not(feature = "unicode-linebreak"), allow(unused_variables))]
+    pub fn detect(line: &str) -> WordSeparator {
+        #[cfg(feature = "unicode-linebreak")]
+        if !line.contains(' ') || line.chars().any(is_cjk) {
+            return WordSeparator::UnicodeBreakProperties;
+        }
+
+        WordSeparator::AsciiSpace
+    }
+
     // This function should really return impl Iterator<Item = Word>, but
     // this isn't possible until Rust supports higher-kinded types:
     // https://github.com/rust-lang/rfcs/blob/master/text/1522-conservative-impl-trait.md
     /// Find all words in `line`.
+    ///
+    /// This boxes the iterator, use [`WordSeparator::iter_words`] to
+    /// avoid the allocation.
     pub fn find_words<'a>(&self, line: &'a str) -> Box<dyn Iterator<Item = Word<'a>> + 'a> {
         match self {
-            WordSeparator::AsciiSpace => find_words_ascii_space(line),
-            #[cfg(feature = "unicode-linebreak")]
-            WordSeparator::UnicodeBreakProperties => find_words_unicode_break_properties(line),
             WordSeparator::Custom(func) => func(line),
+            _ => Box::new(self.iter_words(line)),
         }
     }
+
+    /// Find all words in `line` without boxing the iterator.
+    ///
+    /// This returns the same words as [`WordSeparator::find_words`].
+    /// Only a [`WordSeparator::Custom`] function allocates since it
+    /// returns a boxed iterator itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::core::Word;
+    /// use textwrap::WordSeparator;
+    ///
+    /// let words = WordSeparator::AsciiSpace.iter_words("Hello World!").collect::<Vec<_>>();
+    /// assert_eq!(words, vec![Word::from("Hello "), Word::from("World!")]);
+    /// ```
+    pub fn iter_words<'a>(&self, line: &'a str) -> WordIter<'a> {
+        let inner = match self {
+            WordSeparator::AsciiSpace => WordIterInner::AsciiSpace(AsciiSpaceIter::new(line)),
+            WordSeparator::AsciiWhitespace => {
+                WordIterInner::AsciiSpace(AsciiSpaceIter::with_tabs(line))
+            }
+            #[cfg(feature = "unicode-linebreak")]
+            WordSeparator::UnicodeBreakProperties => {
+                WordIterInner::UnicodeBreakProperties(UnicodeBreakPropertiesIter::new(line))
+            }
+            WordSeparator::FixedWidth(width) | WordSeparator::FixedWidthPadded(width) => {
+                WordIterInner::FixedWidth(FixedWidthIter::new(line, *width))
+            }
+            WordSeparator::Auto => return WordSeparator::detect(line).iter_words(line),
+            WordSeparator::MarkdownLinks(max_width) => WordIterInner::MarkdownLinks(
+                MarkdownLinkIter::new(line, WordSeparator::new(), *max_width),
+            ),
+            WordSeparator::Punctuation(separator) => {
+                WordIterInner::Punctuation(PunctuationIter::new(line, *separator))
+            }
+            WordSeparator::Custom(func) => WordIterInner::Custom(func(line)),
+        };
+        WordIter { inner }
+    }
+
+    /// Find the byte ranges of all words in `line`.
+    ///
+    /// Each range covers a word including its trailing whitespace,
+    /// which means that the ranges are contiguous and that they
+    /// together cover all of `line`. This lets you map the words
+    /// found by [`WordSeparator::find_words`] back to positions in
+    /// the input, e.g., for placing a cursor in an editor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::WordSeparator::AsciiSpace;
+    ///
+    /// let ranges = AsciiSpace.find_word_ranges("Hello   World!").collect::<Vec<_>>();
+    /// assert_eq!(ranges, vec![0..8, 8..14]);
+    /// ```
+    ///
+    /// For [`WordSeparator::Custom`], the ranges are computed from
+    /// the words returned by the custom function. Like elsewhere in
+    /// the library, the words are assumed to be contiguous in `line`.
+    pub fn find_word_ranges<'a>(
+        &self,
+        line: &'a str,
+    ) -> Box<dyn Iterator<Item = Range<usize>> + 'a> {
+        match self {
+            WordSeparator::AsciiSpace => {
+                let mut iter = AsciiSpaceIter::new(line);
+                Box::new(std::iter::from_fn(move || iter.next_range()))
+            }
+            WordSeparator::AsciiWhitespace => {
+                let mut iter = AsciiSpaceIter::with_tabs(line);
+                Box::new(std::iter::from_fn(move || iter.next_range()))
+            }
+            #[cfg(feature = "unicode-linebreak")]
+            WordSeparator::UnicodeBreakProperties => {
+                let mut iter = UnicodeBreakPropertiesIter::new(line);
+                Box::new(std::iter::from_fn(move || iter.next_range()))
+            }
+            WordSeparator::FixedWidth(width) | WordSeparator::FixedWidthPadded(width) => {
+                let mut iter = FixedWidthIter::new(line, *width);
+                Box::new(std::iter::from_fn(move || iter.next_range()))
+            }
+            WordSeparator::Auto => WordSeparator::detect(line).find_word_ranges(line),
+            WordSeparator::MarkdownLinks(max_width) => {
+                let mut iter = MarkdownLinkIter::new(line, WordSeparator::new(), *max_width);
+                Box::new(std::iter::from_fn(move || iter.next_range()))
+            }
+            WordSeparator::Punctuation(separator) => {
+                let mut iter = PunctuationIter::new(line, *separator);
+                Box::new(std::iter::from_fn(move || iter.next_range()))
+            }
+            WordSeparator::Custom(func) => {
+                let mut start = 0;
+                Box::new(func(line).map(move |word| {
+                    let end = start + word.len() + word.whitespace.len();
+                    let range = start..end;
+                    start = end;
+                    range
+                }))
+            }
+        }
+    }
+}
+
+/// Iterator over the words found by a [`WordSeparator`].
+///
+/// Returned by [`WordSeparator::iter_words`].
+pub struct WordIter<'a> {
+    inner: WordIterInner<'a>,
+}
+
+enum WordIterInner<'a> {
+    AsciiSpace(AsciiSpaceIter<'a>),
+    #[cfg(feature = "unicode-linebreak")]
+    UnicodeBreakProperties(UnicodeBreakPropertiesIter<'a>),
+    FixedWidth(FixedWidthIter<'a>),
+    MarkdownLinks(MarkdownLinkIter<'a>),
+    Punctuation(PunctuationIter<'a>),
+    Custom(Box<dyn Iterator<Item = Word<'a>> + 'a>),
 }
 
-fn find_words_ascii_space<'a>(line: &'a str) -> Box<dyn Iterator<Item = Word<'a>> + 'a> {
-    let mut start = 0;
-    let mut in_whitespace = false;
-    let mut char_indices = line.char_indices();
+impl std::fmt::Debug for WordIter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.inner {
+            WordIterInner::AsciiSpace(iter) => iter.fmt(f),
+            #[cfg(feature = "unicode-linebreak")]
+            WordIterInner::UnicodeBreakProperties(iter) => iter.fmt(f),
+            WordIterInner::FixedWidth(iter) => iter.fmt(f),
+            WordIterInner::MarkdownLinks(iter) => iter.fmt(f),
+            WordIterInner::Punctuation(iter) => iter.fmt(f),
+            WordIterInner::Custom(_) => f.write_str("Custom(...)"),
+        }
+    }
+}
+
+impl<'a> Iterator for WordIter<'a> {
+    type Item = Word<'a>;
 
-    Box::new(std::iter::from_fn(move || {
-        for (idx, ch) in char_indices.by_ref() {
-            if in_whitespace && ch != ' ' {
-                let word = Word::from(&line[start..idx]);
-                start = idx;
-                in_whitespace = ch == ' ';
-                return Some(word);
+    fn next(&mut self) -> Option<Word<'a>> {
+        match &mut self.inner {
+            WordIterInner::AsciiSpace(iter) => iter.next(),
+            #[cfg(feature = "unicode-linebreak")]
+            WordIterInner::UnicodeBreakProperties(iter) => iter.next(),
+            WordIterInner::FixedWidth(iter) => iter.next(),
+            WordIterInner::MarkdownLinks(iter) => iter.next(),
+            WordIterInner::Punctuation(iter) => iter.next(),
+            WordIterInner::Custom(iter) => iter.next(),
+        }
+    }
+}
+
+/// Iterator over the words found by [`WordSeparator::AsciiSpace`]
+/// and [`WordSeparator::AsciiWhitespace`].
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::word_separators::AsciiSpaceIter;
+///
+/// let words = AsciiSpaceIter::new("Hello  World!").collect::<Vec<_>>();
+/// assert_eq!(words, vec![Word::from("Hello  "), Word::from("World!")]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsciiSpaceIter<'a> {
+    line: &'a str,
+    start: usize,
+    in_whitespace: bool,
+    tabs: bool,
+    char_indices: std::str::CharIndices<'a>,
+}
+
+impl<'a> AsciiSpaceIter<'a> {
+    /// Find words separated by ASCII spaces in `line`.
+    pub fn new(line: &'a str) -> Self {
+        AsciiSpaceIter {
+            line,
+            start: 0,
+            in_whitespace: false,
+            tabs: false,
+            char_indices: line.char_indices(),
+        }
+    }
+
+    /// Find words separated by ASCII spaces and tabs in `line`.
+    pub fn with_tabs(line: &'a str) -> Self {
+        AsciiSpaceIter {
+            tabs: true,
+            ..AsciiSpaceIter::new(line)
+        }
+    }
+
+    fn is_whitespace(&self, ch: char) -> bool {
+        ch == ' ' || (self.tabs && ch == '\t')
+    }
+
+    fn next_range(&mut self) -> Option<Range<usize>> {
+        while let Some((idx, ch)) = self.char_indices.next() {
+            let is_whitespace = self.is_whitespace(ch);
+            if self.in_whitespace && !is_whitespace {
+                let range = self.start..idx;
+                self.start = idx;
+                self.in_whitespace = false;
+                return Some(range);
             }
 
-            in_whitespace = ch == ' ';
+            self.in_whitespace = is_whitespace;
+        }
+
+        if self.start < self.line.len() {
+            let range = self.start..self.line.len();
+            self.start = self.line.len();
+            return Some(range);
         }
 
-        if start < line.len() {
-            let word = Word::from(&line[start..]);
-            start = line.len();
+        None
+    }
+}
+
+impl<'a> Iterator for AsciiSpaceIter<'a> {
+    type Item = Word<'a>;
+
+    fn next(&mut self) -> Option<Word<'a>> {
+        let range = self.next_range()?;
+        let text = &self.line[range];
+        if !self.tabs {
+            return Some(Word::from(text));
+        }
+        let word = text.trim_end_matches([' ', '\t']);
+        Some(Word::new(word, &text[word.len()..], ""))
+    }
+}
+
+/// Spaces other than `' '` which are breakable whitespace when
+/// [`Options::wide_spaces`](crate::Options::wide_spaces) is enabled:
+/// U+2003 EM SPACE and U+3000 IDEOGRAPHIC SPACE.
+pub(crate) const WIDE_SPACES: [char; 2] = ['\u{2003}', '\u{3000}'];
+
+/// Split `word` after each run of [`WIDE_SPACES`] inside it. A run
+/// at the end of the word becomes part of the trailing whitespace.
+///
+/// The `text` is `word` followed by its whitespace, as found in the
+/// line. Words without wide spaces are returned unchanged, as are
+/// all words if `enabled` is false.
+pub(crate) fn split_wide_spaces<'a>(
+    text: &'a str,
+    word: Word<'a>,
+    enabled: bool,
+) -> impl Iterator<Item = Word<'a>> {
+    let mut rest = match enabled && word.contains(WIDE_SPACES) {
+        true => text,
+        false => "",
+    };
+    let mut word_len = word.len();
+    let mut unchanged = Some(word).filter(|_| rest.is_empty());
+    std::iter::from_fn(move || {
+        if let Some(word) = unchanged.take() {
             return Some(word);
         }
+        if rest.is_empty() {
+            return None;
+        }
+        let space = match rest[..word_len].find(WIDE_SPACES) {
+            Some(space) => space,
+            None => {
+                let piece = Word::new(&rest[..word_len], &rest[word_len..], word.penalty);
+                rest = "";
+                return Some(piece);
+            }
+        };
+        let piece = match rest[space..word_len].find(|ch| ch != ' ' && !WIDE_SPACES.contains(&ch)) {
+            Some(len) => {
+                let end = space + len;
+                let piece = Word::new(&rest[..space], &rest[space..end], "");
+                word_len -= end;
+                rest = &rest[end..];
+                piece
+            }
+            // The run of spaces continues into the whitespace.
+            None => {
+                let piece = Word::new(&rest[..space], &rest[space..], word.penalty);
+                rest = "";
+                piece
+            }
+        };
+        Some(piece)
+    })
+}
+
+/// Iterator over the words found by [`WordSeparator::FixedWidth`].
+#[derive(Debug, Clone)]
+pub struct FixedWidthIter<'a> {
+    line: &'a str,
+    width: usize,
+    start: usize,
+    chunk_width: usize,
+    char_indices: std::str::CharIndices<'a>,
+}
+
+impl<'a> FixedWidthIter<'a> {
+    /// Split `line` into chunks which are at most `width` columns
+    /// wide. A `width` of zero is treated as one.
+    pub fn new(line: &'a str, width: usize) -> Self {
+        FixedWidthIter {
+            line,
+            width: std::cmp::max(width, 1),
+            start: 0,
+            chunk_width: 0,
+            char_indices: line.char_indices(),
+        }
+    }
+
+    fn next_range(&mut self) -> Option<Range<usize>> {
+        while let Some((idx, ch)) = self.char_indices.next() {
+            if skip_ansi_escape_sequence(ch, &mut self.char_indices.by_ref().map(|(_, ch)| ch)) {
+                continue;
+            }
+
+            if self.chunk_width > 0 && self.chunk_width + ch_width(ch) > self.width {
+                let range = self.start..idx;
+                self.start = idx;
+                self.chunk_width = ch_width(ch);
+                return Some(range);
+            }
+
+            self.chunk_width += ch_width(ch);
+        }
+
+        if self.start < self.line.len() {
+            let range = self.start..self.line.len();
+            self.start = self.line.len();
+            return Some(range);
+        }
+
+        None
+    }
+}
+
+impl<'a> Iterator for FixedWidthIter<'a> {
+    type Item = Word<'a>;
+
+    fn next(&mut self) -> Option<Word<'a>> {
+        let range = self.next_range()?;
+        Some(Word::from(&self.line[range]))
+    }
+}
+
+/// Configuration for [`WordSeparator::Punctuation`].
+///
+/// Words are separated by runs of `' '` characters, like with
+/// [`WordSeparator::AsciiSpace`], and additionally next to each of
+/// the punctuation `chars`. Spaces after the punctuation become the
+/// whitespace of the word, like for the other word separators.
+///
+/// The characters are borrowed. [`WordSeparator::Punctuation`] needs
+/// them for `'static`, but a [`PunctuationIter`] can be used with
+/// characters which are only known at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::word_separators::{PunctuationIter, PunctuationSeparator};
+///
+/// let separator = PunctuationSeparator::new(&[',']);
+/// let words = PunctuationIter::new("a,b, c", separator).collect::<Vec<_>>();
+/// assert_eq!(words, vec![Word::from("a,"), Word::from("b, "), Word::from("c")]);
+///
+/// let separator = separator.keep_with_previous(false);
+/// let words = PunctuationIter::new("a,b, c", separator).collect::<Vec<_>>();
+/// assert_eq!(words, vec![Word::from("a"), Word::from(",b"), Word::from(", c")]);
+///
+/// let chars = String::from(";").chars().collect::<Vec<_>>();
+/// let separator = PunctuationSeparator::new(&chars);
+/// let words = PunctuationIter::new("a;b", separator).collect::<Vec<_>>();
+/// assert_eq!(words, vec![Word::from("a;"), Word::from("b")]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PunctuationSeparator<'a> {
+    /// The punctuation characters which allow a break.
+    pub chars: &'a [char],
+    /// Keep the punctuation with the previous word, i.e., break
+    /// after the punctuation. When `false`, the break comes before
+    /// the punctuation, which then starts the next word. The line is
+    /// then never broken after the punctuation, not even at the
+    /// spaces following it.
+    pub keep_with_previous: bool,
+}
+
+impl<'a> PunctuationSeparator<'a> {
+    /// Allow breaks after each of the given `chars`.
+    pub const fn new(chars: &'a [char]) -> Self {
+        PunctuationSeparator {
+            chars,
+            keep_with_previous: true,
+        }
+    }
+
+    /// Change [`self.keep_with_previous`].
+    ///
+    /// [`self.keep_with_previous`]: #structfield.keep_with_previous
+    pub const fn keep_with_previous(self, keep_with_previous: bool) -> Self {
+        PunctuationSeparator {
+            keep_with_previous,
+            ..self
+        }
+    }
+
+    /// Check if there is a break between `prev` and `ch`. The
+    /// `last_visible` character is the last character before `ch`
+    /// which is not a space.
+    fn is_break(&self, last_visible: Option<char>, prev: char, ch: char) -> bool {
+        if ch == ' ' {
+            return false;
+        }
+        if prev == ' ' {
+            // The punctuation must not end a line when it is kept
+            // with the next word.
+            return self.keep_with_previous
+                || !matches!(last_visible, Some(last) if self.chars.contains(&last));
+        }
+        if self.keep_with_previous {
+            self.chars.contains(&prev)
+        } else {
+            self.chars.contains(&ch)
+        }
+    }
+}
+
+/// Iterator over the words found by [`WordSeparator::Punctuation`].
+#[derive(Debug, Clone)]
+pub struct PunctuationIter<'a> {
+    line: &'a str,
+    separator: PunctuationSeparator<'a>,
+    start: usize,
+    prev: Option<char>,
+    last_visible: Option<char>,
+    char_indices: std::str::CharIndices<'a>,
+}
+
+impl<'a> PunctuationIter<'a> {
+    /// Find words in `line` which are separated by ASCII spaces or
+    /// by the punctuation of `separator`.
+    pub fn new(line: &'a str, separator: PunctuationSeparator<'a>) -> Self {
+        PunctuationIter {
+            line,
+            separator,
+            start: 0,
+            prev: None,
+            last_visible: None,
+            char_indices: line.char_indices(),
+        }
+    }
+
+    fn next_range(&mut self) -> Option<Range<usize>> {
+        for (idx, ch) in self.char_indices.by_ref() {
+            let prev = self.prev.replace(ch);
+            let last_visible = match ch {
+                ' ' => self.last_visible,
+                _ => self.last_visible.replace(ch),
+            };
+            if matches!(prev, Some(prev) if self.separator.is_break(last_visible, prev, ch)) {
+                let range = self.start..idx;
+                self.start = idx;
+                return Some(range);
+            }
+        }
+
+        if self.start < self.line.len() {
+            let range = self.start..self.line.len();
+            self.start = self.line.len();
+            return Some(range);
+        }
 
         None
-    }))
+    }
+}
+
+impl<'a> Iterator for PunctuationIter<'a> {
+    type Item = Word<'a>;
+
+    fn next(&mut self) -> Option<Word<'a>> {
+        let range = self.next_range()?;
+        Some(Word::from(&self.line[range]))
+    }
+}
+
+/// Iterator over the words found by [`WordSeparator::MarkdownLinks`].
+///
+/// This adapts the words found by another [`WordSeparator`] so that
+/// Markdown inline links such as `[text](url)` become single words.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::word_separators::MarkdownLinkIter;
+/// use textwrap::WordSeparator;
+///
+/// let line = "Read [this](https://example.com/a-b) now";
+/// let words = MarkdownLinkIter::new(line, WordSeparator::AsciiSpace, 80).collect::<Vec<_>>();
+/// assert_eq!(words, vec![Word::from("Read "),
+///                        Word::from("[this](https://example.com/a-b) "),
+///                        Word::from("now")]);
+/// ```
+pub struct MarkdownLinkIter<'a> {
+    line: &'a str,
+    max_width: usize,
+    ranges: std::iter::Peekable<Box<dyn Iterator<Item = Range<usize>> + 'a>>,
+    link: Option<MarkdownLink>,
+    // Break points inside a link which is too wide, in reverse order.
+    breaks: Vec<usize>,
+    start: usize,
+}
+
+// Byte ranges of a Markdown inline link and of the URL inside it.
+#[derive(Debug, Clone)]
+struct MarkdownLink {
+    link: Range<usize>,
+    url: Range<usize>,
+}
+
+impl std::fmt::Debug for MarkdownLinkIter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MarkdownLinkIter")
+            .field("line", &self.line)
+            .field("max_width", &self.max_width)
+            .field("start", &self.start)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> MarkdownLinkIter<'a> {
+    /// Find words in `line` with `separator` and join the words of
+    /// Markdown inline links. Links which are wider than `max_width`
+    /// columns can be broken between the words of the link text and
+    /// after each `'/'` in the URL.
+    pub fn new(line: &'a str, separator: WordSeparator, max_width: usize) -> Self {
+        MarkdownLinkIter {
+            line,
+            max_width,
+            ranges: separator.find_word_ranges(line).peekable(),
+            link: find_markdown_link(line, 0),
+            breaks: Vec::new(),
+            start: 0,
+        }
+    }
+
+    fn next_range(&mut self) -> Option<Range<usize>> {
+        if let Some(end) = self.breaks.pop() {
+            let range = self.start..end;
+            self.start = end;
+            return Some(range);
+        }
+
+        let range = self.ranges.next()?;
+        let link = match &self.link {
+            Some(link) if link.link.start < range.end => link.clone(),
+            _ => return Some(range),
+        };
+
+        let mut end = range.end;
+        let mut text_breaks = Vec::new();
+        while end < link.link.end {
+            // The link text ends with "](" before the URL.
+            if end + 2 < link.url.start {
+                text_breaks.push(end);
+            }
+            match self.ranges.next() {
+                Some(next) => end = next.end,
+                None => break,
+            }
+        }
+        self.link = find_markdown_link(self.line, end);
+
+        if display_width(&self.line[link.link.clone()]) <= self.max_width {
+            return Some(range.start..end);
+        }
+
+        let url = &self.line[link.url.clone()];
+        self.breaks = text_breaks
+            .into_iter()
+            .chain(std::iter::once(link.url.start))
+            .chain(
+                url.match_indices('/')
+                    .filter(|(idx, _)| !url[idx + 1..].starts_with('/'))
+                    .map(|(idx, _)| link.url.start + idx + 1),
+            )
+            .filter(|&pos| range.start < pos && pos < end)
+            .chain(std::iter::once(end))
+            .collect();
+        self.breaks.dedup();
+        self.breaks.reverse();
+        self.start = range.start;
+        self.next_range()
+    }
+}
+
+impl<'a> Iterator for MarkdownLinkIter<'a> {
+    type Item = Word<'a>;
+
+    fn next(&mut self) -> Option<Word<'a>> {
+        let range = self.next_range()?;
+        Some(Word::from(&self.line[range]))
+    }
+}
+
+// Find the first Markdown inline link which starts at or after
+// `from` in `line`.
+fn find_markdown_link(line: &str, from: usize) -> Option<MarkdownLink> {
+    let mut start = from;
+    while let Some(idx) = line[start..].find('[') {
+        let open = start + idx;
+        start = open + 1;
+
+        let close = match line[open..].find(']') {
+            Some(idx) => open + idx,
+            None => return None,
+        };
+        if !line[close + 1..].starts_with('(') {
+            continue;
+        }
+
+        // Allow balanced parentheses in the URL, which are common in
+        // links to Wikipedia.
+        let url_start = close + 2;
+        let mut depth = 0;
+        for (idx, ch) in line[url_start..].char_indices() {
+            match ch {
+                '(' => depth += 1,
+                ')' if depth == 0 => {
+                    let url_end = url_start + idx;
+                    return Some(MarkdownLink {
+                        link: open..url_end + 1,
+                        url: url_start..url_end,
+                    });
+                }
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        return None;
+    }
+    None
 }
 
 // Strip all ANSI escape sequences from `text`.
@@ -242,6 +1139,25 @@ fn strip_ansi_escape_sequences(text: &str) -> String {
     result
 }
 
+/// Check if `ch` belongs to one of the Chinese, Japanese, or Korean
+/// scripts, or is a punctuation character used with them.
+#[cfg(feature = "unicode-linebreak")]
+fn is_cjk(ch: char) -> bool {
+    matches!(ch,
+        '\u{1100}'..='\u{11ff}'   // Hangul Jamo
+        | '\u{2e80}'..='\u{2fdf}' // CJK Radicals
+        | '\u{3000}'..='\u{303f}' // CJK Symbols and Punctuation
+        | '\u{3040}'..='\u{30ff}' // Hiragana and Katakana
+        | '\u{3100}'..='\u{31ff}' // Bopomofo, Hangul compatibility, ...
+        | '\u{3400}'..='\u{4dbf}' // CJK Unified Ideographs Extension A
+        | '\u{4e00}'..='\u{9fff}' // CJK Unified Ideographs
+        | '\u{ac00}'..='\u{d7af}' // Hangul Syllables
+        | '\u{f900}'..='\u{faff}' // CJK Compatibility Ideographs
+        | '\u{ff00}'..='\u{ffef}' // Halfwidth and Fullwidth Forms
+        | '\u{20000}'..='\u{3134f}' // CJK Unified Ideographs Extension B-G
+    )
+}
+
 /// Soft hyphen, also knows as a “shy hyphen”. Should show up as ‘-’
 /// if a line is broken at this point, and otherwise be invisible.
 /// Textwrap does not currently support breaking words at soft
@@ -249,70 +1165,120 @@ fn strip_ansi_escape_sequences(text: &str) -> String {
 #[cfg(feature = "unicode-linebreak")]
 const SHY: char = '\u{00ad}';
 
-/// Find words in line. ANSI escape sequences are ignored in `line`.
+/// Iterator over the words found by
+/// [`WordSeparator::UnicodeBreakProperties`].
+///
+/// The break opportunities are found one word at a time. Lines with
+/// ANSI escape sequences are the exception: the escape sequences are
+/// ignored by finding the break opportunities up front in a copy of
+/// the line without them.
+///
+/// **Note:** Only available when the `unicode-linebreak` Cargo
+/// feature is enabled.
 #[cfg(feature = "unicode-linebreak")]
-fn find_words_unicode_break_properties<'a>(
+#[derive(Debug, Clone)]
+pub struct UnicodeBreakPropertiesIter<'a> {
     line: &'a str,
-) -> Box<dyn Iterator<Item = Word<'a>> + 'a> {
-    // Construct an iterator over (original index, stripped index)
-    // tuples. We find the Unicode linebreaks on a stripped string,
-    // but we need the original indices so we can form words based on
-    // the original string.
-    let mut last_stripped_idx = 0;
-    let mut char_indices = line.char_indices();
-    let mut idx_map = std::iter::from_fn(move || match char_indices.next() {
-        Some((orig_idx, ch)) => {
-            let stripped_idx = last_stripped_idx;
-            if !skip_ansi_escape_sequence(ch, &mut char_indices.by_ref().map(|(_, ch)| ch)) {
-                last_stripped_idx += ch.len_utf8();
-            }
-            Some((orig_idx, stripped_idx))
-        }
-        None => None,
-    });
-
-    let stripped = strip_ansi_escape_sequences(line);
-    let mut opportunities = unicode_linebreak::linebreaks(&stripped)
-        .filter(|(idx, _)| {
-            #[allow(clippy::match_like_matches_macro)]
-            match &stripped[..*idx].chars().next_back() {
-                // We suppress breaks at ‘-’ since we want to control
-                // this via the WordSplitter.
-                Some('-') => false,
-                // Soft hyphens are currently not supported since we
-                // require all `Word` fragments to be continuous in
-                // the input string.
-                Some(SHY) => false,
-                // Other breaks should be fine!
-                _ => true,
-            }
-        })
-        .collect::<Vec<_>>()
-        .into_iter();
-
-    // Remove final break opportunity, we will add it below using
-    // &line[start..]; This ensures that we correctly include a
-    // trailing ANSI escape sequence.
-    opportunities.next_back();
-
-    let mut start = 0;
-    Box::new(std::iter::from_fn(move || {
-        for (idx, _) in opportunities.by_ref() {
-            if let Some((orig_idx, _)) = idx_map.find(|&(_, stripped_idx)| stripped_idx == idx) {
-                let word = Word::from(&line[start..orig_idx]);
-                start = orig_idx;
-                return Some(word);
-            }
+    start: usize,
+    /// Break opportunities found up front, used for lines with ANSI
+    /// escape sequences.
+    breaks: Option<std::vec::IntoIter<usize>>,
+}
+
+/// Check if a break is allowed after `text`.
+#[cfg(feature = "unicode-linebreak")]
+fn is_break_allowed_after(text: &str) -> bool {
+    // We suppress breaks at ‘-’ since we want to control this via
+    // the WordSplitter. Soft hyphens are currently not supported
+    // since we require all `Word` fragments to be continuous in the
+    // input string.
+    !matches!(text.chars().next_back(), Some('-') | Some(SHY))
+}
+
+#[cfg(feature = "unicode-linebreak")]
+impl<'a> UnicodeBreakPropertiesIter<'a> {
+    /// Find words in `line` using the Unicode line breaking
+    /// algorithm.
+    pub fn new(line: &'a str) -> Self {
+        let breaks = match line.contains('\x1b') {
+            true => Some(Self::ansi_breaks(line).into_iter()),
+            false => None,
+        };
+        UnicodeBreakPropertiesIter {
+            line,
+            start: 0,
+            breaks,
         }
+    }
 
-        if start < line.len() {
-            let word = Word::from(&line[start..]);
-            start = line.len();
-            return Some(word);
+    /// Find the break opportunities in a `line` with ANSI escape
+    /// sequences.
+    fn ansi_breaks(line: &str) -> Vec<usize> {
+        // Construct an iterator over (original index, stripped index)
+        // tuples. We find the Unicode linebreaks on a stripped string,
+        // but we need the original indices so we can form words based on
+        // the original string.
+        let mut last_stripped_idx = 0;
+        let mut char_indices = line.char_indices();
+        let mut idx_map = std::iter::from_fn(move || match char_indices.next() {
+            Some((orig_idx, ch)) => {
+                let stripped_idx = last_stripped_idx;
+                if !skip_ansi_escape_sequence(ch, &mut char_indices.by_ref().map(|(_, ch)| ch)) {
+                    last_stripped_idx += ch.len_utf8();
+                }
+                Some((orig_idx, stripped_idx))
+            }
+            None => None,
+        });
+
+        let stripped = strip_ansi_escape_sequences(line);
+        // The final break opportunity is left out, we add it in
+        // next_range using &line[start..]. This ensures that we
+        // correctly include a trailing ANSI escape sequence.
+        unicode_linebreak::linebreaks(&stripped)
+            .map(|(idx, _)| idx)
+            .filter(|&idx| idx < stripped.len() && is_break_allowed_after(&stripped[..idx]))
+            // Translate the break opportunities to indices in the
+            // original line.
+            .filter_map(|idx| {
+                idx_map
+                    .find(|&(_, stripped_idx)| stripped_idx == idx)
+                    .map(|(orig_idx, _)| orig_idx)
+            })
+            .collect()
+    }
+
+    fn next_range(&mut self) -> Option<Range<usize>> {
+        if self.start >= self.line.len() {
+            return None;
         }
+        let end = match &mut self.breaks {
+            Some(breaks) => breaks.next().unwrap_or(self.line.len()),
+            None => {
+                // The line breaking algorithm starts afresh after an
+                // allowed break, so we only look at the rest of the
+                // line.
+                let rest = &self.line[self.start..];
+                let idx = unicode_linebreak::linebreaks(rest)
+                    .map(|(idx, _)| idx)
+                    .find(|&idx| idx == rest.len() || is_break_allowed_after(&rest[..idx]));
+                self.start + idx.unwrap_or(rest.len())
+            }
+        };
+        let range = self.start..end;
+        self.start = end;
+        Some(range)
+    }
+}
 
-        None
-    }))
+#[cfg(feature = "unicode-linebreak")]
+impl<'a> Iterator for UnicodeBreakPropertiesIter<'a> {
+    type Item = Word<'a>;
+
+    fn next(&mut self) -> Option<Word<'a>> {
+        let range = self.next_range()?;
+        Some(Word::from(&self.line[range]))
+    }
 }
 
 #[cfg(test)]
@@ -470,6 +1436,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_word_ranges_cover_line() {
+        let line = "  foo bar\u{1b}[0m   baz ";
+        assert_iter_eq!(
+            AsciiSpace.find_word_ranges(line),
+            vec![0..2, 2..6, 6..16, 16..20]
+        );
+
+        #[cfg(feature = "unicode-linebreak")]
+        assert_iter_eq!(
+            UnicodeBreakProperties.find_word_ranges(line),
+            vec![0..2, 2..6, 6..16, 16..20]
+        );
+    }
+
+    #[test]
+    fn find_word_ranges_custom() {
+        fn split_at_comma(line: &str) -> Box<dyn Iterator<Item = Word<'_>> + '_> {
+            Box::new(line.split_inclusive(',').map(Word::from))
+        }
+        assert_iter_eq!(
+            Custom(split_at_comma).find_word_ranges("foo,bar, baz"),
+            vec![0..4, 4..8, 8..12]
+        );
+    }
+
+    #[test]
+    fn find_words_fixed_width() {
+        assert_iter_eq!(FixedWidth(3).find_words(""), vec![]);
+        assert_iter_eq!(
+            FixedWidth(3).find_words("abcdefg"),
+            to_words(vec!["abc", "def", "g"])
+        );
+        assert_iter_eq!(
+            FixedWidth(3).find_words("ab  cd"),
+            to_words(vec!["ab ", " cd"])
+        );
+        assert_iter_eq!(
+            FixedWidth(0).find_words("abc"),
+            to_words(vec!["a", "b", "c"])
+        );
+    }
+
+    #[test]
+    fn find_words_fixed_width_wide_chars() {
+        assert_iter_eq!(
+            FixedWidth(3).find_words("你好世界"),
+            to_words(vec!["你", "好", "世", "界"])
+        );
+        assert_iter_eq!(
+            FixedWidth(4).find_words("a你好"),
+            to_words(vec!["a你", "好"])
+        );
+    }
+
+    #[test]
+    fn find_words_fixed_width_colored_text() {
+        let text = "\u{1b}[31mabcd\u{1b}[0mef";
+        assert_iter_eq!(
+            FixedWidth(3).find_words(text),
+            to_words(vec!["\u{1b}[31mabc", "d\u{1b}[0mef"])
+        );
+    }
+
+    #[test]
+    fn iter_words_matches_find_words() {
+        let line = "  foo bar\u{1b}[0m   baz 你好世界 ";
+        for separator in [AsciiSpace, FixedWidth(3), WordSeparator::new()] {
+            assert_eq!(
+                separator.iter_words(line).collect::<Vec<_>>(),
+                separator.find_words(line).collect::<Vec<_>>(),
+                "{:?}",
+                separator
+            );
+        }
+    }
+
+    #[test]
+    fn find_words_ascii_whitespace() {
+        let line = "foo\tbar \t baz\t";
+        assert_iter_eq!(
+            AsciiWhitespace.find_words(line),
+            vec![
+                Word::new("foo", "\t", ""),
+                Word::new("bar", " \t ", ""),
+                Word::new("baz", "\t", "")
+            ]
+        );
+        assert_iter_eq!(
+            AsciiWhitespace.find_word_ranges(line),
+            vec![0..4, 4..10, 10..14]
+        );
+        assert_iter_eq!(
+            AsciiSpace.find_words("foo\tbar"),
+            vec![Word::from("foo\tbar")]
+        );
+    }
+
+    #[test]
+    fn split_wide_spaces_moves_spaces_to_whitespace() {
+        let text = "a\u{3000}b\u{2003} c\u{3000} ";
+        let word = Word::new("a\u{3000}b\u{2003} c\u{3000}", " ", "");
+        assert_iter_eq!(
+            split_wide_spaces(text, word, true),
+            vec![
+                Word::new("a", "\u{3000}", ""),
+                Word::new("b", "\u{2003} ", ""),
+                Word::new("c", "\u{3000} ", "")
+            ]
+        );
+        assert_iter_eq!(split_wide_spaces(text, word, false), vec![word]);
+        assert_eq!(Word::new("a", "\u{3000}", "").whitespace_columns(), 2);
+    }
+
+    #[test]
+    fn iter_words_custom() {
+        fn split_at_comma(line: &str) -> Box<dyn Iterator<Item = Word<'_>> + '_> {
+            Box::new(line.split_inclusive(',').map(Word::from))
+        }
+        assert_iter_eq!(
+            Custom(split_at_comma).iter_words("foo,bar"),
+            to_words(vec!["foo,", "bar"])
+        );
+    }
+
+    #[test]
+    fn auto_detects_per_line() {
+        assert_iter_eq!(Auto.find_words("foo bar"), to_words(vec!["foo ", "bar"]));
+        #[cfg(feature = "unicode-linebreak")]
+        assert_iter_eq!(
+            Auto.find_words("foo 你好"),
+            to_words(vec!["foo ", "你", "好"])
+        );
+        #[cfg(feature = "unicode-linebreak")]
+        assert_iter_eq!(Auto.find_word_ranges("foo/bar"), vec![0..4, 4..7]);
+        #[cfg(not(feature = "unicode-linebreak"))]
+        assert_iter_eq!(Auto.find_word_ranges("foo/bar"), vec![0..7]);
+    }
+
     #[test]
     fn word_separator_new() {
         #[cfg(feature = "unicode-linebreak")]
@@ -478,4 +1583,97 @@ mod tests {
         #[cfg(not(feature = "unicode-linebreak"))]
         assert!(matches!(WordSeparator::new(), AsciiSpace));
     }
+
+    #[test]
+    fn find_words_markdown_links() {
+        let line = "a [b c](d/e) [f] (g) [h](https://en.wikipedia.org/wiki/I_(J)).";
+        assert_iter_eq!(
+            MarkdownLinkIter::new(line, AsciiSpace, 80),
+            to_words(vec![
+                "a ",
+                "[b c](d/e) ",
+                "[f] ",
+                "(g) ",
+                "[h](https://en.wikipedia.org/wiki/I_(J))."
+            ])
+        );
+        assert_iter_eq!(
+            MarkdownLinkIter::new("[b c](d/e)", AsciiSpace, 5),
+            to_words(vec!["[b ", "c](", "d/", "e)"])
+        );
+        assert_iter_eq!(
+            MarkdownLinkIter::new("[b](c", AsciiSpace, 80),
+            to_words(vec!["[b](c"])
+        );
+    }
+
+    #[test]
+    fn find_word_ranges_markdown_links() {
+        let line = "See [the docs](https://docs.rs/) now";
+        assert_iter_eq!(
+            MarkdownLinks(80).find_word_ranges(line),
+            vec![0..4, 4..33, 33..36]
+        );
+        assert_iter_eq!(
+            MarkdownLinks(10).find_word_ranges(line),
+            vec![0..4, 4..9, 9..15, 15..23, 23..31, 31..33, 33..36]
+        );
+    }
+
+    #[test]
+    fn find_words_punctuation() {
+        let separator = PunctuationSeparator::new(&[',', ';']);
+        assert_iter_eq!(
+            Punctuation(separator).find_words("a,,b ;c;"),
+            to_words(vec!["a,", ",", "b ", ";", "c;"])
+        );
+        assert_iter_eq!(
+            Punctuation(separator).find_word_ranges("a, b;c"),
+            vec![0..3, 3..5, 5..6]
+        );
+
+        let separator = separator.keep_with_previous(false);
+        assert_iter_eq!(
+            Punctuation(separator).find_words("a,,b ;c;"),
+            to_words(vec!["a", ",", ",b ", ";c", ";"])
+        );
+        assert_iter_eq!(
+            Punctuation(separator).find_words("a, b ,c"),
+            to_words(vec!["a", ", b ", ",c"])
+        );
+    }
+
+    #[test]
+    fn parse_display_roundtrip() {
+        let separators = [
+            AsciiSpace,
+            AsciiWhitespace,
+            FixedWidth(3),
+            FixedWidthPadded(3),
+            Auto,
+            MarkdownLinks(20),
+            WordSeparator::new(),
+        ];
+        for separator in separators {
+            assert_eq!(separator.to_string().parse(), Ok(separator));
+        }
+        assert!("fixed-width:x".parse::<WordSeparator>().is_err());
+        assert!("fixed-width".parse::<WordSeparator>().is_err());
+    }
+
+    #[cfg(feature = "unicode-linebreak")]
+    proptest::proptest! {
+        #[test]
+        fn unicode_breaks_found_lazily(
+            line in "[a-z0-9 .,:;!?'\"()\u{ad}\u{a0}\u{200b}\u{200d}\u{2014}\u{3000}-\u{3010}\u{4e00}-\u{4e08}\u{1f1e6}-\u{1f1ea}\u{1f600}-\u{1f604}\u{300}-\u{302}-]{0,30}"
+        ) {
+            let lazy = UnicodeBreakPropertiesIter::new(&line).collect::<Vec<_>>();
+            let eager = UnicodeBreakPropertiesIter {
+                line: &line,
+                start: 0,
+                breaks: Some(UnicodeBreakPropertiesIter::ansi_breaks(&line).into_iter()),
+            };
+            proptest::prop_assert_eq!(lazy, eager.collect::<Vec<_>>());
+        }
+    }
 }
@@ -4,7 +4,11 @@
 //! across lines. The [`WordSplitter`] enum defines this
 //! functionality.
 
+use std::borrow::Cow;
+
+use crate::break_points::hyphenation_penalty;
 use crate::core::{display_width, Word};
+use crate::{Options, ParseError};
 
 /// The `WordSplitter` enum describes where words can be split.
 ///
@@ -34,6 +38,7 @@ use crate::core::{display_width, Word};
 ///
 /// [hyphenation]: https://docs.rs/hyphenation/
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WordSplitter {
     /// Use this as a [`Options.word_splitter`] to avoid any kind of
     /// hyphenation:
@@ -67,6 +72,38 @@ pub enum WordSplitter {
     /// ```
     HyphenSplitter,
 
+    /// `UnicodeHyphenSplitter` works like
+    /// [`WordSplitter::HyphenSplitter`], but it also splits words
+    /// after U+2010 HYPHEN (`'‐'`). If `split_dashes` is `true`,
+    /// words are also split after U+2013 EN DASH (`'–'`) and U+2014
+    /// EM DASH (`'—'`). Dashes are often written without spaces
+    /// around them, as in `"1990–2000"` or `"wait—what"`. The
+    /// U+2011 NON-BREAKING HYPHEN (`'‑'`) is never split.
+    ///
+    /// The hyphens must be surrounded by alphanumeric characters,
+    /// just like for the `HyphenSplitter`. The dashes must be
+    /// surrounded by characters which are not dashes. No `'-'` is
+    /// inserted when a line is broken after a hyphen or a dash.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WordSplitter};
+    ///
+    /// let splitter = WordSplitter::UnicodeHyphenSplitter { split_dashes: true };
+    /// assert_eq!(splitter.split_points("well‐known"), vec![7]);
+    /// assert_eq!(splitter.split_points("non‑breaking"), vec![]);
+    /// assert_eq!(splitter.split_points("1990–2000"), vec![7]);
+    ///
+    /// let options = Options::new(8).word_splitter(splitter);
+    /// assert_eq!(wrap("A well‐known fact", &options),
+    ///            vec!["A well‐", "known", "fact"]);
+    /// ```
+    UnicodeHyphenSplitter {
+        /// Split words after en and em dashes.
+        split_dashes: bool,
+    },
+
     /// Use a custom function as the word splitter.
     ///
     /// This variant lets you implement a custom word splitter using
@@ -85,6 +122,7 @@ pub enum WordSplitter {
     /// assert_eq!(word_splitter.split_points("a_long_identifier"),
     ///            vec![2, 7]);
     /// ```
+    #[cfg_attr(feature = "serde", serde(skip))]
     Custom(fn(word: &str) -> Vec<usize>),
 
     /// A hyphenation dictionary can be used to do language-specific
@@ -95,7 +133,92 @@ pub enum WordSplitter {
     ///
     /// [hyphenation]: https://docs.rs/hyphenation/
     #[cfg(feature = "hyphenation")]
+    #[cfg_attr(feature = "serde", serde(skip))]
     Hyphenation(hyphenation::Standard),
+
+    /// A shared hyphenation dictionary. This works like
+    /// [`WordSplitter::Hyphenation`], but the dictionary is kept
+    /// behind an [`Arc`](std::sync::Arc). Cloning the word splitter
+    /// is thus cheap, which lets you use the same dictionary in many
+    /// [`Options`] and threads:
+    ///
+    /// ```
+    /// #[cfg(feature = "hyphenation")] {
+    ///     use hyphenation::{Language, Load, Standard};
+    ///     use std::sync::Arc;
+    ///     use textwrap::{wrap, Options, WordSplitter};
+    ///
+    ///     let dictionary = Arc::new(Standard::from_embedded(Language::EnglishUS).unwrap());
+    ///     let splitter = WordSplitter::SharedHyphenation(dictionary);
+    ///     let narrow = Options::new(8).word_splitter(splitter.clone());
+    ///     let wide = Options::new(12).word_splitter(splitter);
+    ///     assert_eq!(wrap("Hyphenation", &narrow), vec!["Hyphena-", "tion"]);
+    ///     assert_eq!(wrap("Hyphenation", &wide), vec!["Hyphenation"]);
+    /// }
+    /// ```
+    ///
+    /// **Note:** Only available when the `hyphenation` Cargo feature is
+    /// enabled.
+    #[cfg(feature = "hyphenation")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    SharedHyphenation(std::sync::Arc<hyphenation::Standard>),
+
+    /// Hyphenation with a dictionary for the language of the text.
+    ///
+    /// The language of each paragraph is detected using the
+    /// [whatlang] crate and the embedded hyphenation dictionary for
+    /// that language is used. The given language is used when the
+    /// detection is unreliable, e.g., for very short paragraphs, and
+    /// when there is no embedded dictionary for the detected
+    /// language. A detected language which matches the default, such
+    /// as English for [`Language::EnglishGB`], also uses the default.
+    /// Words are not hyphenated if neither dictionary is embedded.
+    ///
+    /// By default, only the American English dictionary is embedded.
+    /// Enable the `embed_all` feature of the [hyphenation] crate in
+    /// your own `Cargo.toml` file to embed all dictionaries. The
+    /// dictionaries are loaded the first time they are needed and
+    /// are kept in memory afterwards.
+    ///
+    /// Create the word splitter with
+    /// [`WordSplitter::auto_hyphenation`] and use
+    /// [`WordSplitter::resolve`] to find the word splitter used for
+    /// a given text.
+    ///
+    /// **Note:** Only available when the `hyphenation` and
+    /// `whatlang` Cargo features are enabled.
+    ///
+    /// [whatlang]: https://docs.rs/whatlang/
+    /// [hyphenation]: https://docs.rs/hyphenation/
+    /// [`Language::EnglishGB`]: hyphenation::Language::EnglishGB
+    #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    AutoHyphenation(hyphenation::Language),
+
+    /// Combine several word splitters. A word can be split at the
+    /// split points found by any of the word splitters.
+    ///
+    /// This lets you, e.g., split paths at `'/'` in addition to the
+    /// hyphens found by [`WordSplitter::HyphenSplitter`] or a
+    /// hyphenation dictionary, without writing a custom word splitter
+    /// which does both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::WordSplitter;
+    ///
+    /// fn split_at_slash(word: &str) -> Vec<usize> {
+    ///     word.match_indices('/').map(|(idx, _)| idx + 1).collect()
+    /// }
+    ///
+    /// let splitter = WordSplitter::Chain(vec![
+    ///     WordSplitter::HyphenSplitter,
+    ///     WordSplitter::Custom(split_at_slash),
+    /// ]);
+    /// assert_eq!(splitter.split_points("src/word-splitters.rs"), vec![4, 9]);
+    /// ```
+    Chain(Vec<WordSplitter>),
 }
 
 impl std::fmt::Debug for WordSplitter {
@@ -103,9 +226,23 @@ impl std::fmt::Debug for WordSplitter {
         match self {
             WordSplitter::NoHyphenation => f.write_str("NoHyphenation"),
             WordSplitter::HyphenSplitter => f.write_str("HyphenSplitter"),
+            WordSplitter::UnicodeHyphenSplitter { split_dashes } => {
+                write!(
+                    f,
+                    "UnicodeHyphenSplitter {{ split_dashes: {} }}",
+                    split_dashes
+                )
+            }
             WordSplitter::Custom(_) => f.write_str("Custom(...)"),
             #[cfg(feature = "hyphenation")]
             WordSplitter::Hyphenation(dict) => write!(f, "Hyphenation({})", dict.language()),
+            #[cfg(feature = "hyphenation")]
+            WordSplitter::SharedHyphenation(dict) => {
+                write!(f, "SharedHyphenation({})", dict.language())
+            }
+            #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+            WordSplitter::AutoHyphenation(language) => write!(f, "AutoHyphenation({})", language),
+            WordSplitter::Chain(splitters) => write!(f, "Chain({:?})", splitters),
         }
     }
 }
@@ -115,15 +252,153 @@ impl PartialEq<WordSplitter> for WordSplitter {
         match (self, other) {
             (WordSplitter::NoHyphenation, WordSplitter::NoHyphenation) => true,
             (WordSplitter::HyphenSplitter, WordSplitter::HyphenSplitter) => true,
+            (
+                WordSplitter::UnicodeHyphenSplitter { split_dashes: this },
+                WordSplitter::UnicodeHyphenSplitter {
+                    split_dashes: other,
+                },
+            ) => this == other,
             #[cfg(feature = "hyphenation")]
             (WordSplitter::Hyphenation(this_dict), WordSplitter::Hyphenation(other_dict)) => {
                 this_dict.language() == other_dict.language()
             }
+            #[cfg(feature = "hyphenation")]
+            (
+                WordSplitter::SharedHyphenation(this_dict),
+                WordSplitter::SharedHyphenation(other_dict),
+            ) => this_dict.language() == other_dict.language(),
+            #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+            (WordSplitter::AutoHyphenation(this), WordSplitter::AutoHyphenation(other)) => {
+                this == other
+            }
+            (WordSplitter::Chain(this), WordSplitter::Chain(other)) => this == other,
             (_, _) => false,
         }
     }
 }
 
+impl std::hash::Hash for WordSplitter {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            WordSplitter::UnicodeHyphenSplitter { split_dashes } => split_dashes.hash(state),
+            WordSplitter::Chain(splitters) => splitters.hash(state),
+            _ => {}
+        }
+        #[cfg(feature = "hyphenation")]
+        match self {
+            WordSplitter::Hyphenation(dict) => dict.language().hash(state),
+            WordSplitter::SharedHyphenation(dict) => dict.language().hash(state),
+            #[cfg(feature = "whatlang")]
+            WordSplitter::AutoHyphenation(language) => language.hash(state),
+            _ => {}
+        }
+    }
+}
+
+/// Formats the word splitter as `"no-hyphenation"`,
+/// `"hyphen-splitter"`, `"unicode-hyphen-splitter"`,
+/// `"unicode-hyphen-splitter:no-dashes"`, `"hyphenation"`, or
+/// `"custom"`. The language of the hyphenation dictionary is not
+/// included. A [`WordSplitter::Chain`] is formatted as the word
+/// splitters joined by `'+'`, e.g., `"hyphen-splitter+hyphenation"`.
+///
+/// Not all chains survive a round trip through [`FromStr`]: an empty
+/// chain is formatted as `""`, which does not parse, and a chain
+/// nested inside another chain is parsed back as a single flat
+/// chain. The flat chain finds the same split points.
+///
+/// [`FromStr`]: std::str::FromStr
+impl std::fmt::Display for WordSplitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WordSplitter::NoHyphenation => f.write_str("no-hyphenation"),
+            WordSplitter::HyphenSplitter => f.write_str("hyphen-splitter"),
+            WordSplitter::UnicodeHyphenSplitter { split_dashes: true } => {
+                f.write_str("unicode-hyphen-splitter")
+            }
+            WordSplitter::UnicodeHyphenSplitter {
+                split_dashes: false,
+            } => f.write_str("unicode-hyphen-splitter:no-dashes"),
+            WordSplitter::Custom(_) => f.write_str("custom"),
+            #[cfg(feature = "hyphenation")]
+            WordSplitter::Hyphenation(_) | WordSplitter::SharedHyphenation(_) => {
+                f.write_str("hyphenation")
+            }
+            #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+            WordSplitter::AutoHyphenation(_) => f.write_str("auto-hyphenation"),
+            WordSplitter::Chain(splitters) => {
+                for (idx, splitter) in splitters.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str("+")?;
+                    }
+                    write!(f, "{}", splitter)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Parses `"no-hyphenation"`, `"hyphen-splitter"`,
+/// `"unicode-hyphen-splitter"`, `"unicode-hyphen-splitter:no-dashes"`,
+/// and `"hyphenation"`. The latter uses the embedded American English
+/// dictionary. Names joined by `'+'` are parsed as a
+/// [`WordSplitter::Chain`]. Empty names are rejected, so an empty
+/// chain cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{ParseError, WordSplitter};
+///
+/// assert_eq!("no-hyphenation".parse(), Ok(WordSplitter::NoHyphenation));
+/// assert_eq!("hyphen-splitter".parse(), Ok(WordSplitter::HyphenSplitter));
+/// assert_eq!("hyphen-splitter+no-hyphenation".parse(),
+///            Ok(WordSplitter::Chain(vec![WordSplitter::HyphenSplitter,
+///                                        WordSplitter::NoHyphenation])));
+///
+/// #[cfg(not(feature = "hyphenation"))]
+/// assert_eq!("hyphenation".parse::<WordSplitter>(),
+///            Err(ParseError::Unsupported { name: "hyphenation", feature: "hyphenation" }));
+/// ```
+impl std::str::FromStr for WordSplitter {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "no-hyphenation" => Ok(WordSplitter::NoHyphenation),
+            "hyphen-splitter" => Ok(WordSplitter::HyphenSplitter),
+            "unicode-hyphen-splitter" => {
+                Ok(WordSplitter::UnicodeHyphenSplitter { split_dashes: true })
+            }
+            "unicode-hyphen-splitter:no-dashes" => Ok(WordSplitter::UnicodeHyphenSplitter {
+                split_dashes: false,
+            }),
+            #[cfg(feature = "hyphenation")]
+            "hyphenation" => {
+                use hyphenation::Load;
+                let language = hyphenation::Language::EnglishUS;
+                // The dictionary is embedded with the "embed_en-us"
+                // feature, so loading it cannot fail.
+                let dictionary = hyphenation::Standard::from_embedded(language).unwrap();
+                Ok(WordSplitter::Hyphenation(dictionary))
+            }
+            #[cfg(not(feature = "hyphenation"))]
+            "hyphenation" => Err(ParseError::Unsupported {
+                name: "hyphenation",
+                feature: "hyphenation",
+            }),
+            _ if s.contains('+') => s
+                .split('+')
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map(WordSplitter::Chain),
+            _ => Err(ParseError::Unknown(s.to_string())),
+        }
+    }
+}
+
 impl WordSplitter {
     /// Return all possible indices where `word` can be split.
     ///
@@ -140,7 +415,36 @@ impl WordSplitter {
     /// assert_eq!(WordSplitter::HyphenSplitter.split_points("can-be-split"), vec![4, 7]);
     /// assert_eq!(WordSplitter::Custom(|word| vec![word.len()/2]).split_points("middle"), vec![3]);
     /// ```
+    ///
+    /// Dictionaries are used for all words, use
+    /// [`WordSplitter::split_points_with()`] with
+    /// [`is_hyphenatable()`] to skip acronyms and identifiers.
     pub fn split_points(&self, word: &str) -> Vec<usize> {
+        self.split_points_with(word, |_| true)
+    }
+
+    /// Return all possible indices where `word` can be split, using
+    /// `hyphenate` to decide if a dictionary should be used.
+    ///
+    /// This is like [`WordSplitter::split_points()`], except that
+    /// `hyphenate` is called before a hyphenation dictionary is
+    /// consulted. No split points are found by the dictionary if
+    /// `hyphenate` returns `false` for `word`. The other word
+    /// splitters ignore `hyphenate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::WordSplitter;
+    ///
+    /// let splitter = WordSplitter::HyphenSplitter;
+    /// assert_eq!(splitter.split_points_with("HTTP-client", |_| false), vec![5]);
+    /// ```
+    #[cfg_attr(
+        not(feature = "hyphenation"),
+        allow(unused_variables, clippy::only_used_in_recursion)
+    )]
+    pub fn split_points_with(&self, word: &str, hyphenate: fn(&str) -> bool) -> Vec<usize> {
         match self {
             WordSplitter::NoHyphenation => Vec::new(),
             WordSplitter::HyphenSplitter => {
@@ -162,14 +466,316 @@ impl WordSplitter {
 
                 splits
             }
+            WordSplitter::UnicodeHyphenSplitter { split_dashes } => {
+                let mut splits = Vec::new();
+                let mut prev = None;
+                let mut chars = word.char_indices().peekable();
+                while let Some((idx, ch)) = chars.next() {
+                    let next = chars.peek().map(|&(_, ch)| ch);
+                    let is_split = match ch {
+                        '-' | '\u{2010}' => {
+                            prev.filter(|ch: &char| ch.is_alphanumeric()).is_some()
+                                && next.filter(|ch| ch.is_alphanumeric()).is_some()
+                        }
+                        '\u{2013}' | '\u{2014}' if *split_dashes => {
+                            prev.filter(|&ch| !is_dash(ch)).is_some()
+                                && next.filter(|&ch| !is_dash(ch)).is_some()
+                        }
+                        _ => false,
+                    };
+                    if is_split {
+                        splits.push(idx + ch.len_utf8());
+                    }
+                    prev = Some(ch);
+                }
+                splits
+            }
             WordSplitter::Custom(splitter_func) => splitter_func(word),
             #[cfg(feature = "hyphenation")]
+            WordSplitter::Hyphenation(_) | WordSplitter::SharedHyphenation(_)
+                if !hyphenate(word) =>
+            {
+                Vec::new()
+            }
+            #[cfg(feature = "hyphenation")]
             WordSplitter::Hyphenation(dictionary) => {
                 use hyphenation::Hyphenator;
                 dictionary.hyphenate(word).breaks
             }
+            #[cfg(feature = "hyphenation")]
+            WordSplitter::SharedHyphenation(dictionary) => {
+                use hyphenation::Hyphenator;
+                dictionary.hyphenate(word).breaks
+            }
+            #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+            WordSplitter::AutoHyphenation(_) => {
+                self.resolve(word).split_points_with(word, hyphenate)
+            }
+            WordSplitter::Chain(splitters) => {
+                let mut splits = splitters
+                    .iter()
+                    .flat_map(|splitter| splitter.split_points_with(word, hyphenate))
+                    .collect::<Vec<_>>();
+                splits.sort_unstable();
+                splits.dedup();
+                splits
+            }
         }
     }
+
+    /// Create a [`WordSplitter::AutoHyphenation`] which falls back to
+    /// the `default` language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyphenation::Language;
+    /// use textwrap::{wrap, Options, WordSplitter};
+    ///
+    /// let splitter = WordSplitter::auto_hyphenation(Language::EnglishUS);
+    /// let options = Options::new(8).word_splitter(splitter);
+    /// assert_eq!(wrap("Oxidation is the loss of electrons.", &options),
+    ///            vec!["Oxida-", "tion is", "the loss", "of elec-", "trons."]);
+    /// ```
+    ///
+    /// **Note:** Only available when the `hyphenation` and
+    /// `whatlang` Cargo features are enabled.
+    #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+    pub fn auto_hyphenation(default: hyphenation::Language) -> Self {
+        WordSplitter::AutoHyphenation(default)
+    }
+
+    /// The word splitter to use for words in `text`.
+    ///
+    /// This returns the word splitter itself, except for
+    /// [`WordSplitter::AutoHyphenation`], where the language of
+    /// `text` is detected and a [`WordSplitter::SharedHyphenation`]
+    /// with the dictionary for the language is returned. The members
+    /// of a [`WordSplitter::Chain`] are resolved in the same way. The
+    /// wrapping functions call this for each paragraph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::WordSplitter;
+    ///
+    /// let splitter = WordSplitter::HyphenSplitter;
+    /// assert_eq!(*splitter.resolve("Some text"), WordSplitter::HyphenSplitter);
+    /// ```
+    #[cfg_attr(
+        not(all(feature = "hyphenation", feature = "whatlang")),
+        allow(unused_variables)
+    )]
+    pub fn resolve(&self, text: &str) -> Cow<'_, WordSplitter> {
+        match self {
+            #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+            WordSplitter::AutoHyphenation(default) => {
+                match auto_hyphenation::dictionary(text, *default) {
+                    Some(dictionary) => Cow::Owned(WordSplitter::SharedHyphenation(dictionary)),
+                    None => Cow::Owned(WordSplitter::NoHyphenation),
+                }
+            }
+            #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+            WordSplitter::Chain(splitters) if !self.is_resolved() => {
+                Cow::Owned(WordSplitter::Chain(
+                    splitters
+                        .iter()
+                        .map(|splitter| splitter.resolve(text).into_owned())
+                        .collect(),
+                ))
+            }
+            _ => Cow::Borrowed(self),
+        }
+    }
+
+    /// Check if this word splitter is, or chains together, a
+    /// [`WordSplitter::Custom`] function.
+    pub(crate) fn is_custom(&self) -> bool {
+        match self {
+            WordSplitter::Custom(_) => true,
+            WordSplitter::Chain(splitters) => splitters.iter().any(WordSplitter::is_custom),
+            _ => false,
+        }
+    }
+
+    /// Check if this word splitter is, or chains together, a
+    /// [`WordSplitter::UnicodeHyphenSplitter`]. No extra hyphen is
+    /// inserted after Unicode hyphens and dashes when this is the
+    /// case.
+    pub(crate) fn has_unicode_hyphens(&self) -> bool {
+        match self {
+            WordSplitter::UnicodeHyphenSplitter { .. } => true,
+            WordSplitter::Chain(splitters) => {
+                splitters.iter().any(WordSplitter::has_unicode_hyphens)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if [`WordSplitter::resolve`] returns the word splitter
+    /// itself for all texts.
+    #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+    fn is_resolved(&self) -> bool {
+        match self {
+            WordSplitter::AutoHyphenation(_) => false,
+            WordSplitter::Chain(splitters) => splitters.iter().all(WordSplitter::is_resolved),
+            _ => true,
+        }
+    }
+}
+
+/// Check if `ch` is a hyphen or a dash.
+fn is_dash(ch: char) -> bool {
+    matches!(ch, '-' | '\u{2010}' | '\u{2011}' | '\u{2013}' | '\u{2014}')
+}
+
+/// Check if `word` should be hyphenated using a dictionary.
+///
+/// Hyphenation dictionaries are made for ordinary words. They will
+/// happily split acronyms, version numbers, and identifiers from
+/// source code, which looks odd: "NA-SA" or "get-Value". This
+/// function returns `false` for words which
+///
+/// * are written in all caps, such as "NASA" or "HTTP",
+/// * contain a digit, such as "HTTP2" or "x86",
+/// * look like identifiers, i.e., contain a `'_'` or an uppercase
+///   letter after a lowercase letter, such as "snake_case" or
+///   "getValue".
+///
+/// A leading uppercase letter is fine, so capitalized words are
+/// still hyphenated. All words are hyphenated by default, pass this
+/// function to [`Options::hyphenation_filter`] to skip these words.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::word_splitters::is_hyphenatable;
+///
+/// assert!(is_hyphenatable("hyphenation"));
+/// assert!(is_hyphenatable("Hyphenation,"));
+/// assert!(!is_hyphenatable("NASA"));
+/// assert!(!is_hyphenatable("HTTP2"));
+/// assert!(!is_hyphenatable("getValue"));
+/// assert!(!is_hyphenatable("snake_case"));
+/// ```
+pub fn is_hyphenatable(word: &str) -> bool {
+    let mut letters = 0;
+    let mut uppercase = 0;
+    let mut prev_lowercase = false;
+    for ch in word.chars() {
+        if ch.is_numeric() || ch == '_' {
+            return false;
+        }
+        if ch.is_uppercase() {
+            if prev_lowercase {
+                return false;
+            }
+            uppercase += 1;
+        }
+        if ch.is_alphabetic() {
+            letters += 1;
+        }
+        prev_lowercase = ch.is_lowercase();
+    }
+    letters < 2 || uppercase < letters
+}
+
+#[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+mod auto_hyphenation {
+    use std::sync::{Arc, Mutex};
+
+    use hyphenation::{Language, Load, Standard};
+    use whatlang::Lang;
+
+    /// Dictionaries loaded so far. A `None` entry means that the
+    /// dictionary is not embedded.
+    #[allow(clippy::type_complexity)]
+    static DICTIONARIES: Mutex<Vec<(Language, Option<Arc<Standard>>)>> = Mutex::new(Vec::new());
+
+    /// The dictionary for the language of `text`, falling back to
+    /// `default`.
+    pub(super) fn dictionary(text: &str, default: Language) -> Option<Arc<Standard>> {
+        let detected = whatlang::detect(text)
+            .filter(|info| info.is_reliable())
+            .and_then(|info| language(info.lang()))
+            .filter(|&language| base_code(language) != base_code(default));
+        detected.and_then(load).or_else(|| load(default))
+    }
+
+    fn load(language: Language) -> Option<Arc<Standard>> {
+        // The lock is only poisoned if loading a dictionary panicked,
+        // the cached dictionaries are still valid in that case.
+        let mut dictionaries = DICTIONARIES.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some((_, dictionary)) = dictionaries.iter().find(|(lang, _)| *lang == language) {
+            return dictionary.clone();
+        }
+        let dictionary = Standard::from_embedded(language).ok().map(Arc::new);
+        dictionaries.push((language, dictionary.clone()));
+        dictionary
+    }
+
+    /// The language code without the region or variant, e.g., `"en"`
+    /// for both `"en-us"` and `"en-gb"`.
+    fn base_code(language: Language) -> &'static str {
+        let code = language.code();
+        code.split('-').next().unwrap_or(code)
+    }
+
+    /// The hyphenation language for a detected language.
+    fn language(lang: Lang) -> Option<Language> {
+        let language = match lang {
+            Lang::Afr => Language::Afrikaans,
+            Lang::Bel => Language::Belarusian,
+            Lang::Ben => Language::Bengali,
+            Lang::Bul => Language::Bulgarian,
+            Lang::Cat => Language::Catalan,
+            Lang::Ces => Language::Czech,
+            Lang::Dan => Language::Danish,
+            Lang::Deu => Language::German1996,
+            Lang::Ell => Language::GreekMono,
+            Lang::Eng => Language::EnglishUS,
+            Lang::Epo => Language::Esperanto,
+            Lang::Est => Language::Estonian,
+            Lang::Fin => Language::Finnish,
+            Lang::Fra => Language::French,
+            Lang::Guj => Language::Gujarati,
+            Lang::Hin => Language::Hindi,
+            Lang::Hrv => Language::Croatian,
+            Lang::Hun => Language::Hungarian,
+            Lang::Hye => Language::Armenian,
+            Lang::Ind => Language::Indonesian,
+            Lang::Ita => Language::Italian,
+            Lang::Kan => Language::Kannada,
+            Lang::Kat => Language::Georgian,
+            Lang::Lat => Language::Latin,
+            Lang::Lav => Language::Latvian,
+            Lang::Lit => Language::Lithuanian,
+            Lang::Mal => Language::Malayalam,
+            Lang::Mar => Language::Marathi,
+            Lang::Mkd => Language::Macedonian,
+            Lang::Nld => Language::Dutch,
+            Lang::Nob => Language::NorwegianBokmal,
+            Lang::Ori => Language::Oriya,
+            Lang::Pan => Language::Panjabi,
+            Lang::Pol => Language::Polish,
+            Lang::Por => Language::Portuguese,
+            Lang::Ron => Language::Romanian,
+            Lang::Rus => Language::Russian,
+            Lang::Slk => Language::Slovak,
+            Lang::Slv => Language::Slovenian,
+            Lang::Spa => Language::Spanish,
+            Lang::Srp => Language::SerbianCyrillic,
+            Lang::Swe => Language::Swedish,
+            Lang::Tam => Language::Tamil,
+            Lang::Tel => Language::Telugu,
+            Lang::Tha => Language::Thai,
+            Lang::Tuk => Language::Turkmen,
+            Lang::Tur => Language::Turkish,
+            Lang::Ukr => Language::Ukrainian,
+            _ => return None,
+        };
+        Some(language)
+    }
 }
 
 /// Split words into smaller words according to the split points given
@@ -178,48 +784,172 @@ impl WordSplitter {
 /// Note that we split all words, regardless of their length. This is
 /// to more cleanly separate the business of splitting (including
 /// automatic hyphenation) from the business of word wrapping.
+///
+/// This function takes the word splitter directly, which makes it
+/// easy to use in custom wrapping pipelines. Use
+/// [`split_words_with_options()`] if you want the splitting to
+/// respect your [`Options`], and [`split_words_with_payload()`] if
+/// your words carry a payload.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::word_splitters::split_words;
+/// use textwrap::WordSplitter;
+///
+/// let words = vec![Word::from("foo-bar "), Word::from("baz")];
+/// assert_eq!(split_words(words, &WordSplitter::HyphenSplitter).collect::<Vec<_>>(),
+///            vec![Word::from("foo-"), Word::from("bar "), Word::from("baz")]);
+/// ```
 pub fn split_words<'a, I>(
     words: I,
     word_splitter: &'a WordSplitter,
 ) -> impl Iterator<Item = Word<'a>>
 where
     I: IntoIterator<Item = Word<'a>>,
+{
+    split_words_with_payload(words, word_splitter)
+}
+
+/// Split words carrying a payload into smaller words according to
+/// the split points given by `word_splitter`.
+///
+/// This is like [`split_words()`], but the words can have any
+/// [`Word::payload`]. The payload is cloned onto every piece of a
+/// split word.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::word_splitters::split_words_with_payload;
+/// use textwrap::WordSplitter;
+///
+/// let words = vec![Word::from("foo-bar ").with_payload(1), Word::from("baz").with_payload(2)];
+/// let payloads = split_words_with_payload(words, &WordSplitter::HyphenSplitter)
+///     .map(|word| (word.word, word.payload))
+///     .collect::<Vec<_>>();
+/// assert_eq!(payloads, vec![("foo-", 1), ("bar", 1), ("baz", 2)]);
+/// ```
+pub fn split_words_with_payload<'a, T, I>(
+    words: I,
+    word_splitter: &'a WordSplitter,
+) -> impl Iterator<Item = Word<'a, T>>
+where
+    T: Clone + 'a,
+    I: IntoIterator<Item = Word<'a, T>>,
 {
     words.into_iter().flat_map(move |word| {
-        let mut prev = 0;
-        let mut split_points = word_splitter.split_points(&word).into_iter();
-        std::iter::from_fn(move || {
-            if let Some(idx) = split_points.next() {
-                let need_hyphen = !word[..idx].ends_with('-');
-                let w = Word {
-                    word: &word.word[prev..idx],
-                    width: display_width(&word[prev..idx]),
-                    whitespace: "",
-                    penalty: if need_hyphen { "-" } else { "" },
-                };
-                prev = idx;
-                return Some(w);
-            }
+        let split_points = word_splitter.split_points(&word);
+        split_word_at(word, split_points, "-", word_splitter.has_unicode_hyphens())
+    })
+}
 
-            if prev < word.word.len() || prev == 0 {
-                let w = Word {
-                    word: &word.word[prev..],
-                    width: display_width(&word[prev..]),
-                    whitespace: word.whitespace,
-                    penalty: word.penalty,
-                };
-                prev = word.word.len() + 1;
-                return Some(w);
-            }
+/// Split words into smaller words using the word splitter from
+/// `options`.
+///
+/// This is like [`split_words()`], but it uses
+/// [`Options::word_splitter`] and drops split points which would
+/// leave fragments shorter than [`Options::min_fragment_lengths`].
+///
+/// The [`Options::break_filter`] is not applied since it needs to
+/// see the entire line of text, not just the individual words.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::word_splitters::split_words_with_options;
+/// use textwrap::Options;
+///
+/// let options = Options::new(80).min_fragment_lengths((2, 2));
+/// let words = vec![Word::from("e-mail "), Word::from("re-do")];
+/// assert_eq!(split_words_with_options(words, &options).collect::<Vec<_>>(),
+///            vec![Word::from("e-mail "), Word::from("re-"), Word::from("do")]);
+/// ```
+pub fn split_words_with_options<'a, T, I>(
+    words: I,
+    options: &'a Options<'_>,
+) -> impl Iterator<Item = Word<'a, T>>
+where
+    T: Clone + 'a,
+    I: IntoIterator<Item = Word<'a, T>>,
+    I::IntoIter: 'a,
+{
+    words.into_iter().flat_map(move |word| {
+        let mut split_points = options
+            .word_splitter
+            .split_points_with(&word, options.hyphenation_filter.unwrap_or(|_| true));
+        retain_long_fragments(&word, &mut split_points, options.min_fragment_lengths);
+        let unicode_hyphens = options.word_splitter.has_unicode_hyphens();
+        split_word_at(word, split_points, options.hyphen, unicode_hyphens)
+    })
+}
 
-            None
-        })
+/// Remove the split points in `word` which leave fewer alphanumeric
+/// characters before or after them than given by
+/// `min_fragment_lengths`.
+pub(crate) fn retain_long_fragments(
+    word: &str,
+    split_points: &mut Vec<usize>,
+    min_fragment_lengths: (usize, usize),
+) {
+    let (min_leading, min_trailing) = min_fragment_lengths;
+    if min_leading <= 1 && min_trailing <= 1 {
+        return;
+    }
+    let fragment_len = |fragment: &str| fragment.chars().filter(|ch| ch.is_alphanumeric()).count();
+    split_points.retain(|&idx| {
+        fragment_len(&word[..idx]) >= min_leading && fragment_len(&word[idx..]) >= min_trailing
+    });
+}
+
+/// Split `word` into smaller words at the given split points. The
+/// `hyphen` is used as the penalty, see [`hyphenation_penalty()`].
+pub(crate) fn split_word_at<'a, T: Clone>(
+    word: Word<'a, T>,
+    split_points: Vec<usize>,
+    hyphen: &'static str,
+    unicode_hyphens: bool,
+) -> impl Iterator<Item = Word<'a, T>> {
+    let mut prev = 0;
+    let mut split_points = split_points.into_iter();
+    std::iter::from_fn(move || {
+        if let Some(idx) = split_points.next() {
+            let w = Word {
+                word: &word.word[prev..idx],
+                width: display_width(&word[prev..idx]),
+                whitespace: "",
+                tab_width: word.tab_width,
+                penalty: hyphenation_penalty(&word, idx, hyphen, unicode_hyphens),
+                payload: word.payload.clone(),
+            };
+            prev = idx;
+            return Some(w);
+        }
+
+        if prev < word.word.len() || prev == 0 {
+            let w = Word {
+                word: &word.word[prev..],
+                width: display_width(&word[prev..]),
+                whitespace: word.whitespace,
+                tab_width: word.tab_width,
+                penalty: word.penalty,
+                payload: word.payload.clone(),
+            };
+            prev = word.word.len() + 1;
+            return Some(w);
+        }
+
+        None
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::DEFAULT_TAB_WIDTH;
 
     // Like assert_eq!, but the left expression is an iterator.
     macro_rules! assert_iter_eq {
@@ -279,13 +1009,17 @@ mod tests {
                     word: "foo",
                     width: 3,
                     whitespace: "",
-                    penalty: "-"
+                    tab_width: DEFAULT_TAB_WIDTH,
+                    penalty: "-",
+                    payload: ()
                 },
                 Word {
                     word: "bar",
                     width: 3,
                     whitespace: "",
-                    penalty: ""
+                    tab_width: DEFAULT_TAB_WIDTH,
+                    penalty: "",
+                    payload: ()
                 }
             ]
         );
@@ -300,15 +1034,186 @@ mod tests {
                     word: "fo-",
                     width: 3,
                     whitespace: "",
-                    penalty: ""
+                    tab_width: DEFAULT_TAB_WIDTH,
+                    penalty: "",
+                    payload: ()
                 },
                 Word {
                     word: "bar",
                     width: 3,
                     whitespace: "",
-                    penalty: ""
+                    tab_width: DEFAULT_TAB_WIDTH,
+                    penalty: "",
+                    payload: ()
                 }
             ]
         );
     }
+
+    #[test]
+    fn parse_display_roundtrip() {
+        for splitter in [
+            WordSplitter::NoHyphenation,
+            WordSplitter::HyphenSplitter,
+            WordSplitter::UnicodeHyphenSplitter { split_dashes: true },
+            WordSplitter::UnicodeHyphenSplitter {
+                split_dashes: false,
+            },
+            WordSplitter::Chain(vec![
+                WordSplitter::HyphenSplitter,
+                WordSplitter::UnicodeHyphenSplitter {
+                    split_dashes: false,
+                },
+            ]),
+        ] {
+            assert_eq!(splitter.to_string().parse(), Ok(splitter));
+        }
+        assert!("custom".parse::<WordSplitter>().is_err());
+        assert!("hyphen-splitter+custom".parse::<WordSplitter>().is_err());
+    }
+
+    #[test]
+    fn chain_display_edge_cases() {
+        let empty = WordSplitter::Chain(Vec::new());
+        assert_eq!(empty.to_string(), "");
+        assert!(empty.to_string().parse::<WordSplitter>().is_err());
+        assert!("hyphen-splitter+".parse::<WordSplitter>().is_err());
+
+        let nested = WordSplitter::Chain(vec![
+            WordSplitter::NoHyphenation,
+            WordSplitter::Chain(vec![
+                WordSplitter::HyphenSplitter,
+                WordSplitter::NoHyphenation,
+            ]),
+        ]);
+        let flat = nested.to_string().parse::<WordSplitter>().unwrap();
+        assert_eq!(
+            flat,
+            WordSplitter::Chain(vec![
+                WordSplitter::NoHyphenation,
+                WordSplitter::HyphenSplitter,
+                WordSplitter::NoHyphenation,
+            ])
+        );
+        assert_eq!(flat.split_points("a-b"), nested.split_points("a-b"));
+    }
+
+    #[test]
+    #[cfg(feature = "hyphenation")]
+    fn parse_hyphenation() {
+        let splitter = "hyphenation".parse::<WordSplitter>().unwrap();
+        assert_eq!(splitter.to_string(), "hyphenation");
+        assert_eq!(splitter.split_points("hyphenation"), vec![2, 6, 7]);
+    }
+
+    #[test]
+    fn unicode_hyphen_splitter() {
+        let splitter = WordSplitter::UnicodeHyphenSplitter { split_dashes: true };
+        assert_eq!(splitter.split_points("foo-bar‐baz"), vec![4, 10]);
+        assert_eq!(splitter.split_points("a—b–c"), vec![4, 8]);
+        assert_eq!(splitter.split_points("—foo——"), vec![]);
+        assert_eq!(splitter.split_points("‐foo‐"), vec![]);
+
+        let splitter = WordSplitter::UnicodeHyphenSplitter {
+            split_dashes: false,
+        };
+        assert_eq!(splitter.split_points("a—b‐c"), vec![8]);
+    }
+
+    #[test]
+    fn unicode_hyphen_splitter_no_inserted_hyphen() {
+        let splitter = WordSplitter::UnicodeHyphenSplitter { split_dashes: true };
+        assert_iter_eq!(
+            split_words(vec![Word::from("wait—what")], &splitter),
+            vec![Word::from("wait—"), Word::from("what")]
+        );
+    }
+
+    #[test]
+    fn other_splitters_insert_hyphen_after_dash() {
+        let splitter = WordSplitter::Custom(|word| match word.find('—') {
+            Some(idx) => vec![idx + '—'.len_utf8()],
+            None => vec![],
+        });
+        let mut expected = Word::from("wait—");
+        expected.penalty = "-";
+        assert_iter_eq!(
+            split_words(vec![Word::from("wait—what")], &splitter),
+            vec![expected, Word::from("what")]
+        );
+    }
+
+    #[test]
+    fn chain_merges_split_points() {
+        let splitter = WordSplitter::Chain(vec![
+            WordSplitter::Custom(|word| vec![word.len() / 2]),
+            WordSplitter::HyphenSplitter,
+            WordSplitter::UnicodeHyphenSplitter { split_dashes: true },
+        ]);
+        assert_eq!(splitter.split_points("foo-bar—baz"), vec![4, 6, 10]);
+        assert_eq!(
+            WordSplitter::Chain(Vec::new()).split_points("foo-bar"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn is_hyphenatable_words() {
+        assert!(is_hyphenatable("word"));
+        assert!(is_hyphenatable("Word."));
+        assert!(is_hyphenatable("I"));
+        assert!(is_hyphenatable(""));
+        assert!(!is_hyphenatable("ANNOUNCEMENT"));
+        assert!(!is_hyphenatable("(NASA)"));
+        assert!(!is_hyphenatable("x86"));
+        assert!(!is_hyphenatable("iPhone"));
+    }
+
+    #[test]
+    #[cfg(feature = "hyphenation")]
+    fn hyphenation_skips_acronyms() {
+        use hyphenation::{Language, Load, Standard};
+
+        let dictionary = Standard::from_embedded(Language::EnglishUS).unwrap();
+        let splitter = WordSplitter::Hyphenation(dictionary);
+        assert_eq!(splitter.split_points("ANNOUNCEMENT"), vec![2, 8]);
+        assert_eq!(
+            splitter.split_points_with("ANNOUNCEMENT", is_hyphenatable),
+            vec![]
+        );
+        assert_eq!(
+            splitter.split_points_with("snake_case", is_hyphenatable),
+            vec![]
+        );
+        assert_eq!(
+            splitter.split_points_with("announcement", is_hyphenatable),
+            vec![2, 8]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "hyphenation", feature = "whatlang"))]
+    fn auto_hyphenation_resolve() {
+        use hyphenation::{Language, Load, Standard};
+
+        let splitter = WordSplitter::auto_hyphenation(Language::EnglishUS);
+        let english = "The quick brown fox jumps over the lazy dog and runs away.";
+        let dictionary = Standard::from_embedded(Language::EnglishUS).unwrap();
+        let expected = WordSplitter::SharedHyphenation(std::sync::Arc::new(dictionary));
+        assert_eq!(*splitter.resolve(english), expected);
+
+        // Only the English dictionary is embedded, so German text
+        // falls back to the default.
+        let german = "Der schnelle braune Fuchs springt über den faulen Hund.";
+        assert_eq!(*splitter.resolve(german), expected);
+
+        let chain = WordSplitter::Chain(vec![WordSplitter::HyphenSplitter, splitter]);
+        assert_eq!(
+            *chain.resolve(german),
+            WordSplitter::Chain(vec![WordSplitter::HyphenSplitter, expected])
+        );
+
+        let splitter = WordSplitter::auto_hyphenation(Language::EnglishGB);
+        assert_eq!(*splitter.resolve(english), WordSplitter::NoHyphenation);
+    }
 }
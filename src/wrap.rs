@@ -1,10 +1,18 @@
 //! Functions for wrapping text.
 
 use std::borrow::Cow;
-
-use crate::core::{break_words, display_width, Word};
-use crate::word_splitters::split_words;
-use crate::Options;
+use std::ops::Range;
+
+use crate::break_points::{is_protected_span, split_filtered_words};
+use crate::core::{
+    break_words_with, ch_width_with, display_width, display_width_with, line_widths_from,
+    total_width, Fragment, Word,
+};
+use crate::line_ending::replace_unicode_separators;
+use crate::{
+    lines_with_endings, Compact, Error, MinWidth, Normalization, Options, WordOverflow,
+    WordSeparator, WrapAlgorithm,
+};
 
 /// Wrap a line of text at a given width.
 ///
@@ -14,6 +22,10 @@ use crate::Options;
 /// including a final `'\n'`. Please use [`fill()`](crate::fill()) if
 /// you need a [`String`] instead.
 ///
+/// The text is split into lines at both `"\n"` and `"\r\n"`.
+/// [`Options::line_ending`] does not change this, it only decides
+/// how [`fill()`](crate::fill()) joins the wrapped lines.
+///
 /// The easiest way to use this function is to pass an integer for
 /// `width_or_options`:
 ///
@@ -181,74 +193,813 @@ pub fn wrap<'a, Opt>(text: &str, width_or_options: Opt) -> Vec<Cow<'_, str>>
 where
     Opt: Into<Options<'a>>,
 {
-    let options: Options = width_or_options.into();
-    let line_ending_str = options.line_ending.as_str();
+    // Wrapping text cannot overflow since the line widths are
+    // integers, and it cannot fail with `MinWidth::Error` since that
+    // is replaced, see `try_wrap`.
+    let options = width_or_options.into().proceed_on_min_width_error();
+    try_wrap(text, options).unwrap()
+}
 
+/// Wrap a line of text at a given width, returning an error instead
+/// of panicking.
+///
+/// This is like [`wrap()`], but the errors listed below are returned
+/// instead of causing a panic. In practice, [`wrap()`] does not fail
+/// for these reasons either: the line widths are integers, so the
+/// cost computation in the optimal-fit algorithm cannot overflow,
+/// and [`wrap()`] treats [`MinWidth::Error`](crate::MinWidth::Error)
+/// like [`MinWidth::Proceed`](crate::MinWidth::Proceed). Use this
+/// function if you want to handle the errors explicitly, or if you
+/// want to detect a too small width.
+///
+/// If you only need the size of the wrapped text, e.g., in a layout
+/// pass before the text is drawn, use
+/// [`try_wrap_stats()`](crate::try_wrap_stats()) or
+/// [`try_fill_height()`](crate::try_fill_height()). They compute the
+/// line widths and the number of lines without building the lines.
+///
+/// # Errors
+///
+/// Returns [`Error::Overflow`] if the optimal-fit algorithm
+/// overflows, and [`Error::WidthTooSmall`] if
+/// [`Options::min_width`] is [`MinWidth::Error`](crate::MinWidth::Error)
+/// and there is too little room for the text.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::try_wrap;
+///
+/// assert_eq!(try_wrap("Memory safety without garbage collection.", 15),
+///            Ok(vec!["Memory safety".into(),
+///                    "without garbage".into(),
+///                    "collection.".into()]));
+/// ```
+pub fn try_wrap<'a, Opt>(text: &str, width_or_options: Opt) -> Result<Vec<Cow<'_, str>>, Error>
+where
+    Opt: Into<Options<'a>>,
+{
     let mut lines = Vec::new();
-    for line in text.split(line_ending_str) {
-        wrap_single_line(line, &options, &mut lines);
+    wrap_lines(text, &width_or_options.into(), &mut lines)?;
+    Ok(lines)
+}
+
+/// Wrap a line of text at a given width and append the lines to an
+/// existing vector.
+///
+/// This is like [`wrap()`], but the lines are pushed onto `lines`
+/// instead of being returned in a new vector. This lets you reuse
+/// the allocation of `lines` when you wrap text repeatedly, e.g., in
+/// a render loop. The existing lines are left untouched: the
+/// [`Options::initial_indent`] is used for the first line added.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap_into, Options};
+///
+/// let options = Options::new(10).initial_indent("* ").subsequent_indent("  ");
+/// let mut lines = Vec::new();
+/// wrap_into("Lists of things", &options, &mut lines);
+/// wrap_into("Other things", &options, &mut lines);
+/// assert_eq!(lines, vec!["* Lists of", "  things", "* Other", "  things"]);
+///
+/// lines.clear();
+/// wrap_into("Reused", &options, &mut lines);
+/// assert_eq!(lines, vec!["* Reused"]);
+/// ```
+pub fn wrap_into<'t, 'a, Opt>(text: &'t str, width_or_options: Opt, lines: &mut Vec<Cow<'t, str>>)
+where
+    Opt: Into<Options<'a>>,
+{
+    let mut sink = AppendedLines {
+        start: lines.len(),
+        lines,
+    };
+    // Wrapping text cannot overflow since the line widths are
+    // integers, and it cannot fail with `MinWidth::Error` since that
+    // is replaced, see `try_wrap`.
+    let options = width_or_options.into().proceed_on_min_width_error();
+    wrap_lines(text, &options, &mut sink).unwrap();
+}
+
+/// Wrap text and return the words placed on each line.
+///
+/// This computes the same line breaks as [`wrap()`], but instead of
+/// returning the lines, it returns the range of word indices placed
+/// on each line. The words are the words found by
+/// [`Options::word_separator`] in each line of `text`, numbered
+/// consecutively from the start of `text`. Renderers which keep their
+/// own array of words, e.g., words with styling, can use this to
+/// apply the line breaks without converting to [`Word`].
+///
+/// A word which is hyphenated or broken because it is too long ends
+/// up on several lines. The ranges of these lines will all include
+/// the word. Empty lines get an empty range which starts at the next
+/// word.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap_word_indices, Options, WrapAlgorithm};
+///
+/// let options = Options::new(15).wrap_algorithm(WrapAlgorithm::FirstFit);
+/// assert_eq!(wrap_word_indices("Memory safety without garbage collection.", &options),
+///            vec![0..2, 2..4, 4..5]);
+///
+/// assert_eq!(wrap_word_indices("Foo bar\n\nbaz", &options),
+///            vec![0..2, 2..2, 2..3]);
+///
+/// // The long word is broken and is found on both lines.
+/// assert_eq!(wrap_word_indices("foo barbazquux", 6),
+///            vec![0..1, 1..2, 1..2]);
+/// ```
+pub fn wrap_word_indices<'a, Opt>(text: &str, width_or_options: Opt) -> Vec<Range<usize>>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options: Options = width_or_options.into();
+    let mut sink = WordIndices {
+        ranges: Vec::new(),
+        word_count: 0,
+        line: 0..0,
+        word_starts: Vec::new(),
+        line_words: 0..0,
+    };
+    // Wrapping text cannot overflow since the line widths are
+    // integers, and it cannot fail with `MinWidth::Error` since that
+    // is replaced, see `try_wrap`.
+    wrap_lines(text, &options.proceed_on_min_width_error(), &mut sink).unwrap();
+    sink.ranges
+}
+
+/// Maps the wrapped lines back to the words of the input lines, see
+/// [`wrap_word_indices()`].
+struct WordIndices {
+    ranges: Vec<Range<usize>>,
+    /// Number of words in the previous input lines.
+    word_count: usize,
+    /// Address range of the current input line.
+    line: Range<usize>,
+    /// Byte offsets of the words in the current input line, used to
+    /// map the fragments back to the words.
+    word_starts: Vec<usize>,
+    /// Indices of the first and last non-empty words in the current
+    /// input line, used for lines which were not wrapped.
+    line_words: Range<usize>,
+}
+
+impl WordIndices {
+    fn offset_of(&self, word: &str) -> Option<usize> {
+        let start = word.as_ptr() as usize;
+        (self.line.start <= start && start + word.len() <= self.line.end)
+            .then(|| start - self.line.start)
+    }
+
+    fn word_idx(&self, offset: usize) -> usize {
+        self.word_starts.partition_point(|&start| start <= offset)
+    }
+
+    fn push_range(&mut self, range: Option<Range<usize>>) {
+        let next = self.ranges.last().map_or(self.word_count, |range| {
+            std::cmp::max(range.end, self.word_count)
+        });
+        self.ranges.push(range.unwrap_or(next..next));
+    }
+}
+
+impl<'a> LineSink<'a> for WordIndices {
+    fn line_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    fn start_input_line(&mut self, line: &str, options: &Options<'_>) {
+        let start = line.as_ptr() as usize;
+        self.line = start..start + line.len();
+        let words = options.word_separator.find_words(line).collect::<Vec<_>>();
+        self.word_starts = words
+            .iter()
+            .filter_map(|word| self.offset_of(word.word))
+            .collect();
+        let first = words.iter().position(|word| !word.is_empty());
+        let last = words.iter().rposition(|word| !word.is_empty());
+        self.line_words = match (first, last) {
+            (Some(first), Some(last)) => first..last + 1,
+            _ => 0..0,
+        };
+    }
+
+    fn end_input_line(&mut self) {
+        self.word_count += self.word_starts.len();
+    }
+
+    fn push_line(&mut self, line: Cow<'a, str>) {
+        // Lines which are not wrapped have all the words of the input
+        // line, except for whitespace.
+        let range = match line.is_empty() || self.line_words.is_empty() {
+            true => None,
+            false => {
+                Some(self.word_count + self.line_words.start..self.word_count + self.line_words.end)
+            }
+        };
+        self.push_range(range);
+    }
+
+    fn push_parts(&mut self, parts: LineParts<'a, '_>) {
+        let mut offsets = parts
+            .words
+            .iter()
+            .filter(|word| !word.is_empty())
+            .filter_map(|word| self.offset_of(word.word).map(|start| (start, word.len())));
+        let range = match (offsets.next(), offsets.next_back()) {
+            (Some((first, len)), last) => {
+                let (start, len) = last.unwrap_or((first, len));
+                let end = self.word_count + self.word_idx(start + len - 1);
+                Some(self.word_count + self.word_idx(first).saturating_sub(1)..end)
+            }
+            (None, _) => None,
+        };
+        self.push_range(range);
+    }
+}
+
+/// Receives the lines produced when wrapping text.
+///
+/// This lets [`try_wrap()`], [`wrap_into()`], and
+/// [`fill_into()`](crate::fill_into()) share the wrapping code
+/// without collecting the lines into an intermediate vector.
+pub(crate) trait LineSink<'a> {
+    /// The number of lines added so far. This decides the
+    /// indentation of the next line.
+    fn line_count(&self) -> usize;
+
+    /// Called before the lines for the input `line` are added.
+    fn start_input_line(&mut self, _line: &str, _options: &Options<'_>) {}
+
+    /// Called after the lines for an input line have been added.
+    fn end_input_line(&mut self) {}
+
+    /// Add a line which was not wrapped, or an empty line.
+    fn push_line(&mut self, line: Cow<'a, str>);
+
+    /// Add a wrapped line made of the given parts.
+    ///
+    /// Sinks which copy the line anyway can override this to avoid
+    /// building the line first.
+    fn push_parts(&mut self, parts: LineParts<'a, '_>) {
+        self.push_line(parts.into_line());
+    }
+}
+
+/// The parts of a wrapped line, see [`LineSink::push_parts()`].
+pub(crate) struct LineParts<'a, 'p> {
+    /// The indentation of the line.
+    pub(crate) indent: &'p str,
+    /// The text of the line, usually a slice of the input line.
+    pub(crate) text: Cow<'a, str>,
+    /// The penalty of the last word.
+    pub(crate) penalty: &'p str,
+    /// The wide character padding, see [`Options::wide_char_padding`].
+    pub(crate) padding: &'p str,
+    /// The words which make up `text`.
+    pub(crate) words: &'p [Word<'p>],
+}
+
+impl<'a> LineParts<'a, '_> {
+    /// Join the parts. The line borrows from the input if the other
+    /// parts are empty.
+    pub(crate) fn into_line(self) -> Cow<'a, str> {
+        if self.indent.is_empty() && self.penalty.is_empty() && self.padding.is_empty() {
+            return self.text;
+        }
+        let mut line = String::with_capacity(
+            self.indent.len() + self.text.len() + self.penalty.len() + self.padding.len(),
+        );
+        line.push_str(self.indent);
+        line.push_str(&self.text);
+        line.push_str(self.penalty);
+        line.push_str(self.padding);
+        Cow::Owned(line)
+    }
+}
+
+impl<'a> LineSink<'a> for Vec<Cow<'a, str>> {
+    fn line_count(&self) -> usize {
+        self.len()
+    }
+
+    fn push_line(&mut self, line: Cow<'a, str>) {
+        self.push(line);
+    }
+}
+
+/// Appends lines to a vector which might already contain lines.
+struct AppendedLines<'v, 'a> {
+    lines: &'v mut Vec<Cow<'a, str>>,
+    start: usize,
+}
+
+impl<'a> LineSink<'a> for AppendedLines<'_, 'a> {
+    fn line_count(&self) -> usize {
+        self.lines.len() - self.start
+    }
+
+    fn push_line(&mut self, line: Cow<'a, str>) {
+        self.lines.push(line);
+    }
+}
+
+/// Converts the lines to owned strings. This is used for lines
+/// which borrow from a temporary string, such as sanitized text.
+struct OwnedLines<'s, S> {
+    sink: &'s mut S,
+}
+
+impl<'a, 'b, S: LineSink<'b>> LineSink<'a> for OwnedLines<'_, S> {
+    fn line_count(&self) -> usize {
+        self.sink.line_count()
+    }
+
+    fn start_input_line(&mut self, line: &str, options: &Options<'_>) {
+        self.sink.start_input_line(line, options);
+    }
+
+    fn end_input_line(&mut self) {
+        self.sink.end_input_line();
     }
 
-    lines
+    fn push_line(&mut self, line: Cow<'a, str>) {
+        self.sink.push_line(Cow::Owned(line.into_owned()));
+    }
+
+    fn push_parts(&mut self, parts: LineParts<'a, '_>) {
+        self.sink.push_parts(LineParts {
+            indent: parts.indent,
+            text: Cow::Owned(parts.text.into_owned()),
+            penalty: parts.penalty,
+            padding: parts.padding,
+            words: parts.words,
+        });
+    }
+}
+
+/// Wrap `text` and add the lines to `sink`.
+pub(crate) fn wrap_lines<'a, S: LineSink<'a>>(
+    text: &'a str,
+    options: &Options<'_>,
+    sink: &mut S,
+) -> Result<(), Error> {
+    let built;
+    let options = match options.min_width {
+        MinWidth::Proceed if options.width <= options.max_width => options,
+        _ => {
+            built = options.clone().build()?;
+            &built
+        }
+    };
+    let mut lines = split_lines(text).peekable();
+    while let Some((line_offset, line)) = lines.next() {
+        let is_last_line = lines.peek().is_none();
+        match sanitize_text(line, options) {
+            Cow::Borrowed(line) => {
+                wrap_input_line(line, line_offset, is_last_line, options, sink)?;
+            }
+            Cow::Owned(sanitized) => {
+                // The protected ranges must move with the text.
+                let ranges;
+                let moved;
+                let options = match options.protected_ranges.is_empty() {
+                    true => options,
+                    false => {
+                        ranges = sanitized_ranges(line, line_offset, options);
+                        moved = options.clone().protected_ranges(&ranges);
+                        &moved
+                    }
+                };
+                // Only the lines which were changed are copied. The
+                // Unicode separators can split the line further.
+                let mut sink = OwnedLines { sink };
+                let mut sanitized_lines = split_lines(&sanitized).peekable();
+                while let Some((offset, line)) = sanitized_lines.next() {
+                    let is_last_line = is_last_line && sanitized_lines.peek().is_none();
+                    let line_offset = line_offset + offset;
+                    wrap_input_line(line, line_offset, is_last_line, options, &mut sink)?;
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
-pub(crate) fn wrap_single_line<'a>(
+fn wrap_input_line<'a, S: LineSink<'a>>(
     line: &'a str,
+    line_offset: usize,
+    is_last_line: bool,
     options: &Options<'_>,
-    lines: &mut Vec<Cow<'a, str>>,
-) {
-    let indent = if lines.is_empty() {
-        options.initial_indent
+    sink: &mut S,
+) -> Result<(), Error> {
+    sink.start_input_line(line, options);
+    wrap_single_line(line, line_offset, is_last_line, options, sink)?;
+    sink.end_input_line();
+    Ok(())
+}
+
+/// Sanitize control characters, replace Unicode separators, and
+/// normalize `text` as configured in `options`.
+fn sanitize_text<'t>(text: &'t str, options: &Options<'_>) -> Cow<'t, str> {
+    let text = options.sanitize_control_chars.sanitize(text);
+    let text = if options.unicode_separators {
+        then_cow(text, replace_unicode_separators)
     } else {
-        options.subsequent_indent
+        text
     };
-    if line.len() < options.width && indent.is_empty() {
-        lines.push(Cow::from(line.trim_end_matches(' ')));
-    } else {
-        wrap_single_line_slow_path(line, options, lines)
+    then_cow(text, |text| options.normalization.normalize(text))
+}
+
+/// Move the [`Options::protected_ranges`] which overlap `line` to
+/// where they are after [`sanitize_text()`]. The line starts at
+/// `line_offset` in the text, both before and after sanitizing.
+fn sanitized_ranges(line: &str, line_offset: usize, options: &Options<'_>) -> Vec<Range<usize>> {
+    let line_end = line_offset + line.len();
+    let sanitized_offset = |offset: usize| {
+        let mut idx = offset.clamp(line_offset, line_end) - line_offset;
+        while !line.is_char_boundary(idx) {
+            idx -= 1;
+        }
+        line_offset + sanitize_text(&line[..idx], options).len()
+    };
+    options
+        .protected_ranges
+        .iter()
+        .filter(|range| range.start < line_end && range.end > line_offset)
+        .map(|range| sanitized_offset(range.start)..sanitized_offset(range.end))
+        .collect()
+}
+
+/// Apply `f` to `text`, keeping the result borrowed if possible.
+fn then_cow<'t, F>(text: Cow<'t, str>, f: F) -> Cow<'t, str>
+where
+    F: for<'s> Fn(&'s str) -> Cow<'s, str>,
+{
+    match text {
+        Cow::Borrowed(text) => f(text),
+        Cow::Owned(text) => match f(&text) {
+            Cow::Owned(changed) => Cow::Owned(changed),
+            Cow::Borrowed(_) => Cow::Owned(text),
+        },
     }
 }
 
-/// Wrap a single line of text.
+/// Split `text` into lines. The lines are returned together with
+/// their byte offset in `text`.
 ///
-/// This is taken when `line` is longer than `options.width`.
-pub(crate) fn wrap_single_line_slow_path<'a>(
+/// We split on both kinds of line endings, so that text with mixed
+/// line endings does not produce lines with stray '\r' characters.
+pub(crate) fn split_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+    lines_with_endings(text).map(move |(line, line_ending)| {
+        let line_offset = offset;
+        offset += line.len() + line_ending.map_or(0, |ending| ending.as_str().len());
+        (line_offset, line)
+    })
+}
+
+pub(crate) fn wrap_single_line<'a, S: LineSink<'a>>(
     line: &'a str,
+    line_offset: usize,
+    is_last_line: bool,
+    options: &Options<'_>,
+    lines: &mut S,
+) -> Result<(), Error> {
+    let indent = options.indent_for(lines.line_count());
+    if line.len() < fast_path_width(is_last_line, options)
+        && indent.is_empty()
+        && options.width_overrides.is_empty()
+        && !expands_tabs(line, options)
+    {
+        if options.drop_whitespace {
+            lines.push_line(Cow::from(line.trim_end_matches(' ')));
+        } else {
+            lines.push_line(Cow::from(line));
+        }
+        Ok(())
+    } else {
+        wrap_single_line_slow_path(line, line_offset, is_last_line, options, lines)
+    }
+}
+
+/// Lines shorter than this (in bytes) fit without wrapping.
+pub(crate) fn fast_path_width(is_last_line: bool, options: &Options<'_>) -> usize {
+    if let WordSeparator::FixedWidthPadded(_) = options.word_separator {
+        // Even short lines end with a padded chunk.
+        return 0;
+    }
+    match is_last_line {
+        true => options.width.saturating_sub(options.last_line_reserve),
+        false => options.width,
+    }
+}
+
+/// Check if `line` has tabs between words which are wider than the
+/// single byte of the `'\t'`, see [`Options::tab_width`]. Such lines
+/// can be too wide even if they are short.
+pub(crate) fn expands_tabs(line: &str, options: &Options<'_>) -> bool {
+    matches!(
+        options.word_separator,
+        WordSeparator::AsciiWhitespace | WordSeparator::Custom(_)
+    ) && line.contains('\t')
+}
+
+/// Wrap `words` with the wrap algorithm from `options`.
+///
+/// A short last line is merged into the previous line as configured
+/// by `options.compact`.
+///
+/// When `is_last_line` is set, the words are the last line of input
+/// and the last line of output must leave room for
+/// `options.last_line_reserve` columns. Trailing words are moved from
+/// the last line to a new line until this is the case. The new line
+/// uses the width of the line after the last line. The last line is
+/// left alone if not even its last word leaves room on the new line.
+pub(crate) fn wrap_words<'a, 'b>(
+    words: &'b [Word<'a>],
+    line_widths: &'b [usize],
+    is_last_line: bool,
     options: &Options<'_>,
-    lines: &mut Vec<Cow<'a, str>>,
+) -> Result<Vec<&'b [Word<'a>]>, Error> {
+    let mut wrapped_words = match options.wrap_algorithm {
+        WrapAlgorithm::Custom(_) => options.wrap_algorithm.try_wrap(words, line_widths)?,
+        _ if options.drop_whitespace && !options.preserve_inner_whitespace => {
+            options.wrap_algorithm.try_wrap(words, line_widths)?
+        }
+        _ => wrap_keeping_whitespace(words, line_widths, options)?,
+    };
+    compact_last_line(words, &mut wrapped_words, line_widths, options.compact);
+    if !is_last_line || options.last_line_reserve == 0 {
+        return Ok(wrapped_words);
+    }
+
+    let available = |line_idx: usize| {
+        let line_width = line_widths
+            .get(line_idx)
+            .or_else(|| line_widths.last())
+            .copied()
+            .unwrap_or(0);
+        line_width.saturating_sub(options.last_line_reserve) as f64
+    };
+    let last_idx = wrapped_words.len().saturating_sub(1);
+    let last_line = match wrapped_words.last() {
+        Some(&last_line) if total_width(last_line) > available(last_idx) => last_line,
+        _ => return Ok(wrapped_words),
+    };
+
+    // The trailing words are moved to the next line. They get wider
+    // as more words are included, so we can search for the first
+    // word which fits.
+    let available = available(last_idx + 1);
+    let (mut split, mut end) = (0, last_line.len());
+    while split < end {
+        let mid = (split + end) / 2;
+        if total_width(&last_line[mid..]) <= available {
+            end = mid;
+        } else {
+            split = mid + 1;
+        }
+    }
+    if split == 0 || split == last_line.len() {
+        // Either the whole line fits on the next line, which leaves
+        // the same line with a different indentation, or no words
+        // fit at all.
+        return Ok(wrapped_words);
+    }
+    let (head, tail) = last_line.split_at(split);
+    wrapped_words.pop();
+    wrapped_words.push(head);
+    wrapped_words.push(tail);
+    Ok(wrapped_words)
+}
+
+/// A word together with the whitespace kept after it when it ends a
+/// line, see [`Options::drop_whitespace`] and
+/// [`Options::preserve_inner_whitespace`].
+#[derive(Debug)]
+struct KeptWhitespace<'b, 'a> {
+    word: &'b Word<'a>,
+    kept_width: usize,
+}
+
+impl Fragment for KeptWhitespace<'_, '_> {
+    fn width(&self) -> f64 {
+        self.word.width()
+    }
+
+    fn whitespace_width(&self) -> f64 {
+        self.word.whitespace_width()
+    }
+
+    fn penalty_width(&self) -> f64 {
+        self.word.penalty_width() + self.kept_width as f64
+    }
+
+    fn break_kind(&self) -> crate::core::FragmentBreak {
+        self.word.break_kind()
+    }
+
+    fn ends_sentence(&self) -> bool {
+        self.word.ends_sentence()
+    }
+}
+
+/// Wrap `words` so that the whitespace kept at the end of the lines
+/// fits within the line widths too.
+fn wrap_keeping_whitespace<'a, 'b>(
+    words: &'b [Word<'a>],
+    line_widths: &[usize],
+    options: &Options<'_>,
+) -> Result<Vec<&'b [Word<'a>]>, Error> {
+    let fragments = words
+        .iter()
+        .enumerate()
+        .map(|(idx, word)| {
+            let is_last_word = idx + 1 == words.len();
+            let dropped = dropped_whitespace(word.whitespace, is_last_word, options);
+            KeptWhitespace {
+                word,
+                kept_width: word.whitespace_columns() - dropped_whitespace_width(word, dropped),
+            }
+        })
+        .collect::<Vec<_>>();
+    let line_widths = line_widths.iter().map(|&w| w as f64).collect::<Vec<_>>();
+    let mut start = 0;
+    Ok(options
+        .wrap_algorithm
+        .try_wrap_fragments(&fragments, &line_widths)?
+        .into_iter()
+        .map(|line| {
+            let words = &words[start..start + line.len()];
+            start += line.len();
+            words
+        })
+        .collect())
+}
+
+/// Merge the last line of `wrapped_words` into the previous line if
+/// it is narrower than [`Compact::short_width`] and if the merged
+/// line overflows the line width by at most [`Compact::overflow`]
+/// columns.
+fn compact_last_line<'a, 'b>(
+    words: &'b [Word<'a>],
+    wrapped_words: &mut Vec<&'b [Word<'a>]>,
+    line_widths: &[usize],
+    compact: Compact,
 ) {
-    let initial_width = options
-        .width
-        .saturating_sub(display_width(options.initial_indent));
-    let subsequent_width = options
-        .width
-        .saturating_sub(display_width(options.subsequent_indent));
-    let line_widths = [initial_width, subsequent_width];
-
-    let words = options.word_separator.find_words(line);
-    let split_words = split_words(words, &options.word_splitter);
-    let broken_words = if options.break_words {
-        let mut broken_words = break_words(split_words, line_widths[1]);
-        if !options.initial_indent.is_empty() {
+    if wrapped_words.len() < 2 {
+        return;
+    }
+    let last_idx = wrapped_words.len() - 1;
+    let last_line = wrapped_words[last_idx];
+    if last_line.is_empty() || total_width(last_line) >= compact.short_width as f64 {
+        return;
+    }
+    let start = wrapped_words[..last_idx - 1]
+        .iter()
+        .map(|line| line.len())
+        .sum::<usize>();
+    let end = start + wrapped_words[last_idx - 1].len() + last_line.len();
+    // A custom wrap algorithm might not return consecutive lines.
+    let merged = match words.get(start..end) {
+        Some(merged) if std::ptr::eq(merged.as_ptr(), wrapped_words[last_idx - 1].as_ptr()) => {
+            merged
+        }
+        _ => return,
+    };
+    let available = line_widths
+        .get(last_idx - 1)
+        .or_else(|| line_widths.last())
+        .copied()
+        .unwrap_or(0);
+    if !merged.is_empty()
+        && total_width(merged) <= available.saturating_add(compact.overflow) as f64
+    {
+        wrapped_words.truncate(last_idx - 1);
+        wrapped_words.push(merged);
+    }
+}
+
+/// Find, split, and break the words in `line`, ready for the wrap
+/// algorithm. Long words are broken or truncated to `line_width`.
+/// The `line_offset` is the offset of `line` in the wrapped text, it
+/// is used for [`Options::protected_ranges`].
+///
+/// The flags tell which words were truncated. They are empty if no
+/// words were truncated.
+pub(crate) fn prepare_words<'a>(
+    line: &'a str,
+    line_offset: usize,
+    options: &'a Options<'_>,
+    line_width: usize,
+) -> (Vec<Word<'a>>, Vec<bool>) {
+    let split_words = split_filtered_words(line, line_offset, options).map(|mut word| {
+        if options.normalization != Normalization::Keep || !options.width_overrides.is_empty() {
+            let measured = options.normalization.measured(word.word);
+            word.width = display_width_with(&measured, options.width_overrides);
+        }
+        // Only `WordSeparator::AsciiWhitespace` and custom separators
+        // produce tabs between words.
+        word.tab_width = options.tab_width;
+        word
+    });
+    let mut truncated = Vec::new();
+    // The `break_words` field decides unless words are truncated,
+    // see `Options::word_overflow`.
+    let truncate = matches!(options.word_overflow, WordOverflow::Truncate(_));
+    let broken_words = if options.break_words || truncate {
+        let mut broken_words = match options.word_overflow {
+            WordOverflow::Truncate(ellipsis) => {
+                let (words, flags) = truncate_words(split_words, line_width, ellipsis, options);
+                if flags.contains(&true) {
+                    truncated = flags;
+                }
+                words
+            }
+            _ if options.protected_ranges.is_empty() && options.break_words_if.is_none() => {
+                break_words_with(split_words, line_width, options.width_overrides)
+            }
+            _ => break_unprotected_words(split_words, line_offset, options, line_width),
+        };
+        if !options.indent_for(0).is_empty() {
             // Without this, the first word will always go into the
             // first line. However, since we break words based on the
             // _second_ line width, it can be wrong to unconditionally
             // put the first word onto the first line. An empty
             // zero-width word fixed this.
             broken_words.insert(0, Word::from(""));
+            if !truncated.is_empty() {
+                truncated.insert(0, false);
+            }
         }
         broken_words
     } else {
         split_words.collect::<Vec<_>>()
     };
 
-    let wrapped_words = options.wrap_algorithm.wrap(&broken_words, &line_widths);
+    (broken_words, truncated)
+}
 
+/// The number of bytes of `whitespace` to drop when it ends a line.
+///
+/// The whitespace at the end of the last line is always dropped
+/// completely since it is not between two words.
+pub(crate) fn dropped_whitespace(
+    whitespace: &str,
+    is_last_line: bool,
+    options: &Options<'_>,
+) -> usize {
+    if !options.drop_whitespace {
+        0
+    } else if options.preserve_inner_whitespace && !is_last_line {
+        whitespace.chars().next_back().map_or(0, char::len_utf8)
+    } else {
+        whitespace.len()
+    }
+}
+
+/// The width of the last `dropped` bytes of the whitespace of
+/// `word`, see [`dropped_whitespace()`].
+pub(crate) fn dropped_whitespace_width(word: &Word<'_>, dropped: usize) -> usize {
+    let whitespace = word.whitespace;
+    match dropped == whitespace.len() {
+        true => word.whitespace_columns(),
+        false => display_width(&whitespace[whitespace.len() - dropped..]),
+    }
+}
+
+/// Wrap a single line of text.
+///
+/// This is taken when `line` is longer than `options.width`.
+pub(crate) fn wrap_single_line_slow_path<'a, S: LineSink<'a>>(
+    line: &'a str,
+    line_offset: usize,
+    is_last_line: bool,
+    options: &Options<'_>,
+    lines: &mut S,
+) -> Result<(), Error> {
+    // Only the very first line uses the initial indentation, later
+    // paragraphs continue with the indentation of the next line.
+    let line_widths = line_widths_from(options, lines.line_count());
+    let break_width = line_widths[line_widths.len() - 1];
+    let (broken_words, truncated) = prepare_words(line, line_offset, options, break_width);
+    let wrapped_words = wrap_words(&broken_words, &line_widths, is_last_line, options)?;
+
+    let wrapped_lines = wrapped_words.len();
     let mut idx = 0;
-    for words in wrapped_words {
+    let mut word_idx = 0;
+    for (line_idx, words) in wrapped_words.into_iter().enumerate() {
+        let first_word_idx = word_idx;
+        word_idx += words.len();
         let last_word = match words.last() {
             None => {
-                lines.push(Cow::from(""));
+                lines.push_line(Cow::from(""));
                 continue;
             }
             Some(word) => word,
@@ -257,48 +1008,353 @@ pub(crate) fn wrap_single_line_slow_path<'a>(
         // We assume here that all words are contiguous in `line`.
         // That is, the sum of their lengths should add up to the
         // length of `line`.
+        let dropped_whitespace =
+            dropped_whitespace(last_word.whitespace, line_idx + 1 == wrapped_lines, options);
         let len = words
             .iter()
             .map(|word| word.len() + word.whitespace.len())
             .sum::<usize>()
-            - last_word.whitespace.len();
-
-        // The result is owned if we have indentation, otherwise we
-        // can simply borrow an empty string.
-        let mut result = if lines.is_empty() && !options.initial_indent.is_empty() {
-            Cow::Owned(options.initial_indent.to_owned())
-        } else if !lines.is_empty() && !options.subsequent_indent.is_empty() {
-            Cow::Owned(options.subsequent_indent.to_owned())
+            - dropped_whitespace;
+
+        let indent = options.indent_for(lines.line_count());
+        let line_width = line_widths[std::cmp::min(line_idx, line_widths.len() - 1)];
+        let next_word = broken_words.get(word_idx);
+        let chunk_padding;
+        let padding = match options.word_separator {
+            WordSeparator::FixedWidthPadded(width) if line_idx + 1 == wrapped_lines => {
+                chunk_padding = last_chunk_padding(last_word, dropped_whitespace, width);
+                &chunk_padding
+            }
+            _ => wide_char_padding(words, next_word, line_width, dropped_whitespace, options),
+        };
+
+        let text = if truncated.is_empty() {
+            Cow::Borrowed(&line[idx..idx + len])
         } else {
-            // We can use an empty string here since string
-            // concatenation for `Cow` preserves a borrowed value when
-            // either side is empty.
-            Cow::from("")
+            // Truncated words are no longer contiguous in `line`, so
+            // we build the line word by word.
+            let mut result = String::new();
+            for (i, word) in words.iter().enumerate() {
+                result.push_str(word);
+                if truncated[first_word_idx + i] {
+                    if let WordOverflow::Truncate(ellipsis) = options.word_overflow {
+                        result.push_str(ellipsis);
+                    }
+                }
+                if i + 1 < words.len() {
+                    result.push_str(word.whitespace);
+                } else {
+                    let kept = word.whitespace.len() - dropped_whitespace;
+                    result.push_str(&word.whitespace[..kept]);
+                }
+            }
+            Cow::Owned(result)
         };
+        lines.push_parts(LineParts {
+            indent,
+            text,
+            penalty: last_word.penalty,
+            padding,
+            words,
+        });
+
+        // Advance by the length of `result`, plus the length of the
+        // dropped whitespace -- even if we had a penalty, we need to
+        // skip over the whitespace.
+        idx += len + dropped_whitespace;
+    }
 
-        result += &line[idx..idx + len];
+    Ok(())
+}
+
+/// The padding which makes `last_word` as wide as the other chunks
+/// found by [`WordSeparator::FixedWidthPadded`] with `width`.
+fn last_chunk_padding(last_word: &Word<'_>, dropped_whitespace: usize, width: usize) -> String {
+    let kept_whitespace =
+        last_word.whitespace_columns() - dropped_whitespace_width(last_word, dropped_whitespace);
+    let chunk_width = last_word.width + kept_whitespace;
+    " ".repeat(std::cmp::max(width, 1).saturating_sub(chunk_width))
+}
 
-        if !last_word.penalty.is_empty() {
-            result.to_mut().push_str(last_word.penalty);
+/// The padding to add to a line with `words` when the `next_word`
+/// starts with a double-width character which does not fit on the
+/// line. See [`Options::wide_char_padding`].
+pub(crate) fn wide_char_padding<'o>(
+    words: &[Word<'_>],
+    next_word: Option<&Word<'_>>,
+    line_width: usize,
+    dropped_whitespace: usize,
+    options: &'o Options<'_>,
+) -> &'o str {
+    let starts_wide = match next_word.and_then(|word| word.chars().next()) {
+        Some(ch) => ch_width_with(ch, options.width_overrides) == 2,
+        None => false,
+    };
+    if options.wide_char_padding.is_empty() || !starts_wide {
+        return "";
+    }
+    let trailing_whitespace = match words.last() {
+        Some(word) => {
+            word.whitespace_width() - dropped_whitespace_width(word, dropped_whitespace) as f64
         }
+        None => 0.0,
+    };
+    let width = total_width(words) + trailing_whitespace;
+    match width as usize + 1 == line_width {
+        true => &options.wide_char_padding,
+        false => "",
+    }
+}
 
-        lines.push(result);
+/// Break words wider than `line_width` like [`break_words`], except
+/// for words with a protected range inside them, see
+/// [`Options::protected_ranges`], and words rejected by
+/// [`Options::break_words_if`]. The words are assumed to be
+/// contiguous in the line starting at `line_offset`.
+fn break_unprotected_words<'a, I>(
+    words: I,
+    line_offset: usize,
+    options: &Options<'_>,
+    line_width: usize,
+) -> Vec<Word<'a>>
+where
+    I: IntoIterator<Item = Word<'a>>,
+{
+    let mut offset = line_offset;
+    let mut broken_words = Vec::new();
+    for word in words {
+        let end = offset + word.len();
+        let too_wide = word.width() > line_width as f64;
+        let kept_whole = too_wide
+            && !options
+                .break_words_if
+                .map_or(true, |may_break| may_break(&word));
+        if kept_whole || is_protected_span(offset..end, options) {
+            broken_words.push(word);
+        } else {
+            broken_words.extend(break_words_with(
+                [word],
+                line_width,
+                options.width_overrides,
+            ));
+        }
+        offset = end + word.whitespace.len();
+    }
+    broken_words
+}
 
-        // Advance by the length of `result`, plus the length of
-        // `last_word.whitespace` -- even if we had a penalty, we need
-        // to skip over the whitespace.
-        idx += len + last_word.whitespace.len();
+/// Truncate words wider than `line_width` so that they fit on a
+/// line together with `ellipsis`.
+///
+/// The truncated words get a width of `line_width`, which ensures
+/// that they end up on lines of their own. Returns the words together
+/// with a flag for each word which tells if the word was truncated.
+fn truncate_words<'a, I>(
+    words: I,
+    line_width: usize,
+    ellipsis: &str,
+    options: &Options<'_>,
+) -> (Vec<Word<'a>>, Vec<bool>)
+where
+    I: IntoIterator<Item = Word<'a>>,
+{
+    let overrides = options.width_overrides;
+    let available_width = line_width.saturating_sub(display_width_with(ellipsis, overrides));
+    let mut truncated_words = Vec::new();
+    let mut truncated = Vec::new();
+    for word in words {
+        if word.width > line_width {
+            let prefix = match word.break_apart_with(available_width, overrides).next() {
+                // A wide first character can be wider than the
+                // available width, we drop it in that case.
+                Some(prefix) if available_width > 0 && prefix.width <= available_width => {
+                    prefix.word
+                }
+                _ => "",
+            };
+            truncated_words.push(Word {
+                word: prefix,
+                width: line_width,
+                whitespace: word.whitespace,
+                tab_width: word.tab_width,
+                penalty: "",
+                payload: (),
+            });
+            truncated.push(true);
+        } else {
+            truncated_words.push(word);
+            truncated.push(false);
+        }
     }
+    (truncated_words, truncated)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{WordSeparator, WordSplitter, WrapAlgorithm};
+    use crate::{ControlChars, LineEnding, WordSeparator, WordSplitter, WrapAlgorithm};
 
     #[cfg(feature = "hyphenation")]
     use hyphenation::{Language, Load, Standard};
 
+    #[test]
+    fn sanitize_control_chars_counts_escapes() {
+        let options = Options::new(6).sanitize_control_chars(ControlChars::Escape);
+        assert_eq!(wrap("foo\x0cbar baz", &options), vec!["foo^Lb", "ar baz"]);
+        let options = options.sanitize_control_chars(ControlChars::Strip);
+        assert_eq!(wrap("foo\x0cbar baz", &options), vec!["foobar", "baz"]);
+    }
+
+    #[test]
+    fn sanitize_control_chars_borrows_unchanged_lines() {
+        let options = Options::new(10).sanitize_control_chars(ControlChars::Escape);
+        let lines = wrap("foo\nbar\x07\nbaz\u{2028}quux", &options);
+        assert_eq!(lines, vec!["foo", "bar^G", "baz\u{2028}quux"]);
+        assert!(matches!(lines[0], Cow::Borrowed(_)));
+        assert!(matches!(lines[1], Cow::Owned(_)));
+
+        let options = options.unicode_separators(true);
+        let lines = wrap("foo\nbaz\u{2028}quux", &options);
+        assert_eq!(lines, vec!["foo", "baz", "quux"]);
+        assert!(matches!(lines[0], Cow::Borrowed(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn normalization_with_escaped_control_chars() {
+        let options = Options::new(20)
+            .sanitize_control_chars(ControlChars::Escape)
+            .normalization(Normalization::Nfc);
+        assert_eq!(wrap("Cafe\u{301}\x07", &options), vec!["Caf\u{e9}^G"]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "unicode-normalization", not(feature = "unicode-width")))]
+    fn normalization_measures_combining_chars() {
+        // Without unicode-width, the combining accent is one column.
+        let options = Options::new(7).word_separator(WordSeparator::AsciiSpace);
+        assert_eq!(wrap("Cafe\u{301} au", &options), vec!["Cafe\u{301}", "au"]);
+        let options = options.normalization(Normalization::MeasureNfc);
+        assert_eq!(wrap("Cafe\u{301} au", &options), vec!["Cafe\u{301} au"]);
+    }
+
+    #[test]
+    fn width_overrides_break_long_words() {
+        let options = Options::new(4).width_overrides(&[('x', 2), ('y', 3)]);
+        assert_eq!(wrap("xxxxx", &options), vec!["xx", "xx", "x"]);
+        assert_eq!(wrap("xyy", &options), vec!["x", "y", "y"]);
+        let options = options.overflow_marker("y");
+        assert_eq!(wrap("xxxxx", &options), vec!["y"]);
+    }
+
+    #[test]
+    fn width_overrides_in_indentation() {
+        let options = Options::new(6)
+            .initial_indent("»")
+            .width_overrides(&[('»', 2), ('-', 1)])
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .word_separator(WordSeparator::AsciiSpace);
+        assert_eq!(wrap("ab cd ef", &options), vec!["»ab", "cd ef"]);
+    }
+
+    #[test]
+    fn wrap_into_sanitized_text() {
+        let options = Options::new(10)
+            .initial_indent("> ")
+            .sanitize_control_chars(ControlChars::Strip);
+        let mut lines = vec![Cow::from("header")];
+        wrap_into("foo\x0cbar baz", &options, &mut lines);
+        assert_eq!(lines, vec!["header", "> foobar", "baz"]);
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn overflow_marker_with_wide_chars() {
+        let options = Options::new(6)
+            .overflow_marker("…")
+            .word_separator(WordSeparator::AsciiSpace);
+        assert_eq!(wrap("你好世界你好", &options), vec!["你好…"]);
+        assert_eq!(wrap("a你好世界你好", &options), vec!["a你好…"]);
+        let options = options.width(2).overflow_marker("~");
+        assert_eq!(wrap("你好世界", &options), vec!["~"]);
+    }
+
+    #[test]
+    fn protected_ranges_are_not_broken() {
+        let text = "see https://example.com/path now";
+        let url = 4..28;
+        let options = Options::new(10)
+            .word_separator(WordSeparator::AsciiSpace)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .protected_ranges(std::slice::from_ref(&url));
+        assert_eq!(
+            wrap(text, &options),
+            vec!["see", "https://example.com/path", "now"]
+        );
+
+        let see = 0..3;
+        let options = options.protected_ranges(std::slice::from_ref(&see));
+        assert_eq!(
+            wrap(text, &options),
+            vec!["see", "https://ex", "ample.com/", "path now"]
+        );
+    }
+
+    #[test]
+    fn protected_ranges_move_with_sanitized_text() {
+        let text = "a\x07b see https://example.com/path now";
+        let url = 8..32;
+        assert_eq!(&text[url.clone()], "https://example.com/path");
+        let options = Options::new(10)
+            .word_separator(WordSeparator::AsciiSpace)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .protected_ranges(std::slice::from_ref(&url));
+
+        let escape = options.clone().sanitize_control_chars(ControlChars::Escape);
+        assert_eq!(
+            wrap(text, &escape),
+            vec!["a^Gb see", "https://example.com/path", "now"]
+        );
+        let strip = options.sanitize_control_chars(ControlChars::Strip);
+        assert_eq!(
+            wrap(text, &strip),
+            vec!["ab see", "https://example.com/path", "now"]
+        );
+    }
+
+    #[test]
+    fn protected_ranges_move_with_unicode_separators() {
+        let text = "a\u{2028}see https://example.com/path now";
+        let url = 8..32;
+        assert_eq!(&text[url.clone()], "https://example.com/path");
+        let options = Options::new(10)
+            .word_separator(WordSeparator::AsciiSpace)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .unicode_separators(true)
+            .protected_ranges(std::slice::from_ref(&url));
+        assert_eq!(
+            wrap(text, &options),
+            vec!["a", "see", "https://example.com/path", "now"]
+        );
+    }
+
+    #[test]
+    fn mixed_line_endings() {
+        assert_eq!(wrap("foo\r\nbar\nbaz\r", 10), vec!["foo", "bar", "baz\r"]);
+        let options = Options::new(10).line_ending(LineEnding::CRLF);
+        assert_eq!(wrap("foo\r\nbar\nbaz", &options), vec!["foo", "bar", "baz"]);
+        let options = options.line_ending(LineEnding::LF);
+        assert_eq!(wrap("foo\r\nbar\nbaz", &options), vec!["foo", "bar", "baz"]);
+    }
+
+    #[test]
+    fn try_wrap_matches_wrap() {
+        let text = "Memory safety without garbage collection.";
+        for width in 0..45 {
+            assert_eq!(try_wrap(text, width), Ok(wrap(text, width)));
+        }
+    }
+
     #[test]
     fn no_wrap() {
         assert_eq!(wrap("foo", 10), vec!["foo"]);
@@ -441,6 +1497,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn indent_with_ansi_colors() {
+        let gutter = "\x1b[2m|\x1b[0m ";
+        let options = Options::new(9)
+            .initial_indent(gutter)
+            .subsequent_indent(gutter)
+            .wrap_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(
+            wrap("foo bar baz", &options),
+            vec![format!("{}foo bar", gutter), format!("{}baz", gutter)]
+        );
+        assert_eq!(wrap("foo bar baz", options.gutter(gutter, 2)).len(), 2);
+    }
+
+    #[test]
+    fn gutter_width_is_trusted() {
+        let options = Options::new(6)
+            .gutter("\t", 2)
+            .wrap_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(wrap("foo bar", &options), vec!["\tfoo", "\tbar"]);
+    }
+
+    #[test]
+    fn indents_clear_gutter_width() {
+        let options = Options::new(6)
+            .gutter("\t", 2)
+            .wrap_algorithm(WrapAlgorithm::FirstFit);
+        let measured = options.clone().initial_indent(">").subsequent_indent(">");
+        assert_eq!(wrap("ab cd ef", &measured), vec![">ab cd", ">ef"]);
+        let measured = options.indents(&[">", ">"]);
+        assert_eq!(wrap("ab cd ef", &measured), vec![">ab cd", ">ef"]);
+    }
+
+    #[test]
+    fn fixed_width_padded_last_chunk() {
+        let options = Options::new(9).word_separator(WordSeparator::FixedWidthPadded(4));
+        assert_eq!(
+            wrap("deadbeefcafe12\nab\nabc ", &options),
+            vec!["deadbeef", "cafe12  ", "ab  ", "abc "]
+        );
+        assert_eq!(crate::fill("cafe12", &options), "cafe12  ");
+    }
+
+    #[test]
+    fn gutter_replaces_indents() {
+        let options = Options::new(20).indents(&["a: ", "b: "]).gutter("> ", 2);
+        let text = "aaaaaaaaaaaaaaaaaa bbbbbbbbbbbbbbbbbb";
+        let lines = wrap(text, &options);
+        assert_eq!(lines, vec!["> aaaaaaaaaaaaaaaaaa", "> bbbbbbbbbbbbbbbbbb"]);
+        assert!(lines.iter().all(|line| line.len() <= 20));
+    }
+
+    #[test]
+    fn indents_continue_across_paragraphs() {
+        let options = Options::new(6)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .indents(&["1 ", "2 ", "3 "]);
+        assert_eq!(
+            wrap("foo bar\nbaz\nquux", &options),
+            vec!["1 foo", "2 bar", "3 baz", "3 quux"]
+        );
+        assert_eq!(crate::fill("foo bar\nbaz", &options), "1 foo\n2 bar\n3 baz");
+        let options = Options::new(6).indents(&["> "]);
+        assert_eq!(wrap("foo bar", &options), vec!["> foo", "> bar"]);
+    }
+
+    #[test]
+    fn indent_setters_clear_indents() {
+        let options = Options::new(10).indents(&["a", "b", "c"]);
+        assert_eq!(options.initial_indent, "a");
+        assert_eq!(options.subsequent_indent, "c");
+        let options = options.subsequent_indent("  ");
+        assert!(options.indents.is_empty());
+        assert_eq!(options.initial_indent, "a");
+    }
+
     #[test]
     fn only_initial_indent_multiple_lines() {
         let options = Options::new(10).initial_indent("  ");
@@ -456,12 +1588,164 @@ mod tests {
         );
     }
 
+    #[test]
+    fn last_line_reserve_only_last_paragraph() {
+        let options = Options::new(10)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .last_line_reserve(4);
+        assert_eq!(
+            wrap("foo bar\nfoo bar baz", &options),
+            vec!["foo bar", "foo bar", "baz"]
+        );
+        assert_eq!(wrap("foo bar\n", &options), vec!["foo bar", ""]);
+        assert_eq!(wrap("foobarbaz", &options), vec!["foobarbaz"]);
+        assert_eq!(crate::wrap_stats("foo bar\nfoo bar baz", &options).lines, 3);
+    }
+
+    #[test]
+    fn last_line_reserve_uses_next_line_width() {
+        let options = Options::new(10)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .subsequent_indent("    ")
+            .last_line_reserve(4);
+        // The moved "bar" would not leave room for the reserve
+        // either, so the line is kept.
+        assert_eq!(wrap("foo bar", &options), vec!["foo bar"]);
+        assert_eq!(wrap("foo bar ab", &options), vec!["foo bar", "    ab"]);
+        assert_eq!(
+            wrap("foo bar baz a", &options),
+            vec!["foo bar", "    baz", "    a"]
+        );
+    }
+
+    #[test]
+    fn compact_merges_short_last_line() {
+        let options = Options::new(8)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .compact(Compact::new(3, 1));
+        assert_eq!(wrap("foo bar baz", &options), vec!["foo bar", "baz"]);
+        assert_eq!(wrap("foo bar ab", &options), vec!["foo bar", "ab"]);
+        assert_eq!(
+            wrap("foo bar b\nfoo bar b", &options),
+            vec!["foo bar b", "foo bar b"]
+        );
+        assert_eq!(crate::wrap_stats("foo bar b", &options).lines, 1);
+    }
+
+    #[test]
+    fn compact_with_huge_overflow() {
+        let options = Options::new(8)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .compact(Compact::new(3, usize::MAX));
+        assert_eq!(wrap("foo bar ab", &options), vec!["foo bar ab"]);
+    }
+
+    #[test]
+    fn custom_hyphen() {
+        let options = Options::new(6)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .word_splitter(WordSplitter::Custom(|word| vec![word.len() / 2]))
+            .hyphen("~~");
+        assert_eq!(wrap("abcdefgh", &options), vec!["abcd~~", "efgh"]);
+        assert_eq!(wrap("ab~~cdef", &options), vec!["ab~~", "cdef"]);
+        // The penalty counts towards the line width.
+        let options = options.width(7);
+        assert_eq!(wrap("a abcdefgh", &options), vec!["a", "abcd~~", "efgh"]);
+        let options = options.hyphen("-");
+        assert_eq!(wrap("a abcdefgh", &options), vec!["a abcd-", "efgh"]);
+    }
+
+    #[test]
+    fn break_words_if_keeps_rejected_words() {
+        let options = Options::new(5)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .break_words_if(|word| !word.contains(['a', 'e', 'i', 'o', 'u']));
+        assert_eq!(
+            wrap("xyzzyq banana", &options),
+            vec!["xyzzy", "q", "banana"]
+        );
+        let range = 0..6;
+        let options = options.protected_ranges(std::slice::from_ref(&range));
+        assert_eq!(wrap("xyzzyq", &options), vec!["xyzzyq"]);
+        assert_eq!(options.cache_key(), None);
+    }
+
+    #[test]
+    fn wide_char_padding_only_for_wide_chars() {
+        let options = Options::new(5)
+            .word_separator(WordSeparator::AsciiSpace)
+            .wide_char_padding(" ");
+        assert_eq!(wrap("abcdefg", &options), vec!["abcde", "fg"]);
+        assert_eq!(wrap("ab漢字漢", &options), vec!["ab漢 ", "字漢"]);
+        assert_eq!(wrap("abcd 漢", &options), vec!["abcd ", "漢"]);
+    }
+
+    #[test]
+    fn subsequent_indent_later_paragraphs() {
+        let options = Options::new(4).subsequent_indent("  ");
+        assert_eq!(wrap("a\na,a", &options), vec!["a", "  a,", "  a"]);
+        assert_eq!(crate::wrap_stats("a\na,a", &options).max_line_width, 4);
+    }
+
     #[test]
     fn indent_break_words() {
         let options = Options::new(5).initial_indent("* ").subsequent_indent("  ");
         assert_eq!(wrap("foobarbaz", &options), vec!["* foo", "  bar", "  baz"]);
     }
 
+    #[test]
+    fn keep_whitespace() {
+        let options = Options::new(5)
+            .drop_whitespace(false)
+            .wrap_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(wrap("foo  bar  ", &options), vec!["foo  ", "bar  "]);
+        assert_eq!(wrap("  foo bar", &options), vec!["  ", "foo ", "bar"]);
+        assert_eq!(wrap("foo-bar baz", &options), vec!["foo-", "bar ", "baz"]);
+    }
+
+    #[test]
+    fn keep_whitespace_fits_in_width() {
+        let options = Options::new(6).drop_whitespace(false);
+        assert_eq!(wrap("let x  =  1;", &options), vec!["let ", "x  =  ", "1;"]);
+        let options = options.wrap_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(wrap("let x  =  1;", &options), vec!["let ", "x  =  ", "1;"]);
+    }
+
+    #[test]
+    fn preserved_inner_whitespace_fits_in_width() {
+        let options = Options::new(10).preserve_inner_whitespace(true);
+        assert_eq!(
+            wrap("One.  Two.  Three.  ", &options),
+            vec!["One. ", "Two. ", "Three."]
+        );
+        let options = options.wrap_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(
+            wrap("One.  Two.  Three.  ", &options),
+            vec!["One. ", "Two. ", "Three."]
+        );
+    }
+
+    #[test]
+    fn keep_whitespace_with_indent() {
+        let options = Options::new(8)
+            .initial_indent("* ")
+            .subsequent_indent("  ")
+            .drop_whitespace(false);
+        assert_eq!(
+            wrap("foo bar baz", &options),
+            vec!["* foo ", "  bar ", "  baz"]
+        );
+        assert_eq!(crate::fill("foo ", &options), "* foo ");
+    }
+
+    #[test]
+    fn keep_whitespace_truncated() {
+        let options = Options::new(6)
+            .drop_whitespace(false)
+            .word_overflow(WordOverflow::Truncate("~"));
+        assert_eq!(wrap("abcdefgh  ij", &options), vec!["abcde~  ", "ij"]);
+    }
+
     #[test]
     fn initial_indent_break_words() {
         // This is a corner-case showing how the long word is broken
@@ -574,6 +1858,31 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "hyphenation")]
+    fn shared_hyphenation_across_threads() {
+        let dictionary = Standard::from_embedded(Language::EnglishUS).unwrap();
+        let splitter = WordSplitter::SharedHyphenation(std::sync::Arc::new(dictionary));
+        let handles = (0..2)
+            .map(|_| {
+                let splitter = splitter.clone();
+                std::thread::spawn(move || {
+                    let options = Options::new(10).word_splitter(splitter);
+                    wrap("Internationalization", &options)
+                        .into_iter()
+                        .map(String::from)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            assert_eq!(
+                handle.join().unwrap(),
+                vec!["Interna-", "tionaliza-", "tion"]
+            );
+        }
+    }
+
     #[test]
     #[cfg(feature = "hyphenation")]
     fn auto_hyphenation_issue_158() {
@@ -642,6 +1951,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn truncate_long_words() {
+        let options = Options::new(5).word_overflow(WordOverflow::Truncate("~"));
+        assert_eq!(
+            wrap("a foobarbaz b foobar", &options),
+            vec!["a", "foob~", "b", "foob~"]
+        );
+    }
+
+    #[test]
+    fn truncate_short_words() {
+        let options = Options::new(10).word_overflow(WordOverflow::Truncate("~"));
+        assert_eq!(
+            wrap("foo bar baz quux", &options),
+            vec!["foo bar", "baz quux"]
+        );
+    }
+
+    #[test]
+    fn truncate_with_indent() {
+        let options = Options::new(6)
+            .initial_indent("* ")
+            .subsequent_indent("  ")
+            .word_overflow(WordOverflow::Truncate("..."));
+        assert_eq!(wrap("foobarbaz quux", &options), vec!["* f...", "  quux"]);
+    }
+
+    #[test]
+    fn truncate_wide_ellipsis() {
+        let options = Options::new(2).word_overflow(WordOverflow::Truncate("..."));
+        assert_eq!(wrap("foobar", &options), vec!["..."]);
+    }
+
+    #[test]
+    fn truncate_keeps_hyphenated_pieces() {
+        let options = Options::new(6).word_overflow(WordOverflow::Truncate("~"));
+        assert_eq!(wrap("foo-barbazquux", &options), vec!["foo-", "barba~"]);
+    }
+
     #[test]
     fn break_words() {
         assert_eq!(wrap("foobarbaz", 3), vec!["foo", "bar", "baz"]);
@@ -672,6 +2020,41 @@ mod tests {
         assert_eq!(wrap("  \n \n  \n ", 80), vec!["", "", "", ""]);
     }
 
+    #[test]
+    fn wrap_ascii_whitespace_counts_tabs() {
+        let options = Options::new(10)
+            .word_separator(WordSeparator::AsciiWhitespace)
+            .tab_width(4);
+        assert_eq!(wrap("foo\tbar\tbaz", &options), vec!["foo\tbar", "baz"]);
+        assert_eq!(
+            wrap("foo\tbar\tbaz", options.tab_width(5)),
+            vec!["foo", "bar", "baz"]
+        );
+    }
+
+    #[test]
+    fn wrap_ascii_whitespace_short_line_with_tab() {
+        let options = Options::new(6)
+            .word_separator(WordSeparator::AsciiWhitespace)
+            .tab_width(8);
+        assert_eq!(wrap("a\tb", &options), vec!["a", "b"]);
+        assert_eq!(crate::fill("a\tb", &options), "a\nb");
+        assert_eq!(crate::wrap_stats("a\tb", &options).lines, 2);
+    }
+
+    #[test]
+    fn wrap_drops_wide_spaces() {
+        let text = "東京\u{3000}大阪\u{3000}京都";
+        for separator in [WordSeparator::AsciiSpace, WordSeparator::new()] {
+            let options = Options::new(7).word_separator(separator).wide_spaces(true);
+            assert_eq!(wrap(text, &options), vec!["東京", "大阪", "京都"]);
+            assert_eq!(
+                wrap(text, options.preserve_inner_whitespace(true)),
+                vec!["東京", "大阪", "京都"]
+            );
+        }
+    }
+
     #[test]
     fn wrap_colored_text() {
         // The words are much longer than 6 bytes, but they remain
@@ -683,4 +2066,62 @@ mod tests {
             vec![green_hello, blue_world],
         );
     }
+
+    proptest::proptest! {
+        #[test]
+        fn wrap_respects_width(
+            text in crate::proptest_support::text(),
+            options in crate::proptest_support::options(),
+        ) {
+            let lines = wrap(&text, &options);
+            crate::proptest_support::check_line_widths(&lines, &options)?;
+        }
+
+        #[test]
+        fn wrap_keeps_content(
+            text in crate::proptest_support::text(),
+            options in crate::proptest_support::any_algorithm_options(),
+        ) {
+            let lines = wrap(&text, &options);
+            crate::proptest_support::check_content(&text, &lines, &options)?;
+        }
+
+        #[test]
+        fn wrap_word_indices_matches_wrap(
+            text in crate::proptest_support::text(),
+            options in crate::proptest_support::options(),
+        ) {
+            let significant = |word: &str| {
+                word.chars()
+                    .filter(|ch| !ch.is_whitespace() && *ch != '-')
+                    .collect::<String>()
+            };
+            let words = text
+                .split('\n')
+                .flat_map(|line| options.word_separator.find_words(line))
+                .map(|word| significant(word.word))
+                .collect::<Vec<_>>();
+            let lines = wrap(&text, &options);
+            let ranges = wrap_word_indices(&text, &options);
+            proptest::prop_assert_eq!(ranges.len(), lines.len());
+
+            let contents = crate::proptest_support::strip_indents(&lines, &options);
+            let mut prev_end = 0;
+            for (range, content) in ranges.iter().zip(contents) {
+                // The line is a slice of the words in its range, the
+                // first and last word can be broken.
+                let content = significant(content);
+                let range_words = words[range.clone()].concat();
+                proptest::prop_assert!(
+                    range_words.contains(&content),
+                    "{:?} is not part of {:?}",
+                    content,
+                    range_words
+                );
+                proptest::prop_assert!(!range.is_empty() || content.is_empty());
+                proptest::prop_assert!(range.start + 1 >= prev_end);
+                prev_end = range.end;
+            }
+        }
+    }
 }
@@ -15,7 +15,9 @@
 //! left behind by overly short lines.
 //!
 //! While both algorithms run in linear time, the first-fit algorithm
-//! is about 4 times faster than the optimal-fit algorithm.
+//! is about 4 times faster than the optimal-fit algorithm. Asking for
+//! a different number of lines with [`Penalties::looseness`] makes
+//! the optimal-fit algorithm slower, see there.
 
 #[cfg(feature = "smawk")]
 mod optimal_fit;
@@ -23,6 +25,7 @@ mod optimal_fit;
 pub use optimal_fit::{wrap_optimal_fit, OverflowError, Penalties};
 
 use crate::core::{Fragment, Word};
+use crate::{Error, ParseError};
 
 /// Describes how to wrap words into lines.
 ///
@@ -33,6 +36,7 @@ use crate::core::{Fragment, Word};
 /// an entire paragraph at a time in order to find optimal line breaks
 /// ([`WrapAlgorithm::OptimalFit`]).
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WrapAlgorithm {
     /// Wrap words using a fast and simple algorithm.
     ///
@@ -56,6 +60,47 @@ pub enum WrapAlgorithm {
     #[cfg(feature = "smawk")]
     OptimalFit(Penalties),
 
+    /// Choose the algorithm based on the size of each paragraph.
+    ///
+    /// Paragraphs with fewer than `optimal_below_words` words are
+    /// wrapped with [`WrapAlgorithm::OptimalFit`] and the default
+    /// [`Penalties`], longer paragraphs are wrapped with the faster
+    /// [`WrapAlgorithm::FirstFit`]. The optimal-fit algorithm is
+    /// about 4 times slower and the difference in quality is hard
+    /// to notice in very long paragraphs, such as machine-generated
+    /// logs. This makes the variant a good choice for interactive
+    /// programs which wrap text of unknown size.
+    ///
+    /// Without the `smawk` Cargo feature, all paragraphs are wrapped
+    /// with [`WrapAlgorithm::FirstFit`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, Options, WrapAlgorithm};
+    ///
+    /// let text = "This is a demo of the short last line penalty.";
+    /// let adaptive = |optimal_below_words| {
+    ///     Options::new(37).wrap_algorithm(WrapAlgorithm::Adaptive { optimal_below_words })
+    /// };
+    ///
+    /// // The ten words are wrapped with the first-fit algorithm:
+    /// assert_eq!(wrap(text, adaptive(5)),
+    ///            vec!["This is a demo of the short last line",
+    ///                 "penalty."]);
+    ///
+    /// // The optimal-fit algorithm avoids the short last line:
+    /// #[cfg(feature = "smawk")]
+    /// assert_eq!(wrap(text, adaptive(20)),
+    ///            vec!["This is a demo of the short last",
+    ///                 "line penalty."]);
+    /// ```
+    Adaptive {
+        /// Paragraphs with fewer words than this are wrapped with
+        /// [`WrapAlgorithm::OptimalFit`].
+        optimal_below_words: usize,
+    },
+
     /// Custom wrapping function.
     ///
     /// Use this if you want to implement your own wrapping algorithm.
@@ -86,6 +131,7 @@ pub enum WrapAlgorithm {
     ///                 "second, third,",
     ///                 "fourth, fifth, sixth"]);
     /// ```
+    #[cfg_attr(feature = "serde", serde(skip))]
     Custom(for<'a, 'b> fn(words: &'b [Word<'a>], line_widths: &'b [usize]) -> Vec<&'b [Word<'a>]>),
 }
 
@@ -114,22 +160,114 @@ impl PartialEq for WrapAlgorithm {
             (WrapAlgorithm::FirstFit, WrapAlgorithm::FirstFit) => true,
             #[cfg(feature = "smawk")]
             (WrapAlgorithm::OptimalFit(a), WrapAlgorithm::OptimalFit(b)) => a == b,
+            (
+                WrapAlgorithm::Adaptive {
+                    optimal_below_words: a,
+                },
+                WrapAlgorithm::Adaptive {
+                    optimal_below_words: b,
+                },
+            ) => a == b,
             (_, _) => false,
         }
     }
 }
 
+impl std::hash::Hash for WrapAlgorithm {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        #[cfg(feature = "smawk")]
+        if let WrapAlgorithm::OptimalFit(penalties) = self {
+            penalties.hash(state);
+        }
+        if let WrapAlgorithm::Adaptive {
+            optimal_below_words,
+        } = self
+        {
+            optimal_below_words.hash(state);
+        }
+    }
+}
+
 impl std::fmt::Debug for WrapAlgorithm {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             WrapAlgorithm::FirstFit => f.write_str("FirstFit"),
             #[cfg(feature = "smawk")]
             WrapAlgorithm::OptimalFit(penalties) => write!(f, "OptimalFit({:?})", penalties),
+            WrapAlgorithm::Adaptive {
+                optimal_below_words,
+            } => write!(
+                f,
+                "Adaptive {{ optimal_below_words: {} }}",
+                optimal_below_words
+            ),
             WrapAlgorithm::Custom(_) => f.write_str("Custom(...)"),
         }
     }
 }
 
+/// Formats the algorithm as `"first-fit"`, `"optimal-fit"`,
+/// `"adaptive:N"`, or `"custom"`. The penalties of
+/// [`WrapAlgorithm::OptimalFit`] are not included.
+impl std::fmt::Display for WrapAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WrapAlgorithm::FirstFit => f.write_str("first-fit"),
+            #[cfg(feature = "smawk")]
+            WrapAlgorithm::OptimalFit(_) => f.write_str("optimal-fit"),
+            WrapAlgorithm::Adaptive {
+                optimal_below_words,
+            } => write!(f, "adaptive:{}", optimal_below_words),
+            WrapAlgorithm::Custom(_) => f.write_str("custom"),
+        }
+    }
+}
+
+/// Parses `"first-fit"`, `"optimal-fit"`, and `"adaptive:N"`. The
+/// optimal-fit algorithm uses the default [`Penalties`].
+///
+/// This lets command line programs select the algorithm at runtime:
+///
+/// ```
+/// use textwrap::{ParseError, WrapAlgorithm};
+///
+/// let algorithm: WrapAlgorithm = "first-fit".parse().unwrap();
+/// assert_eq!(algorithm, WrapAlgorithm::FirstFit);
+/// assert_eq!(algorithm.to_string(), "first-fit");
+///
+/// #[cfg(feature = "smawk")]
+/// assert_eq!("optimal-fit".parse(), Ok(WrapAlgorithm::new_optimal_fit()));
+/// #[cfg(not(feature = "smawk"))]
+/// assert_eq!("optimal-fit".parse::<WrapAlgorithm>(),
+///            Err(ParseError::Unsupported { name: "optimal-fit", feature: "smawk" }));
+///
+/// assert_eq!("best-fit".parse::<WrapAlgorithm>(),
+///            Err(ParseError::Unknown(String::from("best-fit"))));
+/// ```
+impl std::str::FromStr for WrapAlgorithm {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first-fit" => Ok(WrapAlgorithm::FirstFit),
+            #[cfg(feature = "smawk")]
+            "optimal-fit" => Ok(WrapAlgorithm::new_optimal_fit()),
+            #[cfg(not(feature = "smawk"))]
+            "optimal-fit" => Err(ParseError::Unsupported {
+                name: "optimal-fit",
+                feature: "smawk",
+            }),
+            _ => match s.strip_prefix("adaptive:").map(str::parse) {
+                Some(Ok(optimal_below_words)) => Ok(WrapAlgorithm::Adaptive {
+                    optimal_below_words,
+                }),
+                _ => Err(ParseError::Unknown(s.to_string())),
+            },
+        }
+    }
+}
+
 impl WrapAlgorithm {
     /// Create new wrap algorithm.
     ///
@@ -158,6 +296,26 @@ impl WrapAlgorithm {
         WrapAlgorithm::OptimalFit(Penalties::new())
     }
 
+    /// The algorithm used for `fragments`. This resolves
+    /// [`WrapAlgorithm::Adaptive`] and returns other algorithms
+    /// unchanged.
+    ///
+    /// A word can be split into several fragments, e.g., by the
+    /// hyphenation or when it is too long for a line. Only the last
+    /// fragment of a word has whitespace after it, so these are the
+    /// fragments which are counted.
+    #[cfg_attr(not(feature = "smawk"), allow(unused_variables))]
+    pub(crate) fn for_fragments<T: Fragment>(&self, fragments: &[T]) -> WrapAlgorithm {
+        match *self {
+            #[cfg(feature = "smawk")]
+            WrapAlgorithm::Adaptive {
+                optimal_below_words,
+            } if count_words(fragments) < optimal_below_words => WrapAlgorithm::new_optimal_fit(),
+            WrapAlgorithm::Adaptive { .. } => WrapAlgorithm::FirstFit,
+            algorithm => algorithm,
+        }
+    }
+
     /// Wrap words according to line widths.
     ///
     /// The `line_widths` slice gives the target line width for each
@@ -169,23 +327,130 @@ impl WrapAlgorithm {
         words: &'b [Word<'a>],
         line_widths: &'b [usize],
     ) -> Vec<&'b [Word<'a>]> {
+        // The computation cannot overflow when the line widths are
+        // restricted to usize.
+        self.try_wrap(words, line_widths).unwrap()
+    }
+
+    /// Wrap words according to line widths, returning an error
+    /// instead of panicking.
+    ///
+    /// This is like [`WrapAlgorithm::wrap`], but if the optimal-fit
+    /// computation overflows, [`Error::Overflow`] is returned. This
+    /// cannot happen for words from a `&str` since the line widths
+    /// are integers, but the method lets you state this invariant
+    /// explicitly instead of relying on it.
+    pub fn try_wrap<'a, 'b>(
+        &self,
+        words: &'b [Word<'a>],
+        line_widths: &'b [usize],
+    ) -> Result<Vec<&'b [Word<'a>]>, Error> {
         // Every integer up to 2u64.pow(f64::MANTISSA_DIGITS) = 2**53
         // = 9_007_199_254_740_992 can be represented without loss by
         // a f64. Larger line widths will be rounded to the nearest
         // representable number.
         let f64_line_widths = line_widths.iter().map(|w| *w as f64).collect::<Vec<_>>();
 
-        match self {
-            WrapAlgorithm::FirstFit => wrap_first_fit(words, &f64_line_widths),
+        match self.for_fragments(words) {
+            WrapAlgorithm::FirstFit | WrapAlgorithm::Adaptive { .. } => {
+                Ok(wrap_first_fit(words, &f64_line_widths))
+            }
 
             #[cfg(feature = "smawk")]
             WrapAlgorithm::OptimalFit(penalties) => {
-                // The computation cannot overflow when the line
-                // widths are restricted to usize.
-                wrap_optimal_fit(words, &f64_line_widths, penalties).unwrap()
+                Ok(wrap_optimal_fit(words, &f64_line_widths, &penalties)?)
             }
 
-            WrapAlgorithm::Custom(func) => func(words, line_widths),
+            WrapAlgorithm::Custom(func) => Ok(func(words, line_widths)),
+        }
+    }
+
+    /// Wrap fragments according to fractional line widths.
+    ///
+    /// This is like [`WrapAlgorithm::wrap`], but it works with any
+    /// [`Fragment`] and with `f64` line widths. Use this when you
+    /// measure text in pixels or points, e.g., when wrapping text
+    /// drawn with a proportional font on a canvas. The fragment
+    /// widths and the line widths must use the same unit.
+    ///
+    /// If the optimal-fit computation overflows (see
+    /// [`wrap_optimal_fit()`] for when this can happen), the
+    /// fragments are wrapped with [`wrap_first_fit()`] instead.
+    ///
+    /// A [`WrapAlgorithm::Custom`] function wraps [`Word`]s with
+    /// integer widths. It is called with one empty word per fragment:
+    /// the word widths are rounded up and the line widths are rounded
+    /// down. The function must return the words in order, like the
+    /// built-in algorithms do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::core::Fragment;
+    /// use textwrap::WrapAlgorithm;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Glyphs(f64);
+    ///
+    /// impl Fragment for Glyphs {
+    ///     fn width(&self) -> f64 { self.0 }
+    ///     fn whitespace_width(&self) -> f64 { 4.5 }
+    ///     fn penalty_width(&self) -> f64 { 0.0 }
+    /// }
+    ///
+    /// let fragments = [Glyphs(30.25), Glyphs(12.5), Glyphs(40.0)];
+    /// let lines = WrapAlgorithm::FirstFit.wrap_fragments(&fragments, &[50.0]);
+    /// assert_eq!(lines, vec![&fragments[..2], &fragments[2..]]);
+    /// ```
+    pub fn wrap_fragments<'a, T: Fragment>(
+        &self,
+        fragments: &'a [T],
+        line_widths: &[f64],
+    ) -> Vec<&'a [T]> {
+        self.try_wrap_fragments(fragments, line_widths)
+            .unwrap_or_else(|_| wrap_first_fit(fragments, line_widths))
+    }
+
+    /// Wrap fragments according to fractional line widths, returning
+    /// [`Error::Overflow`] if the optimal-fit computation overflows.
+    pub(crate) fn try_wrap_fragments<'a, T: Fragment>(
+        &self,
+        fragments: &'a [T],
+        line_widths: &[f64],
+    ) -> Result<Vec<&'a [T]>, Error> {
+        match self.for_fragments(fragments) {
+            WrapAlgorithm::FirstFit | WrapAlgorithm::Adaptive { .. } => {
+                Ok(wrap_first_fit(fragments, line_widths))
+            }
+
+            #[cfg(feature = "smawk")]
+            WrapAlgorithm::OptimalFit(penalties) => {
+                Ok(wrap_optimal_fit(fragments, line_widths, &penalties)?)
+            }
+
+            WrapAlgorithm::Custom(func) => {
+                let words = fragments
+                    .iter()
+                    .map(|fragment| Word {
+                        width: fragment.width().ceil() as usize,
+                        ..Word::from("")
+                    })
+                    .collect::<Vec<_>>();
+                let line_widths = line_widths
+                    .iter()
+                    .map(|&width| width.floor() as usize)
+                    .collect::<Vec<_>>();
+                let mut start = 0;
+                Ok(func(&words, &line_widths)
+                    .into_iter()
+                    .map(|line| {
+                        let end = (start + line.len()).min(fragments.len());
+                        let fragments = &fragments[start..end];
+                        start = end;
+                        fragments
+                    })
+                    .collect())
+            }
         }
     }
 }
@@ -206,6 +471,9 @@ impl Default for WrapAlgorithm {
 /// widths, this function will not (and cannot) attempt to split them
 /// further when arranging them into lines.
 ///
+/// Use [`wrap_first_fit_iter()`] if your fragments are not in a
+/// slice or if you only need the first few lines.
+///
 /// # First-Fit Algorithm
 ///
 /// This implements a simple “greedy” algorithm: accumulate fragments
@@ -370,6 +638,202 @@ pub fn wrap_first_fit<'a, T: Fragment>(
     lines
 }
 
+/// Wrap abstract fragments into lines with a first-fit algorithm,
+/// one line at a time.
+///
+/// This produces the same lines as [`wrap_first_fit()`], but the
+/// fragments can come from any iterator and the lines are returned
+/// lazily. Only the fragments of the current line are kept in
+/// memory. This lets you wrap fragments while they are produced,
+/// e.g., while parsing a document, and you can stop after a number
+/// of lines without looking at the rest of the fragments.
+///
+/// Like [`wrap_first_fit()`], this always returns at least one line,
+/// which is empty if there are no fragments.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::wrap_algorithms::wrap_first_fit_iter;
+/// use textwrap::WordSeparator;
+///
+/// let text = "These few words will unfortunately not wrap nicely.";
+/// let words = WordSeparator::AsciiSpace.find_words(text);
+/// let lines = wrap_first_fit_iter(words, &[15.0])
+///     .take(2)
+///     .map(|line| line.iter().map(|word| word.word).collect::<Vec<_>>().join(" "))
+///     .collect::<Vec<_>>();
+/// assert_eq!(lines, vec!["These few words", "will"]);
+///
+/// // Infinite input works too, as long as you stop eventually:
+/// let words = std::iter::repeat(Word::from("Hello "));
+/// let lines = wrap_first_fit_iter(words, &[12.0]).take(3).collect::<Vec<_>>();
+/// assert_eq!(lines.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 2, 2]);
+/// ```
+pub fn wrap_first_fit_iter<'w, T, I>(
+    fragments: I,
+    line_widths: &'w [f64],
+) -> impl Iterator<Item = Vec<T>> + 'w
+where
+    T: Fragment + 'w,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: 'w,
+{
+    FirstFitIter {
+        fragments: fragments.into_iter(),
+        wrapper: LineWrapper::new(line_widths),
+        line: Vec::new(),
+        done: false,
+    }
+}
+
+/// Iterator returned by [`wrap_first_fit_iter()`].
+struct FirstFitIter<'w, I: Iterator> {
+    fragments: I,
+    wrapper: LineWrapper<'w>,
+    line: Vec<I::Item>,
+    done: bool,
+}
+
+impl<T: Fragment, I: Iterator<Item = T>> Iterator for FirstFitIter<'_, I> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+
+        for fragment in self.fragments.by_ref() {
+            if self.wrapper.push_word(&fragment).is_some() {
+                return Some(std::mem::replace(&mut self.line, vec![fragment]));
+            }
+            self.line.push(fragment);
+        }
+
+        self.done = true;
+        Some(std::mem::take(&mut self.line))
+    }
+}
+
+/// A line break found by [`LineWrapper::push_word()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineBreak {
+    /// Index of the line which was finished, starting from zero.
+    pub line: usize,
+    /// Number of fragments on the finished line.
+    pub fragments: usize,
+    /// Width of the finished line. This includes the penalty of the
+    /// last fragment, but not its whitespace.
+    pub width: f64,
+}
+
+/// The first-fit algorithm as a state machine which is driven one
+/// fragment at a time.
+///
+/// This is the algorithm used by [`wrap_first_fit()`] and
+/// [`wrap_first_fit_iter()`], but you push the fragments yourself
+/// and are told immediately when a line is finished. The wrapper
+/// does not store the fragments, it only keeps track of the width
+/// of the current line. This is useful in an interactive editor
+/// where words are added as the user types: you learn when a line
+/// break occurs without wrapping the paragraph again.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::core::Word;
+/// use textwrap::wrap_algorithms::{LineBreak, LineWrapper};
+///
+/// let mut wrapper = LineWrapper::new(&[10.0]);
+/// assert_eq!(wrapper.push_word(&Word::from("Memory ")), None);
+/// assert_eq!(wrapper.push_word(&Word::from("safety ")),
+///            Some(LineBreak { line: 0, fragments: 1, width: 6.0 }));
+/// assert_eq!(wrapper.push_word(&Word::from("now")), None);
+/// assert_eq!(wrapper.line_count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineWrapper<'w> {
+    line_widths: &'w [f64],
+    line: usize,
+    fragments: usize,
+    width: f64,
+    trailing_width: f64,
+}
+
+impl<'w> LineWrapper<'w> {
+    /// Create a new wrapper with no fragments. The `line_widths` are
+    /// used like in [`wrap_first_fit()`]: the last width is used for
+    /// all remaining lines.
+    pub fn new(line_widths: &'w [f64]) -> Self {
+        LineWrapper {
+            line_widths,
+            line: 0,
+            fragments: 0,
+            width: 0.0,
+            trailing_width: 0.0,
+        }
+    }
+
+    /// Add `fragment` to the current line.
+    ///
+    /// If the fragment does not fit, the current line is finished
+    /// and the fragment starts a new line. The finished line is then
+    /// returned. A fragment is always put on an empty line, even if
+    /// it is too wide.
+    pub fn push_word<T: Fragment>(&mut self, fragment: &T) -> Option<LineBreak> {
+        // The final line width is used for all remaining lines.
+        let line_width = self
+            .line_widths
+            .get(self.line)
+            .or_else(|| self.line_widths.last())
+            .copied()
+            .unwrap_or(0.0);
+        let width = fragment.width();
+        let mut line_break = None;
+        if self.width + width + fragment.penalty_width() > line_width && self.fragments > 0 {
+            line_break = Some(LineBreak {
+                line: self.line,
+                fragments: self.fragments,
+                width: self.width - self.trailing_width,
+            });
+            self.line += 1;
+            self.fragments = 0;
+            self.width = 0.0;
+        }
+        self.fragments += 1;
+        self.width += width + fragment.whitespace_width();
+        self.trailing_width = fragment.whitespace_width() - fragment.penalty_width();
+        line_break
+    }
+
+    /// Number of lines so far, including the current line. This is
+    /// one if no line breaks have been found, even if no fragments
+    /// have been pushed.
+    pub fn line_count(&self) -> usize {
+        self.line + 1
+    }
+
+    /// Number of fragments on the current line.
+    pub fn fragments(&self) -> usize {
+        self.fragments
+    }
+}
+
+/// Count the words in `fragments`. A fragment with whitespace after
+/// it ends a word, and so does the last fragment.
+#[cfg(feature = "smawk")]
+fn count_words<T: Fragment>(fragments: &[T]) -> usize {
+    let ends_with_word = fragments
+        .last()
+        .map_or(false, |fragment| fragment.whitespace_width() == 0.0);
+    fragments
+        .iter()
+        .filter(|fragment| fragment.whitespace_width() > 0.0)
+        .count()
+        + usize::from(ends_with_word)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -410,4 +874,117 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn wrap_first_fit_iter_matches_wrap_first_fit() {
+        let widths = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0, 5.0];
+        for len in 0..widths.len() {
+            let words = widths[..len].iter().map(|&w| Word(w)).collect::<Vec<_>>();
+            for line_widths in [&[0.0][..], &[5.0], &[8.0, 4.0], &[2.0, 7.0, 10.0], &[]] {
+                let expected = wrap_first_fit(&words, line_widths)
+                    .iter()
+                    .map(|line| line.iter().map(|word| word.0).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                let lines =
+                    wrap_first_fit_iter(widths[..len].iter().map(|&w| Word(w)), line_widths)
+                        .map(|line| line.iter().map(|word| word.0).collect::<Vec<_>>())
+                        .collect::<Vec<_>>();
+                assert_eq!(lines, expected, "line_widths: {:?}", line_widths);
+            }
+        }
+    }
+
+    #[test]
+    fn adaptive_for_fragments() {
+        use crate::core::Word;
+
+        let adaptive = WrapAlgorithm::Adaptive {
+            optimal_below_words: 3,
+        };
+        let two_words = [Word::from("foo"), Word::from("bar- "), Word::from("baz")];
+        let three_words = [Word::from("foo "), Word::from("bar "), Word::from("baz")];
+        #[cfg(feature = "smawk")]
+        assert_eq!(
+            adaptive.for_fragments(&two_words),
+            WrapAlgorithm::new_optimal_fit()
+        );
+        #[cfg(not(feature = "smawk"))]
+        assert_eq!(adaptive.for_fragments(&two_words), WrapAlgorithm::FirstFit);
+        assert_eq!(
+            adaptive.for_fragments(&three_words),
+            WrapAlgorithm::FirstFit
+        );
+        assert_eq!(
+            WrapAlgorithm::FirstFit.for_fragments(&two_words),
+            WrapAlgorithm::FirstFit
+        );
+        assert_eq!(adaptive.to_string().parse(), Ok(adaptive));
+    }
+
+    #[test]
+    fn wrap_fragments_fractional_widths() {
+        // The whitespace makes the first two words 2.5 + 1.0 + 2.5 =
+        // 6.0 wide, which only fits on lines at least that wide.
+        let words = vec![Word(2.5), Word(2.5), Word(2.5)];
+        assert_eq!(
+            WrapAlgorithm::FirstFit.wrap_fragments(&words, &[6.0]),
+            vec![&words[..2], &words[2..]]
+        );
+        assert_eq!(
+            WrapAlgorithm::FirstFit.wrap_fragments(&words, &[5.9]),
+            vec![&words[..1], &words[1..2], &words[2..]]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "smawk")]
+    fn wrap_fragments_optimal_fit_overflow() {
+        let words = vec![Word(1e200), Word(1e250), Word(1e300)];
+        assert_eq!(
+            WrapAlgorithm::new_optimal_fit().wrap_fragments(&words, &[1e300]),
+            wrap_first_fit(&words, &[1e300])
+        );
+    }
+
+    #[test]
+    fn line_wrapper_matches_wrap_first_fit() {
+        let words = (1..20).map(|n| Word((n % 7) as f64)).collect::<Vec<_>>();
+        let line_widths = [8.0, 12.0];
+        let mut wrapper = LineWrapper::new(&line_widths);
+        let breaks = words
+            .iter()
+            .filter_map(|word| wrapper.push_word(word))
+            .collect::<Vec<_>>();
+        let lines = wrap_first_fit(&words, &line_widths);
+        assert_eq!(wrapper.line_count(), lines.len());
+        assert_eq!(wrapper.fragments(), lines[lines.len() - 1].len());
+        for (line_break, line) in breaks.iter().zip(&lines) {
+            let width = line.iter().map(|word| word.0 + 1.0).sum::<f64>() - 1.0;
+            assert_eq!(line_break.fragments, line.len());
+            assert_eq!(line_break.width, width);
+        }
+    }
+
+    #[test]
+    fn wrap_fragments_custom() {
+        let words = [Word(6.0), Word(6.0), Word(6.0)];
+        assert_eq!(
+            WrapAlgorithm::Custom(|words, _| vec![words]).wrap_fragments(&words, &[10.0]),
+            vec![&words[..]]
+        );
+
+        fn one_per_line<'a, 'b>(
+            words: &'b [crate::core::Word<'a>],
+            line_widths: &'b [usize],
+        ) -> Vec<&'b [crate::core::Word<'a>]> {
+            assert_eq!(line_widths, [2]);
+            assert_eq!(words[0].width, 2);
+            words.chunks(1).collect()
+        }
+        let words = [Word(1.5), Word(0.5)];
+        assert_eq!(
+            WrapAlgorithm::Custom(one_per_line).wrap_fragments(&words, &[2.5]),
+            vec![&words[..1], &words[1..]]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use std::cell::RefCell;
 
-use crate::core::Fragment;
+use crate::core::{Fragment, FragmentBreak};
 
 /// Penalties for
 /// [`WrapAlgorithm::OptimalFit`](crate::WrapAlgorithm::OptimalFit)
@@ -19,7 +19,8 @@ use crate::core::Fragment;
 ///
 /// **Note:** Only available when the `smawk` Cargo feature is
 /// enabled.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Penalties {
     /// Per-line penalty. This is added for every line, which makes it
     /// expensive to output more lines than the minimum required.
@@ -127,8 +128,56 @@ pub struct Penalties {
     /// Set this to zero if you do not want to penalize short last lines.
     pub short_last_line_penalty: usize,
 
-    /// Penalty for lines ending with a hyphen.
+    /// Penalty for lines ending with a hyphen inserted by the word
+    /// splitter.
     pub hyphen_penalty: usize,
+
+    /// Penalty for lines ending with a hyphen which is part of the
+    /// text, such as the hyphen in `"well-known"`.
+    ///
+    /// Breaking at an existing hyphen does not change the text, so
+    /// this is normally much cheaper than `hyphen_penalty`. The
+    /// default of zero makes such breaks as good as breaks at
+    /// whitespace. Increase it to prefer breaking at whitespace in
+    /// text with many hyphenated words. Use
+    /// [`Penalties::hard_hyphen_penalty()`] to change it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hard_hyphen_penalty: usize,
+
+    /// Requested change in the number of lines.
+    ///
+    /// Like the `\looseness` parameter in TeX, a positive value asks
+    /// for a paragraph with more lines than the optimal solution and
+    /// a negative value asks for fewer lines. The best solution with
+    /// the requested number of lines is used. If no such solution
+    /// exists, the number of lines closest to the request is used
+    /// instead. Only solutions where no line overflows the line
+    /// width are considered, unless the line consists of a single
+    /// fragment which is too wide by itself.
+    ///
+    /// A non-zero value is expensive: the search takes O(_n_ × _k_)
+    /// memory and O(_n_ × _k_ × _w_) time for _n_ fragments, _k_
+    /// lines, and _w_ fragments per line, instead of the usual O(_n_).
+    /// If _n_ × _k_ exceeds one million, the value is ignored and the
+    /// optimal solution is used.
+    ///
+    /// The default of zero gives the optimal solution. Use
+    /// [`Penalties::looseness()`] to change it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub looseness: i32,
+
+    /// Penalty for lines ending with the first word of a sentence.
+    ///
+    /// A line which ends with `"... end. The"` leaves the first word
+    /// of the new sentence stranded at the end of the line. This
+    /// penalty is added to such lines, which makes the algorithm
+    /// prefer moving the word to the next line. Sentences are found
+    /// with [`Fragment::ends_sentence`].
+    ///
+    /// The default of zero disables the penalty. Use
+    /// [`Penalties::sentence_start_penalty()`] to change it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sentence_start_penalty: usize,
 }
 
 impl Penalties {
@@ -145,6 +194,174 @@ impl Penalties {
             short_last_line_fraction: 4,
             short_last_line_penalty: 25,
             hyphen_penalty: 25,
+            hard_hyphen_penalty: 0,
+            looseness: 0,
+            sentence_start_penalty: 0,
+        }
+    }
+
+    /// Penalties for proportional text which is measured in pixels
+    /// or points.
+    ///
+    /// The penalties from [`Penalties::new()`] expect gaps measured
+    /// in columns. When text is measured in pixels, the gaps are many
+    /// times larger and the cost of a gap, which grows with the square
+    /// of the gap, dwarfs the other penalties. Hyphenation and short
+    /// last lines are then no longer penalized in practice.
+    ///
+    /// This returns the default penalties [scaled](Penalties::scaled)
+    /// as if a line `target_width` units wide held 66 characters,
+    /// which is a classic line length for body text. A `target_width`
+    /// which is not positive gives the default penalties.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::measure::ClosureWidth;
+    /// use textwrap::wrap_algorithms::Penalties;
+    /// use textwrap::{Options, WrapAlgorithm};
+    ///
+    /// // A font where every character is 10 pixels wide.
+    /// let measure = ClosureWidth::new(|text: &str| 10.0 * text.chars().count() as f64);
+    /// let text = "The quick brown fox jumps over the lazy dog. Pack my box with \
+    ///             five dozen liquor jugs. How vexingly quick daft zebras jump! \
+    ///             Sphinx of black";
+    /// let last_line = |penalties| {
+    ///     let options = Options::new(80).wrap_algorithm(WrapAlgorithm::OptimalFit(penalties));
+    ///     let lines = measure.wrap(text, 660.0, &options).unwrap();
+    ///     let words = lines.last().unwrap().iter().map(|word| word.word.word);
+    ///     words.collect::<Vec<_>>().join(" ")
+    /// };
+    ///
+    /// // The short last line penalty is insignificant with pixel widths:
+    /// assert_eq!(last_line(Penalties::new()), "black");
+    /// assert_eq!(last_line(Penalties::for_proportional(660.0)), "of black");
+    /// ```
+    pub fn for_proportional(target_width: f64) -> Self {
+        if target_width > 0.0 && target_width.is_finite() {
+            Penalties::new().scaled(target_width / 66.0)
+        } else {
+            Penalties::new()
+        }
+    }
+
+    /// Scale the penalties for gaps which are `factor` times larger.
+    ///
+    /// Use this when one column of text corresponds to `factor`
+    /// units, e.g., when a font with an average character width of 8
+    /// pixels is measured in pixels. The penalties which are compared
+    /// with the squared gaps are multiplied by `factor * factor` and
+    /// the [`overflow_penalty`](Self::overflow_penalty), which is
+    /// multiplied by the overflow, is multiplied by `factor`. This
+    /// keeps the line breaks the same as for monospace text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::wrap_algorithms::Penalties;
+    ///
+    /// let penalties = Penalties::new().scaled(10.0);
+    /// assert_eq!(penalties.nline_penalty, 100 * Penalties::new().nline_penalty);
+    /// assert_eq!(penalties.overflow_penalty, 10 * Penalties::new().overflow_penalty);
+    /// ```
+    pub fn scaled(self, factor: f64) -> Self {
+        let scale = |penalty: usize, factor: f64| (penalty as f64 * factor).round() as usize;
+        let squared = factor * factor;
+        Penalties {
+            nline_penalty: scale(self.nline_penalty, squared),
+            overflow_penalty: scale(self.overflow_penalty, factor),
+            short_last_line_penalty: scale(self.short_last_line_penalty, squared),
+            hyphen_penalty: scale(self.hyphen_penalty, squared),
+            hard_hyphen_penalty: scale(self.hard_hyphen_penalty, squared),
+            sentence_start_penalty: scale(self.sentence_start_penalty, squared),
+            ..self
+        }
+    }
+
+    /// Change [`self.hard_hyphen_penalty`]. This is the penalty for
+    /// breaking a line after a hyphen which is already in the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, wrap_algorithms::Penalties, Options, WrapAlgorithm};
+    ///
+    /// let text = "a well-known and well-tested fact";
+    /// let penalties = Penalties::new();
+    /// let options = Options::new(24).wrap_algorithm(WrapAlgorithm::OptimalFit(penalties));
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["a well-known and well-", "tested fact"]);
+    ///
+    /// let penalties = penalties.hard_hyphen_penalty(100);
+    /// let options = options.wrap_algorithm(WrapAlgorithm::OptimalFit(penalties));
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["a well-known and", "well-tested fact"]);
+    /// ```
+    ///
+    /// [`self.hard_hyphen_penalty`]: #structfield.hard_hyphen_penalty
+    pub const fn hard_hyphen_penalty(self, hard_hyphen_penalty: usize) -> Self {
+        Penalties {
+            hard_hyphen_penalty,
+            ..self
+        }
+    }
+
+    /// Change [`self.looseness`]. This lets you ask for a paragraph
+    /// which is `looseness` lines longer or shorter than the optimal
+    /// paragraph. This is useful to avoid a nearly empty last line,
+    /// e.g., in justified text.
+    ///
+    /// This is meant for paragraphs, not for whole documents: the
+    /// cost grows with the number of lines times the number of
+    /// fragments, see [`self.looseness`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, wrap_algorithms::Penalties, Options, WrapAlgorithm};
+    ///
+    /// let text = "To be, or not to be: that is the question";
+    /// let penalties = Penalties::new();
+    /// let options = Options::new(16).wrap_algorithm(WrapAlgorithm::OptimalFit(penalties));
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["To be, or not to", "be: that is the", "question"]);
+    ///
+    /// let penalties = penalties.looseness(1);
+    /// let options = options.wrap_algorithm(WrapAlgorithm::OptimalFit(penalties));
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["To be, or", "not to be:", "that is the", "question"]);
+    /// ```
+    ///
+    /// [`self.looseness`]: #structfield.looseness
+    pub const fn looseness(self, looseness: i32) -> Self {
+        Penalties { looseness, ..self }
+    }
+
+    /// Change [`self.sentence_start_penalty`]. This discourages
+    /// lines which end with the first word of a sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use textwrap::{wrap, wrap_algorithms::Penalties, Options, WrapAlgorithm};
+    ///
+    /// let text = "It was late. I went home and slept for a long time.";
+    /// let penalties = Penalties::new();
+    /// let options = Options::new(16).wrap_algorithm(WrapAlgorithm::OptimalFit(penalties));
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["It was late. I", "went home and", "slept for a long", "time."]);
+    ///
+    /// let penalties = penalties.sentence_start_penalty(500);
+    /// let options = options.wrap_algorithm(WrapAlgorithm::OptimalFit(penalties));
+    /// assert_eq!(wrap(text, &options),
+    ///            vec!["It was late.", "I went home and", "slept for a long", "time."]);
+    /// ```
+    ///
+    /// [`self.sentence_start_penalty`]: #structfield.sentence_start_penalty
+    pub const fn sentence_start_penalty(self, sentence_start_penalty: usize) -> Self {
+        Penalties {
+            sentence_start_penalty,
+            ..self
         }
     }
 }
@@ -254,7 +471,8 @@ impl std::error::Error for OverflowError {}
 /// points.
 ///
 /// This means that the time complexity remains O(_n_) where _n_ is
-/// the number of words. Compared to
+/// the number of words, unless a non-zero [`Penalties::looseness`]
+/// asks for a different number of lines. Compared to
 /// [`wrap_first_fit()`](super::wrap_first_fit), this function is
 /// about 4 times slower.
 ///
@@ -325,46 +543,7 @@ pub fn wrap_optimal_fit<'a, 'b, T: Fragment>(
             .unwrap_or(default_line_width);
         let target_width = line_width.max(1.0);
 
-        // Compute the width of a line spanning fragments[i..j] in
-        // constant time. We need to adjust widths[j] by subtracting
-        // the whitespace of fragment[j-1] and then add the penalty.
-        let line_width = widths[j] - widths[i] - fragments[j - 1].whitespace_width()
-            + fragments[j - 1].penalty_width();
-
-        // We compute cost of the line containing fragments[i..j]. We
-        // start with values[i].1, which is the optimal cost for
-        // breaking before fragments[i].
-        //
-        // First, every extra line cost NLINE_PENALTY.
-        let mut cost = minima[i].1 + penalties.nline_penalty as f64;
-
-        // Next, we add a penalty depending on the line length.
-        if line_width > target_width {
-            // Lines that overflow get a hefty penalty.
-            let overflow = line_width - target_width;
-            cost += overflow * penalties.overflow_penalty as f64;
-        } else if j < fragments.len() {
-            // Other lines (except for the last line) get a milder
-            // penalty which depend on the size of the gap.
-            let gap = target_width - line_width;
-            cost += gap * gap;
-        } else if i + 1 == j
-            && line_width < target_width / penalties.short_last_line_fraction as f64
-        {
-            // The last line can have any size gap, but we do add a
-            // penalty if the line is very short (typically because it
-            // contains just a single word).
-            cost += penalties.short_last_line_penalty as f64;
-        }
-
-        // Finally, we discourage hyphens.
-        if fragments[j - 1].penalty_width() > 0.0 {
-            // TODO: this should use a penalty value from the fragment
-            // instead.
-            cost += penalties.hyphen_penalty as f64;
-        }
-
-        cost
+        minima[i].1 + line_cost(fragments, &widths, i, j, target_width, penalties)
     });
 
     for (_, cost) in &minima {
@@ -373,7 +552,23 @@ pub fn wrap_optimal_fit<'a, 'b, T: Fragment>(
         }
     }
 
-    let mut lines = Vec::with_capacity(line_numbers.get(fragments.len(), &minima));
+    let line_count = line_numbers.get(fragments.len(), &minima);
+    if penalties.looseness != 0 && !fragments.is_empty() {
+        let requested = (line_count as i64 + penalties.looseness as i64)
+            .clamp(1, fragments.len() as i64) as usize;
+        if let Some(lines) = wrap_with_line_count(
+            fragments,
+            &widths,
+            line_widths,
+            penalties,
+            requested,
+            line_count,
+        ) {
+            return Ok(lines);
+        }
+    }
+
+    let mut lines = Vec::with_capacity(line_count);
     let mut pos = fragments.len();
     loop {
         let prev = minima[pos].0;
@@ -388,6 +583,167 @@ pub fn wrap_optimal_fit<'a, 'b, T: Fragment>(
     Ok(lines)
 }
 
+/// Width of a line with `fragments[i..j]`.
+///
+/// This is computed in constant time using the cumulative `widths`.
+/// We need to adjust widths[j] by subtracting the whitespace of
+/// fragment[j-1] and then add the penalty.
+fn line_width<T: Fragment>(fragments: &[T], widths: &[f64], i: usize, j: usize) -> f64 {
+    widths[j] - widths[i] - fragments[j - 1].whitespace_width() + fragments[j - 1].penalty_width()
+}
+
+/// Cost of the line containing `fragments[i..j]`.
+fn line_cost<T: Fragment>(
+    fragments: &[T],
+    widths: &[f64],
+    i: usize,
+    j: usize,
+    target_width: f64,
+    penalties: &Penalties,
+) -> f64 {
+    let line_width = line_width(fragments, widths, i, j);
+
+    // First, every extra line cost NLINE_PENALTY.
+    let mut cost = penalties.nline_penalty as f64;
+
+    // Next, we add a penalty depending on the line length.
+    if line_width > target_width {
+        // Lines that overflow get a hefty penalty.
+        let overflow = line_width - target_width;
+        cost += overflow * penalties.overflow_penalty as f64;
+    } else if j < fragments.len() {
+        // Other lines (except for the last line) get a milder
+        // penalty which depend on the size of the gap.
+        let gap = target_width - line_width;
+        cost += gap * gap;
+    } else if i + 1 == j && line_width < target_width / penalties.short_last_line_fraction as f64 {
+        // The last line can have any size gap, but we do add a
+        // penalty if the line is very short (typically because it
+        // contains just a single word).
+        cost += penalties.short_last_line_penalty as f64;
+    }
+
+    // We discourage stranding the first word of a sentence.
+    if j < fragments.len() && i + 2 <= j && fragments[j - 2].ends_sentence() {
+        cost += penalties.sentence_start_penalty as f64;
+    }
+
+    // Finally, we discourage hyphens.
+    match fragments[j - 1].break_kind() {
+        FragmentBreak::InsertedHyphen => cost += penalties.hyphen_penalty as f64,
+        FragmentBreak::HardHyphen if j < fragments.len() => {
+            cost += penalties.hard_hyphen_penalty as f64;
+        }
+        _ => {}
+    }
+
+    cost
+}
+
+/// The largest number of back-pointers used by
+/// [`wrap_with_line_count`]. This bounds the memory to 4 MB and keeps
+/// the search fast enough for paragraphs of a few thousand words.
+const MAX_LOOSENESS_CELLS: usize = 1_000_000;
+
+/// Wrap `fragments` into `requested` lines, or as close to
+/// `requested` as possible, see [`Penalties::looseness`].
+///
+/// Unlike the SMAWK-based search in [`wrap_optimal_fit`], this
+/// dynamic programming keeps track of the number of lines. Lines
+/// which overflow are skipped, so only breaks within a line width of
+/// each other are considered. This makes it O(_n_ × _k_ × _w_) where
+/// _k_ is the number of lines and _w_ is the number of fragments on
+/// a line.
+///
+/// Only the costs for the previous number of lines are kept, but a
+/// back-pointer is needed for every number of lines and fragment.
+/// They are stored as `u32` values, which makes the memory use
+/// 4 × _n_ × _k_ bytes. Since the number of lines is at most the
+/// number of fragments, this is 4 × _n_² bytes in the worst case.
+///
+/// Returns `None` if no solution with a number of lines between
+/// `requested` and `optimal_count` (exclusive) can be found without
+/// overflowing lines, or if the back-pointers would need more than
+/// [`MAX_LOOSENESS_CELLS`] entries.
+///
+/// [`MAX_LOOSENESS_CELLS`]: MAX_LOOSENESS_CELLS
+fn wrap_with_line_count<'a, T: Fragment>(
+    fragments: &'a [T],
+    widths: &[f64],
+    line_widths: &[f64],
+    penalties: &Penalties,
+    requested: usize,
+    optimal_count: usize,
+) -> Option<Vec<&'a [T]>> {
+    let default_line_width = line_widths.last().copied().unwrap_or(0.0);
+    let max_count = requested.max(optimal_count);
+    let row_len = fragments.len() + 1;
+    match (max_count + 1).checked_mul(row_len) {
+        Some(cells) if cells <= MAX_LOOSENESS_CELLS => {}
+        _ => return None,
+    }
+
+    // prev_costs[j] and costs[j] are the costs of putting
+    // fragments[..j] on count - 1 and count lines, respectively.
+    // breaks[count * row_len + j] is where the last of these lines
+    // starts, and total_costs[count] is the cost of putting all
+    // fragments on count lines.
+    let mut prev_costs = vec![f64::INFINITY; row_len];
+    let mut costs = vec![f64::INFINITY; row_len];
+    let mut breaks = vec![0u32; (max_count + 1) * row_len];
+    let mut total_costs = vec![f64::INFINITY; max_count + 1];
+    prev_costs[0] = 0.0;
+    for count in 1..=max_count {
+        let target_width = line_widths
+            .get(count - 1)
+            .copied()
+            .unwrap_or(default_line_width)
+            .max(1.0);
+        let row = &mut breaks[count * row_len..(count + 1) * row_len];
+        costs.fill(f64::INFINITY);
+        for j in 1..=fragments.len() {
+            for i in (0..j).rev() {
+                if i + 1 < j && line_width(fragments, widths, i, j) > target_width {
+                    break;
+                }
+                let cost =
+                    prev_costs[i] + line_cost(fragments, widths, i, j, target_width, penalties);
+                if cost < costs[j] {
+                    costs[j] = cost;
+                    // The conversion cannot fail since i < fragments.len().
+                    row[j] = i as u32;
+                }
+            }
+        }
+        total_costs[count] = costs[fragments.len()];
+        std::mem::swap(&mut prev_costs, &mut costs);
+    }
+
+    // Search from the requested number of lines towards the optimal
+    // number of lines.
+    let mut count = requested;
+    while total_costs[count].is_infinite() {
+        if count == optimal_count {
+            return None;
+        }
+        count = if count < optimal_count {
+            count + 1
+        } else {
+            count - 1
+        };
+    }
+
+    let mut lines = Vec::with_capacity(count);
+    let mut pos = fragments.len();
+    for count in (1..=count).rev() {
+        let prev = breaks[count * row_len + pos] as usize;
+        lines.push(&fragments[prev..pos]);
+        pos = prev;
+    }
+    lines.reverse();
+    Some(lines)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -402,6 +758,63 @@ mod tests {
         fn penalty_width(&self) -> f64 { 0.0 }
     }
 
+    #[test]
+    fn looseness_fewer_lines() {
+        use crate::core::Word;
+
+        let mut hyphenated = Word::from("yyy");
+        hyphenated.penalty = "-";
+        let words = vec![
+            Word::from("xxxxx "),
+            hyphenated,
+            Word::from("zzzz "),
+            Word::from("wwwww"),
+        ];
+        let penalties = Penalties {
+            hyphen_penalty: 10_000,
+            ..Penalties::new()
+        };
+        assert_eq!(
+            wrap_optimal_fit(&words, &[10.0], &penalties).unwrap(),
+            vec![&words[0..1], &words[1..3], &words[3..4]]
+        );
+        let penalties = penalties.looseness(-1);
+        assert_eq!(
+            wrap_optimal_fit(&words, &[10.0], &penalties).unwrap(),
+            vec![&words[0..2], &words[2..4]]
+        );
+        let penalties = penalties.looseness(-2);
+        assert_eq!(
+            wrap_optimal_fit(&words, &[10.0], &penalties).unwrap(),
+            vec![&words[0..2], &words[2..4]]
+        );
+    }
+
+    #[test]
+    fn looseness_is_limited_by_fragments() {
+        let words = vec![Word(3.0), Word(3.0)];
+        let penalties = Penalties::new().looseness(5);
+        assert_eq!(
+            wrap_optimal_fit(&words, &[80.0], &penalties).unwrap(),
+            vec![&words[0..1], &words[1..2]]
+        );
+    }
+
+    #[test]
+    fn looseness_is_ignored_for_large_paragraphs() {
+        let words = (0..2_000).map(|_| Word(3.0)).collect::<Vec<_>>();
+        let optimal = wrap_optimal_fit(&words, &[8.0], &Penalties::new()).unwrap();
+        let penalties = Penalties::new().looseness(1);
+        assert_eq!(
+            wrap_optimal_fit(&words, &[8.0], &penalties).unwrap(),
+            optimal
+        );
+
+        let words = &words[..200];
+        let lines = wrap_optimal_fit(words, &[8.0], &penalties).unwrap();
+        assert_eq!(lines.len(), 101);
+    }
+
     #[test]
     fn wrap_fragments_with_infinite_widths() {
         let words = vec![Word(f64::INFINITY)];
@@ -430,4 +843,12 @@ mod tests {
             Ok(vec![&vec![Word(1e25), Word(1e50), Word(1e75)][..]])
         );
     }
+
+    #[test]
+    fn for_proportional_invalid_width() {
+        for width in [0.0, -10.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(Penalties::for_proportional(width), Penalties::new());
+        }
+        assert_eq!(Penalties::for_proportional(66.0), Penalties::new());
+    }
 }
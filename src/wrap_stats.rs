@@ -0,0 +1,308 @@
+//! Statistics about wrapped text.
+
+use std::borrow::Cow;
+
+use crate::core::{display_width, display_width_with, indent_width_for};
+use crate::wrap::{wrap_lines, LineParts, LineSink};
+use crate::{Error, Options};
+
+/// Statistics about wrapped text, as computed by [`wrap_stats()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WrapStats {
+    /// Number of lines.
+    pub lines: usize,
+    /// Width of the widest line, including indentation.
+    pub max_line_width: usize,
+    /// Width of the narrowest line, including indentation.
+    pub min_line_width: usize,
+    /// Average number of unused columns at the end of the lines.
+    /// Only lines followed by another line from the same paragraph
+    /// are counted since the last line of a paragraph is naturally
+    /// short. Lines wider than the width have no gap.
+    pub mean_gap: f64,
+    /// Number of lines which end with a hyphen inserted by the
+    /// [`WordSplitter`](crate::WordSplitter).
+    pub hyphenated_lines: usize,
+}
+
+/// Compute statistics about the result of wrapping text.
+///
+/// The statistics are computed while wrapping, without collecting
+/// the wrapped lines, which makes this faster than calling [`wrap()`](crate::wrap())
+/// and inspecting the result. This is useful in tests or when you
+/// want to compare different [`Options`], e.g., to choose the
+/// [`Penalties`](crate::wrap_algorithms::Penalties) which give the
+/// most even right margin.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{wrap_stats, Options, WrapAlgorithm};
+///
+/// let text = "Memory safety without garbage collection.";
+/// let stats = wrap_stats(text, Options::new(15).wrap_algorithm(WrapAlgorithm::FirstFit));
+/// assert_eq!(stats.lines, 3);
+/// assert_eq!(stats.max_line_width, 15);
+/// assert_eq!(stats.min_line_width, 11);
+/// assert_eq!(stats.mean_gap, 1.0);
+/// assert_eq!(stats.hyphenated_lines, 0);
+/// ```
+pub fn wrap_stats<'a, Opt>(text: &str, width_or_options: Opt) -> WrapStats
+where
+    Opt: Into<Options<'a>>,
+{
+    // Like `wrap`, this cannot overflow since the line widths are
+    // integers, and `MinWidth::Error` is replaced.
+    let options = width_or_options.into().proceed_on_min_width_error();
+    try_wrap_stats(text, options).unwrap()
+}
+
+/// Compute statistics about the result of wrapping text, returning
+/// an error instead of panicking.
+///
+/// This is like [`wrap_stats()`], but the errors listed below are
+/// returned instead of causing a panic. See
+/// [`try_wrap()`](crate::try_wrap()) for details.
+///
+/// # Errors
+///
+/// Returns [`Error::Overflow`] if the optimal-fit algorithm
+/// overflows, and [`Error::WidthTooSmall`] if
+/// [`Options::min_width`] is [`MinWidth::Error`](crate::MinWidth::Error)
+/// and there is too little room for the text.
+pub fn try_wrap_stats<'a, Opt>(text: &str, width_or_options: Opt) -> Result<WrapStats, Error>
+where
+    Opt: Into<Options<'a>>,
+{
+    let options: Options = width_or_options.into();
+    let options = options.build()?;
+    let mut sink = StatsSink {
+        options: &options,
+        stats: WrapStats {
+            lines: 0,
+            max_line_width: 0,
+            min_line_width: usize::MAX,
+            mean_gap: 0.0,
+            hyphenated_lines: 0,
+        },
+        pending_width: None,
+        gaps: 0,
+        gap_lines: 0,
+    };
+    wrap_lines(text, &options, &mut sink)?;
+
+    let mut stats = sink.stats;
+    if sink.gap_lines > 0 {
+        stats.mean_gap = sink.gaps as f64 / sink.gap_lines as f64;
+    }
+    Ok(stats)
+}
+
+/// Measures the wrapped lines instead of collecting them.
+struct StatsSink<'o> {
+    options: &'o Options<'o>,
+    stats: WrapStats,
+    /// Width of the last line. The gap of this line is only counted
+    /// if another line from the same paragraph follows.
+    pending_width: Option<usize>,
+    gaps: usize,
+    gap_lines: usize,
+}
+
+impl StatsSink<'_> {
+    fn add_line(&mut self, width: usize) {
+        if let Some(width) = self.pending_width.replace(width) {
+            self.gaps += self.options.width.saturating_sub(width);
+            self.gap_lines += 1;
+        }
+        self.stats.lines += 1;
+        self.stats.max_line_width = std::cmp::max(self.stats.max_line_width, width);
+        self.stats.min_line_width = std::cmp::min(self.stats.min_line_width, width);
+    }
+
+    fn text_width(&self, text: &str) -> usize {
+        let measured = self.options.normalization.measured(text);
+        display_width_with(&measured, self.options.width_overrides)
+    }
+}
+
+impl<'a> LineSink<'a> for StatsSink<'_> {
+    fn line_count(&self) -> usize {
+        self.stats.lines
+    }
+
+    fn end_input_line(&mut self) {
+        self.pending_width = None;
+    }
+
+    fn push_line(&mut self, line: Cow<'a, str>) {
+        self.add_line(self.text_width(&line));
+    }
+
+    fn push_parts(&mut self, parts: LineParts<'a, '_>) {
+        let width = indent_width_for(self.options, self.stats.lines)
+            + self.text_width(&parts.text)
+            + display_width(parts.penalty)
+            + display_width(parts.padding);
+        if !parts.penalty.is_empty() {
+            self.stats.hyphenated_lines += 1;
+        }
+        self.add_line(width);
+    }
+}
+
+/// Compute the number of lines in the result of filling text.
+///
+/// This is the height of [`fill()`](crate::fill()) with the same
+/// arguments, i.e., the number of lines returned by
+/// [`wrap()`](crate::wrap()). A trailing newline in `text` gives an
+/// empty last line, which is counted. Like
+/// [`wrap_stats()`], the lines are not built, which makes this
+/// suitable for a layout pass which needs to know how tall a block
+/// of text will be before it is drawn.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::{fill, fill_height};
+///
+/// let text = "Memory safety without garbage collection.";
+/// assert_eq!(fill_height(text, 15), 3);
+/// assert_eq!(fill_height(text, 15), fill(text, 15).lines().count());
+/// assert_eq!(fill_height("", 15), 1);
+/// assert_eq!(fill_height("foo\n", 15), 2);
+/// ```
+pub fn fill_height<'a, Opt>(text: &str, width_or_options: Opt) -> usize
+where
+    Opt: Into<Options<'a>>,
+{
+    // Like `fill`, this cannot overflow since the line widths are
+    // integers, and `MinWidth::Error` is replaced.
+    let options = width_or_options.into().proceed_on_min_width_error();
+    try_fill_height(text, options).unwrap()
+}
+
+/// Compute the number of lines in the result of filling text,
+/// returning an error instead of panicking.
+///
+/// This is like [`fill_height()`], but the errors listed below are
+/// returned instead of causing a panic. It is the counterpart of [`try_fill()`](crate::try_fill())
+/// for layout passes. See [`try_wrap()`](crate::try_wrap()) for
+/// details.
+///
+/// # Errors
+///
+/// Returns [`Error::Overflow`] if the optimal-fit algorithm
+/// overflows, and [`Error::WidthTooSmall`] if
+/// [`Options::min_width`] is [`MinWidth::Error`](crate::MinWidth::Error)
+/// and there is too little room for the text.
+///
+/// # Examples
+///
+/// ```
+/// use textwrap::try_fill_height;
+///
+/// assert_eq!(try_fill_height("foo bar baz", 7), Ok(2));
+/// ```
+pub fn try_fill_height<'a, Opt>(text: &str, width_or_options: Opt) -> Result<usize, Error>
+where
+    Opt: Into<Options<'a>>,
+{
+    try_wrap_stats(text, width_or_options).map(|stats| stats.lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wrap, WordOverflow, WordSplitter, WrapAlgorithm};
+
+    fn assert_stats_match_wrap(text: &str, options: &Options<'_>) {
+        let lines = wrap(text, options);
+        let stats = wrap_stats(text, options);
+        let widths = lines
+            .iter()
+            .map(|line| display_width_with(line, options.width_overrides));
+        assert_eq!(stats.lines, lines.len());
+        assert_eq!(stats.max_line_width, widths.clone().max().unwrap());
+        assert_eq!(stats.min_line_width, widths.min().unwrap());
+    }
+
+    #[test]
+    fn stats_match_wrap() {
+        let text = "Memory safety\nwithout  garbage collection.\n\nA fully-qualified example.";
+        for width in 0..30 {
+            let options = Options::new(width);
+            assert_stats_match_wrap(text, &options);
+            assert_stats_match_wrap(text, &options.clone().initial_indent("* "));
+            assert_stats_match_wrap(text, &options.clone().subsequent_indent("  "));
+            assert_stats_match_wrap(text, &options.clone().indents(&["* ", "", "    "]));
+            assert_stats_match_wrap(
+                text,
+                &options.clone().word_overflow(WordOverflow::Truncate("...")),
+            );
+            assert_stats_match_wrap(text, &options.clone().break_words(false));
+            assert_stats_match_wrap(text, &options.clone().drop_whitespace(false));
+            assert_stats_match_wrap(text, &options.clone().preserve_inner_whitespace(true));
+            assert_stats_match_wrap(text, &options.clone().width_overrides(&[('e', 2)]));
+            assert_stats_match_wrap(
+                text,
+                &options
+                    .clone()
+                    .preserve_inner_whitespace(true)
+                    .word_overflow(WordOverflow::Truncate("...")),
+            );
+        }
+    }
+
+    #[test]
+    fn stats_match_wrap_wide_chars() {
+        let text = "東京都庁舎です 漢字\u{3000}abc漢字\u{2003}";
+        for width in 0..20 {
+            let options = Options::new(width).wide_char_padding(" ");
+            assert_stats_match_wrap(text, &options);
+            assert_stats_match_wrap(text, &options.clone().width_overrides(&[('b', 2)]));
+        }
+    }
+
+    #[test]
+    fn fill_height_matches_wrap() {
+        let text = "Memory safety\nwithout garbage collection.\n\n";
+        for width in 0..20 {
+            assert_eq!(fill_height(text, width), wrap(text, width).len());
+        }
+    }
+
+    #[test]
+    fn stats_empty() {
+        let stats = wrap_stats("", 10);
+        assert_eq!(stats.lines, 1);
+        assert_eq!(stats.max_line_width, 0);
+        assert_eq!(stats.min_line_width, 0);
+        assert_eq!(stats.mean_gap, 0.0);
+    }
+
+    #[test]
+    fn stats_mean_gap_skips_last_lines() {
+        let options = Options::new(10).wrap_algorithm(WrapAlgorithm::FirstFit);
+        // Lines are "foo bar", "baz", "1234567", and "xyz".
+        let stats = wrap_stats("foo bar baz\n1234567 xyz", options);
+        assert_eq!(stats.lines, 4);
+        assert_eq!(stats.mean_gap, 3.0);
+    }
+
+    #[test]
+    fn stats_hyphenated_lines() {
+        let options = Options::new(8)
+            .wrap_algorithm(WrapAlgorithm::FirstFit)
+            .word_splitter(WordSplitter::Custom(|word| {
+                word.find("ion").into_iter().collect()
+            }));
+        assert_eq!(
+            wrap("garbage collection", &options),
+            vec!["garbage", "collect-", "ion"]
+        );
+        let stats = wrap_stats("garbage collection", &options);
+        assert_eq!(stats.hyphenated_lines, 1);
+        assert_eq!(stats.max_line_width, 8);
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "serde")]
+
+use serde::Deserialize;
+use textwrap::{
+    wrap, LineEnding, Options, WordOverflow, WordSeparator, WordSplitter, WrapAlgorithm,
+};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct Config {
+    wrap_algorithm: WrapAlgorithm,
+    word_separator: WordSeparator,
+    word_splitter: WordSplitter,
+    line_ending: LineEnding,
+}
+
+#[test]
+fn config_roundtrip() {
+    let config = Config {
+        wrap_algorithm: WrapAlgorithm::FirstFit,
+        word_separator: WordSeparator::FixedWidth(4),
+        word_splitter: WordSplitter::NoHyphenation,
+        line_ending: LineEnding::CRLF,
+    };
+    let serialized = toml::to_string(&config).unwrap();
+    assert_eq!(
+        serialized,
+        "wrap_algorithm = \"FirstFit\"\n\
+         word_splitter = \"NoHyphenation\"\n\
+         line_ending = \"CRLF\"\n\
+         \n\
+         [word_separator]\n\
+         FixedWidth = 4\n"
+    );
+
+    let deserialized: Config = toml::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.wrap_algorithm, config.wrap_algorithm);
+    assert_eq!(deserialized.word_separator, config.word_separator);
+    assert_eq!(deserialized.word_splitter, config.word_splitter);
+    assert_eq!(deserialized.line_ending, config.line_ending);
+}
+
+#[test]
+#[cfg(feature = "smawk")]
+fn penalties_from_toml() {
+    use textwrap::wrap_algorithms::Penalties;
+
+    let mut penalties = Penalties::new();
+    penalties.nline_penalty = 0;
+    let serialized = toml::to_string(&penalties).unwrap();
+    let deserialized: Penalties = toml::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, penalties);
+}
+
+#[test]
+fn options_roundtrip() {
+    let options = Options::new(20)
+        .initial_indent("* ")
+        .subsequent_indent("  ")
+        .word_overflow(WordOverflow::Truncate("..."))
+        .wrap_algorithm(WrapAlgorithm::FirstFit);
+    let serialized = bincode::serialize(&options).unwrap();
+    let deserialized: Options = bincode::deserialize(&serialized).unwrap();
+
+    let text = "Deserialized options wrap text like the originals.";
+    assert_eq!(wrap(text, &deserialized), wrap(text, &options));
+    assert_eq!(deserialized.initial_indent, "* ");
+    assert_eq!(deserialized.word_overflow, WordOverflow::Truncate("..."));
+}
+
+#[test]
+fn options_with_owned_indent() {
+    let options = Options::new(14)
+        .initial_indent("\u{2022} ")
+        .subsequent_indent("  ")
+        .wrap_algorithm(WrapAlgorithm::FirstFit)
+        // TOML integers cannot hold the default of usize::MAX.
+        .max_width(80);
+    // A toml::Value owns its strings, so the indentation is copied.
+    let value = toml::Value::try_from(&options).unwrap();
+    let deserialized = Options::deserialize(value).unwrap();
+    assert_eq!(deserialized.initial_indent, "\u{2022} ");
+    assert_eq!(
+        wrap("Owned indentation works.", &deserialized),
+        vec!["\u{2022} Owned", "  indentation", "  works."]
+    );
+}
+
+#[test]
+fn custom_variants_cannot_be_serialized() {
+    fn split_nowhere(_: &str) -> Vec<usize> {
+        Vec::new()
+    }
+    let splitter = WordSplitter::Custom(split_nowhere);
+    assert!(toml::to_string(&splitter).is_err());
+}